@@ -1,6 +1,7 @@
 // Block size in 32 bit words
 pub(super) const AES_BLOCK_SIZE: usize = 4;
 pub(super) const BYTES_PER_WORD: usize = (u32::BITS / u8::BITS) as usize;
+pub(super) const AES_BLOCK_LEN: usize = AES_BLOCK_SIZE * BYTES_PER_WORD;
 
 // Key size in 32 byte words
 pub(super) const AES_KEY_SIZE_128: usize = 4;
@@ -2,7 +2,10 @@ mod aes_128;
 mod aes_192;
 mod aes_256;
 
-use std::ops::Index;
+use std::{
+    fmt::{self, Display, Formatter},
+    ops::Index,
+};
 
 use super::{consts::*, AesBlock};
 
@@ -12,6 +15,36 @@ pub enum AesKeySize {
     Aes256,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyError {
+    Length(usize, usize),
+}
+
+impl Display for KeyError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Length(len, expected_len) => write!(
+                f,
+                "AES key is {} bytes long instead of {}",
+                len, expected_len
+            ),
+        }
+    }
+}
+
+impl std::error::Error for KeyError {}
+
+impl AesKeySize {
+    pub const fn key_len(&self) -> usize {
+        BYTES_PER_WORD
+            * match self {
+                Self::Aes128 => AES_KEY_SIZE_128,
+                Self::Aes192 => AES_KEY_SIZE_192,
+                Self::Aes256 => AES_KEY_SIZE_256,
+            }
+    }
+}
+
 pub(super) struct AesKey {
     key_expansion_strategy: Box<dyn AesKeyExpansionStrategy>,
 }
@@ -34,16 +67,20 @@ struct Aes256KeyExpansionStrategy {
 }
 
 impl AesKey {
-    pub fn new(init_key: &[u8], key_size: AesKeySize) -> Self {
+    pub fn new(init_key: &[u8], key_size: AesKeySize) -> Result<Self, KeyError> {
+        if init_key.len() != key_size.key_len() {
+            return Err(KeyError::Length(init_key.len(), key_size.key_len()));
+        }
+
         let key_expansion_strategy: Box<dyn AesKeyExpansionStrategy> = match key_size {
             AesKeySize::Aes128 => Box::new(Aes128KeyExpansionStrategy::new(init_key)),
             AesKeySize::Aes192 => Box::new(Aes192KeyExpansionStrategy::new(init_key)),
             AesKeySize::Aes256 => Box::new(Aes256KeyExpansionStrategy::new(init_key)),
         };
 
-        Self {
+        Ok(Self {
             key_expansion_strategy,
-        }
+        })
     }
 
     pub fn len(&self) -> usize {
@@ -2,7 +2,10 @@ use crate::consts::{
     AES_BLOCK_SIZE, AES_KEY_SIZE_128, AES_NUM_ROUNDS_128, BYTES_PER_WORD, RCON, SBOX,
 };
 
-use super::{super::Word, Aes128KeyExpansionStrategy, AesBlock, AesKeyExpansionStrategy};
+use super::{
+    super::{rot_word, sub_word, Word},
+    Aes128KeyExpansionStrategy, AesBlock, AesKeyExpansionStrategy,
+};
 
 impl AesKeyExpansionStrategy for Aes128KeyExpansionStrategy {
     fn get_round_key(&self, round_num: usize) -> &AesBlock {
@@ -22,11 +25,6 @@ impl Aes128KeyExpansionStrategy {
     }
 
     fn initialize_key_schedule(key_bytes: &[u8]) -> AesBlock {
-        assert!(
-            key_bytes.len() == BYTES_PER_WORD * AES_KEY_SIZE_128,
-            "AES 128 Generate Key: Invalid key size"
-        );
-
         let mut key = [Word::zero(); AES_KEY_SIZE_128];
 
         for (word, key_word) in key.iter_mut().enumerate() {
@@ -36,7 +34,7 @@ impl Aes128KeyExpansionStrategy {
             *key_word = Word::from(u32::from_be_bytes(
                 key_bytes[left_idx..right_idx]
                     .try_into()
-                    // If the assertion above is affirmed, this will never happen
+                    // AesKey::new checks the key length, so this will never happen
                     .expect("🙀🧨 AES key generation failed. This was not supposed to happen."),
             ));
         }
@@ -49,9 +47,8 @@ impl Aes128KeyExpansionStrategy {
 
         match word {
             0 => {
-                let prev_round_last_word = key[round - 1][AES_BLOCK_SIZE - 1]
-                    .rotate_left(u8::BITS as usize, u32::BITS as usize)
-                    .substitute_bytes(BYTES_PER_WORD, &SBOX);
+                let prev_round_last_word =
+                    sub_word(rot_word(key[round - 1][AES_BLOCK_SIZE - 1]), &SBOX);
                 let round_constant = Word::from(RCON[round - 1]);
 
                 prev_round_word ^ prev_round_last_word ^ round_constant
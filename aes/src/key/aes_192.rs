@@ -2,7 +2,10 @@ use crate::consts::{
     AES_BLOCK_SIZE, AES_KEY_SIZE_192, AES_NUM_ROUNDS_192, BYTES_PER_WORD, RCON, SBOX,
 };
 
-use super::{super::Word, Aes192KeyExpansionStrategy, AesBlock, AesKeyExpansionStrategy};
+use super::{
+    super::{rot_word, sub_word, Word},
+    Aes192KeyExpansionStrategy, AesBlock, AesKeyExpansionStrategy,
+};
 
 const NUM_WORDS: usize = AES_BLOCK_SIZE * AES_NUM_ROUNDS_192;
 
@@ -24,11 +27,6 @@ impl Aes192KeyExpansionStrategy {
     }
 
     fn initialize_key_schedule(key_bytes: &[u8]) -> [Word; AES_KEY_SIZE_192] {
-        assert!(
-            key_bytes.len() == BYTES_PER_WORD * AES_KEY_SIZE_192,
-            "AES 192 Generate Key: Invalid key size"
-        );
-
         let mut key_words = [Word::zero(); AES_KEY_SIZE_192];
 
         for (word, key_word) in key_words.iter_mut().enumerate() {
@@ -37,7 +35,7 @@ impl Aes192KeyExpansionStrategy {
 
             *key_word = Word::from(u32::from_be_bytes(
                 key_bytes[left_idx..right_idx].try_into().expect(
-                    // If the assertion above is affirmed, this will never happen
+                    // AesKey::new checks the key length, so this will never happen
                     "🙀🧨 AES key generation failed. This was not supposed to happen.",
                 ),
             ));
@@ -50,9 +48,7 @@ impl Aes192KeyExpansionStrategy {
         match word_index {
             word if word % AES_KEY_SIZE_192 == 0 => {
                 let prev_round_word = key_words[word - AES_KEY_SIZE_192];
-                let prev_word_transformed = key_words[word - 1]
-                    .rotate_left(u8::BITS as usize, u32::BITS as usize)
-                    .substitute_bytes(BYTES_PER_WORD, &SBOX);
+                let prev_word_transformed = sub_word(rot_word(key_words[word - 1]), &SBOX);
                 let round_constant = Word::from(RCON[word / AES_KEY_SIZE_192 - 1]);
 
                 prev_round_word ^ prev_word_transformed ^ round_constant
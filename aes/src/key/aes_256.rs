@@ -1,8 +1,9 @@
-use crate::consts::{
-    AES_BLOCK_SIZE, AES_KEY_SIZE_256, AES_NUM_ROUNDS_256, BYTES_PER_WORD, RCON, SBOX,
-};
+use crate::consts::{AES_BLOCK_SIZE, AES_NUM_ROUNDS_256, BYTES_PER_WORD, RCON, SBOX};
 
-use super::{super::Word, Aes256KeyExpansionStrategy, AesBlock, AesKeyExpansionStrategy};
+use super::{
+    super::{rot_word, sub_word, Word},
+    Aes256KeyExpansionStrategy, AesBlock, AesKeyExpansionStrategy,
+};
 
 const INIT_ROUNDS: usize = 2;
 
@@ -24,11 +25,6 @@ impl Aes256KeyExpansionStrategy {
     }
 
     fn initialize_key_schedule(key_bytes: &[u8], init_round_num: usize) -> AesBlock {
-        assert!(
-            key_bytes.len() == BYTES_PER_WORD * AES_KEY_SIZE_256,
-            "AES 256 Generate Key: Invalid key size"
-        );
-
         assert!(
             init_round_num < INIT_ROUNDS,
             "AES 256 Initialize Key Schedule: Only the first two are initialization rounds"
@@ -45,7 +41,7 @@ impl Aes256KeyExpansionStrategy {
             *key_word = Word::from(u32::from_be_bytes(
                 key_bytes[left_idx..right_idx]
                     .try_into()
-                    // If the assertion above is affirmed, this will never happen
+                    // AesKey::new checks the key length, so this will never happen
                     .expect("🙀🧨 AES key generation failed. This was not supposed to happen."),
             ));
         }
@@ -54,22 +50,20 @@ impl Aes256KeyExpansionStrategy {
     }
 
     fn continue_key_schedule(key: &[AesBlock], round: usize, word: usize) -> Word {
-        let is_round_even = round % 2 == 0;
+        let is_round_even = round.is_multiple_of(2);
         let two_rounds_back_word = key[round - 2][word];
 
         match word {
             0 if is_round_even => {
                 let prev_word = key[round - 1][AES_BLOCK_SIZE - 1];
-                let substituted_word = prev_word
-                    .rotate_left(u8::BITS as usize, u32::BITS as usize)
-                    .substitute_bytes(BYTES_PER_WORD, &SBOX);
+                let substituted_word = sub_word(rot_word(prev_word), &SBOX);
                 let round_constant = Word::from(RCON[round / 2 - 1]);
 
                 two_rounds_back_word ^ substituted_word ^ round_constant
             }
             0 if !is_round_even => {
                 let prev_word = key[round - 1][AES_BLOCK_SIZE - 1];
-                let substituted_word = prev_word.substitute_bytes(BYTES_PER_WORD, &SBOX);
+                let substituted_word = sub_word(prev_word, &SBOX);
 
                 two_rounds_back_word ^ substituted_word
             }
@@ -105,6 +99,7 @@ impl Aes256KeyExpansionStrategy {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::consts::AES_KEY_SIZE_256;
 
     #[test]
     fn trivial_all_zeros() {
@@ -7,6 +7,21 @@ use key::AesKey;
 
 type AesBlock = [Word; consts::AES_BLOCK_SIZE];
 
-pub struct AesCipher {
+// Words are read from four bytes of a key or state, so they always fit the 32 bits used here
+fn rot_word(word: Word) -> Word {
+    word.rotate_left(u8::BITS as usize, u32::BITS as usize)
+        .expect("🙀🧨 AES word longer than 32 bits. This was not supposed to happen.")
+}
+
+fn sub_word(word: Word, sbox: &[u8; 256]) -> Word {
+    word.substitute_bytes(consts::BYTES_PER_WORD, sbox)
+        .expect("🙀🧨 AES word longer than 32 bits. This was not supposed to happen.")
+}
+
+pub struct Aes128Cipher(AesCipher);
+pub struct Aes192Cipher(AesCipher);
+pub struct Aes256Cipher(AesCipher);
+
+struct AesCipher {
     key: AesKey,
 }
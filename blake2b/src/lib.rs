@@ -0,0 +1,200 @@
+mod consts;
+
+use consts::*;
+
+pub const BLAKE2B_MAX_DIGEST_LEN: usize = 64;
+pub const BLAKE2B_BLOCK_LEN: usize = 128;
+
+// Unkeyed BLAKE2b (RFC 7693) with any digest length from 1 to 64 bytes
+#[derive(Clone)]
+pub struct Blake2b {
+    state: [u64; 8],
+    buffer: Vec<u8>,
+    message_len: u128,
+    digest_len: usize,
+}
+
+impl Blake2b {
+    pub fn new(digest_len: usize) -> Self {
+        assert!(
+            (1..=BLAKE2B_MAX_DIGEST_LEN).contains(&digest_len),
+            "BLAKE2b: Digest length must be between 1 and 64 bytes"
+        );
+
+        let mut state = BLAKE2B_IV;
+        // Parameter block: digest length, no key, fanout and depth of 1
+        state[0] ^= 0x0101_0000 ^ digest_len as u64;
+
+        Self {
+            state,
+            buffer: Vec::with_capacity(BLAKE2B_BLOCK_LEN),
+            message_len: 0,
+            digest_len,
+        }
+    }
+
+    pub fn digest(digest_len: usize, data: &[u8]) -> Vec<u8> {
+        let mut blake2b = Self::new(digest_len);
+        blake2b.update(data);
+
+        blake2b.finalize()
+    }
+
+    // The last block is flagged when compressed, so a full buffer is only flushed once more data follows
+    pub fn update(&mut self, data: &[u8]) {
+        for &byte in data {
+            if self.buffer.len() == BLAKE2B_BLOCK_LEN {
+                let block = std::mem::take(&mut self.buffer);
+                self.message_len += BLAKE2B_BLOCK_LEN as u128;
+                self.compress(&block, false);
+            }
+
+            self.buffer.push(byte);
+        }
+    }
+
+    pub fn finalize(mut self) -> Vec<u8> {
+        self.message_len += self.buffer.len() as u128;
+
+        let mut block = std::mem::take(&mut self.buffer);
+        block.resize(BLAKE2B_BLOCK_LEN, 0);
+        self.compress(&block, true);
+
+        self.state
+            .iter()
+            .flat_map(|word| word.to_le_bytes())
+            .take(self.digest_len)
+            .collect()
+    }
+
+    fn compress(&mut self, block: &[u8], last_block: bool) {
+        let mut message = [0u64; 16];
+
+        for (word, bytes) in message.iter_mut().zip(block.chunks_exact(8)) {
+            *word = u64::from_le_bytes(bytes.try_into().unwrap());
+        }
+
+        let mut v = [0u64; 16];
+        v[..8].copy_from_slice(&self.state);
+        v[8..].copy_from_slice(&BLAKE2B_IV);
+        v[12] ^= self.message_len as u64;
+        v[13] ^= (self.message_len >> u64::BITS) as u64;
+
+        if last_block {
+            v[14] = !v[14];
+        }
+
+        for round in 0..BLAKE2B_ROUNDS {
+            let sigma = &BLAKE2B_SIGMA[round % BLAKE2B_SIGMA.len()];
+
+            mix(&mut v, [0, 4, 8, 12], message[sigma[0]], message[sigma[1]]);
+            mix(&mut v, [1, 5, 9, 13], message[sigma[2]], message[sigma[3]]);
+            mix(&mut v, [2, 6, 10, 14], message[sigma[4]], message[sigma[5]]);
+            mix(&mut v, [3, 7, 11, 15], message[sigma[6]], message[sigma[7]]);
+            mix(&mut v, [0, 5, 10, 15], message[sigma[8]], message[sigma[9]]);
+            mix(
+                &mut v,
+                [1, 6, 11, 12],
+                message[sigma[10]],
+                message[sigma[11]],
+            );
+            mix(
+                &mut v,
+                [2, 7, 8, 13],
+                message[sigma[12]],
+                message[sigma[13]],
+            );
+            mix(
+                &mut v,
+                [3, 4, 9, 14],
+                message[sigma[14]],
+                message[sigma[15]],
+            );
+        }
+
+        for (i, word) in self.state.iter_mut().enumerate() {
+            *word ^= v[i] ^ v[i + 8];
+        }
+    }
+}
+
+fn mix(v: &mut [u64; 16], [a, b, c, d]: [usize; 4], x: u64, y: u64) {
+    v[a] = v[a].wrapping_add(v[b]).wrapping_add(x);
+    v[d] = (v[d] ^ v[a]).rotate_right(32);
+    v[c] = v[c].wrapping_add(v[d]);
+    v[b] = (v[b] ^ v[c]).rotate_right(24);
+    v[a] = v[a].wrapping_add(v[b]).wrapping_add(y);
+    v[d] = (v[d] ^ v[a]).rotate_right(16);
+    v[c] = v[c].wrapping_add(v[d]);
+    v[b] = (v[b] ^ v[c]).rotate_right(63);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // RFC 7693, appendix A
+    #[test]
+    fn digest_abc() {
+        let left = Blake2b::digest(64, b"abc");
+        let right = [
+            0xBA, 0x80, 0xA5, 0x3F, 0x98, 0x1C, 0x4D, 0x0D, 0x6A, 0x27, 0x97, 0xB6, 0x9F, 0x12,
+            0xF6, 0xE9, 0x4C, 0x21, 0x2F, 0x14, 0x68, 0x5A, 0xC4, 0xB7, 0x4B, 0x12, 0xBB, 0x6F,
+            0xDB, 0xFF, 0xA2, 0xD1, 0x7D, 0x87, 0xC5, 0x39, 0x2A, 0xAB, 0x79, 0x2D, 0xC2, 0x52,
+            0xD5, 0xDE, 0x45, 0x33, 0xCC, 0x95, 0x18, 0xD3, 0x8A, 0xA8, 0xDB, 0xF1, 0x92, 0x5A,
+            0xB9, 0x23, 0x86, 0xED, 0xD4, 0x00, 0x99, 0x23,
+        ];
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn digest_empty() {
+        let left = Blake2b::digest(64, b"");
+        let right = [
+            0x78, 0x6A, 0x02, 0xF7, 0x42, 0x01, 0x59, 0x03, 0xC6, 0xC6, 0xFD, 0x85, 0x25, 0x52,
+            0xD2, 0x72, 0x91, 0x2F, 0x47, 0x40, 0xE1, 0x58, 0x47, 0x61, 0x8A, 0x86, 0xE2, 0x17,
+            0xF7, 0x1F, 0x54, 0x19, 0xD2, 0x5E, 0x10, 0x31, 0xAF, 0xEE, 0x58, 0x53, 0x13, 0x89,
+            0x64, 0x44, 0x93, 0x4E, 0xB0, 0x4B, 0x90, 0x3A, 0x68, 0x5B, 0x14, 0x48, 0xB7, 0x55,
+            0xD5, 0x6F, 0x70, 0x1A, 0xFE, 0x9B, 0xE2, 0xCE,
+        ];
+
+        assert_eq!(left, right);
+    }
+
+    // Ten full blocks, the last one must not be compressed before finalizing
+    #[test]
+    fn digest_short_output_block_aligned() {
+        let message: Vec<u8> = (0..=255).cycle().take(1280).collect();
+
+        let left = Blake2b::digest(32, &message);
+        let right = [
+            0x82, 0x62, 0x8C, 0xBF, 0xC9, 0x68, 0x9E, 0x23, 0x4B, 0x09, 0x23, 0xA5, 0x31, 0xF4,
+            0x57, 0x8F, 0xE2, 0xE7, 0x13, 0x8A, 0x03, 0xE2, 0xF8, 0x1E, 0xD6, 0xCD, 0xE9, 0x75,
+            0x17, 0x33, 0x66, 0x50,
+        ];
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn update_in_pieces() {
+        let message: Vec<u8> = (0..=255).cycle().take(1000).collect();
+
+        let mut blake2b = Blake2b::new(48);
+        for piece in message.chunks(129) {
+            blake2b.update(piece);
+        }
+
+        let left = blake2b.finalize();
+        let right = Blake2b::digest(48, &message);
+
+        assert_eq!(left, right);
+    }
+
+    #[should_panic]
+    #[test]
+    fn digest_too_long() {
+        Blake2b::new(65);
+    }
+}
@@ -0,0 +1,155 @@
+use std::fmt::{self, Display, Formatter};
+
+// Block and key sizes are in bytes. The single-block methods panic unless they are given
+// exactly one block, the multi-block ones report misaligned buffers instead.
+pub trait BlockCipher: Sized {
+    const BLOCK_SIZE: usize;
+    const KEY_SIZE: usize;
+
+    type KeyError: std::error::Error;
+
+    fn new(key: &[u8]) -> Result<Self, Self::KeyError>;
+
+    fn encrypt_block(&self, block: &mut [u8]);
+    fn decrypt_block(&self, block: &mut [u8]);
+
+    fn encrypt_blocks(&self, blocks: &mut [u8]) -> Result<(), BlockLengthError> {
+        check_block_alignment::<Self>(blocks)?;
+
+        blocks
+            .chunks_exact_mut(Self::BLOCK_SIZE)
+            .for_each(|block| self.encrypt_block(block));
+
+        Ok(())
+    }
+
+    fn decrypt_blocks(&self, blocks: &mut [u8]) -> Result<(), BlockLengthError> {
+        check_block_alignment::<Self>(blocks)?;
+
+        blocks
+            .chunks_exact_mut(Self::BLOCK_SIZE)
+            .for_each(|block| self.decrypt_block(block));
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockLengthError {
+    pub len: usize,
+    pub block_size: usize,
+}
+
+impl Display for BlockLengthError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Buffer of {} bytes is not a multiple of the {} byte block",
+            self.len, self.block_size
+        )
+    }
+}
+
+impl std::error::Error for BlockLengthError {}
+
+fn check_block_alignment<C: BlockCipher>(blocks: &[u8]) -> Result<(), BlockLengthError> {
+    if !blocks.len().is_multiple_of(C::BLOCK_SIZE) {
+        return Err(BlockLengthError {
+            len: blocks.len(),
+            block_size: C::BLOCK_SIZE,
+        });
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use super::*;
+
+    // Adds the key to every byte, enough to tell blocks and directions apart
+    struct ByteShiftCipher {
+        key: u8,
+    }
+
+    impl BlockCipher for ByteShiftCipher {
+        const BLOCK_SIZE: usize = 4;
+        const KEY_SIZE: usize = 1;
+
+        type KeyError = Infallible;
+
+        fn new(key: &[u8]) -> Result<Self, Infallible> {
+            Ok(Self { key: key[0] })
+        }
+
+        fn encrypt_block(&self, block: &mut [u8]) {
+            assert_eq!(block.len(), Self::BLOCK_SIZE);
+
+            block
+                .iter_mut()
+                .for_each(|byte| *byte = byte.wrapping_add(self.key));
+        }
+
+        fn decrypt_block(&self, block: &mut [u8]) {
+            assert_eq!(block.len(), Self::BLOCK_SIZE);
+
+            block
+                .iter_mut()
+                .for_each(|byte| *byte = byte.wrapping_sub(self.key));
+        }
+    }
+
+    #[test]
+    fn encrypt_blocks() {
+        let cipher = ByteShiftCipher::new(&[0x10]).unwrap();
+        let mut blocks = [0x00, 0x01, 0x02, 0x03, 0xF0, 0xF1, 0xF2, 0xF3];
+
+        cipher.encrypt_blocks(&mut blocks).unwrap();
+
+        let left = blocks;
+        let right = [0x10, 0x11, 0x12, 0x13, 0x00, 0x01, 0x02, 0x03];
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn encrypt_then_decrypt_blocks() {
+        let cipher = ByteShiftCipher::new(&[0x10]).unwrap();
+        let mut blocks = [0x00, 0x01, 0x02, 0x03, 0xF0, 0xF1, 0xF2, 0xF3];
+
+        cipher.encrypt_blocks(&mut blocks).unwrap();
+        cipher.decrypt_blocks(&mut blocks).unwrap();
+
+        let left = blocks;
+        let right = [0x00, 0x01, 0x02, 0x03, 0xF0, 0xF1, 0xF2, 0xF3];
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn misaligned_blocks_rejected() {
+        let cipher = ByteShiftCipher::new(&[0x10]).unwrap();
+        let mut blocks = [0x00; 6];
+
+        let left = cipher.encrypt_blocks(&mut blocks);
+        let right = Err(BlockLengthError {
+            len: 6,
+            block_size: 4,
+        });
+
+        assert_eq!(left, right);
+        assert_eq!(blocks, [0x00; 6]);
+    }
+
+    #[test]
+    fn empty_buffer_accepted() {
+        let cipher = ByteShiftCipher::new(&[0x10]).unwrap();
+
+        let left = cipher.decrypt_blocks(&mut []);
+        let right = Ok(());
+
+        assert_eq!(left, right);
+    }
+}
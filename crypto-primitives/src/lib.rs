@@ -1,3 +1,4 @@
+pub mod block_cipher;
 pub mod word;
 
 #[derive(Clone, Copy, Default, PartialEq, Eq)]
@@ -1,6 +1,7 @@
 use super::Word;
 
 pub mod bitwise_ops;
+pub mod error;
 pub mod fmt;
 pub mod from;
 pub mod ops;
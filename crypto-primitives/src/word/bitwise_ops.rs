@@ -1,63 +1,51 @@
-use super::Word;
+use super::{error::WordError, Word};
 
 impl Word {
-    pub fn split(self, subword_len: usize) -> (Self, Self) {
-        assert!(
-            subword_len <= Self::MAX_LENGTH / 2 && subword_len > 0,
-            "Word: attempt to split word into subwords with invalid length"
-        );
+    pub fn split(self, subword_len: usize) -> Result<(Self, Self), WordError> {
+        if subword_len > Self::MAX_LENGTH / 2 || subword_len == 0 {
+            return Err(WordError::InvalidSubwordLength);
+        }
 
-        assert!(
-            self.len() <= subword_len * 2,
-            "Word: attempt to split word into subwords shorter than half the word length"
-        );
+        if self.len() > subword_len * 2 {
+            return Err(WordError::WordLongerThanLength);
+        }
 
         let right_mask = Self::ones(subword_len);
 
-        (self >> subword_len, self & right_mask)
+        Ok((self >> subword_len, self & right_mask))
     }
 
-    pub fn concatenate(self, rhs: Self, subword_len: usize) -> Self {
+    pub fn concatenate(self, rhs: Self, subword_len: usize) -> Result<Self, WordError> {
         let max_subword_len = Self::MAX_LENGTH / 2;
 
-        assert!(
-            subword_len != 0,
-            "Word: attempt to concatenate words of length 0"
-        );
-
-        assert!(
-            subword_len <= max_subword_len && subword_len > 0,
-            "Word: attempt to concatenate words with invalid subword length"
-        );
+        if subword_len > max_subword_len || subword_len == 0 {
+            return Err(WordError::InvalidSubwordLength);
+        }
 
-        assert!(
-            self.len() <= max_subword_len && rhs.len() <= max_subword_len,
-            "Word: concatenated word longer than desired subword length"
-        );
+        if self.len() > max_subword_len || rhs.len() > max_subword_len {
+            return Err(WordError::WordLongerThanLength);
+        }
 
-        self << subword_len | rhs
+        Ok(self << subword_len | rhs)
     }
 
-    pub fn rotate_left(self, rotate_by: usize, word_len: usize) -> Self {
-        assert!(
-            word_len <= Self::MAX_LENGTH,
-            "Word: attempted rotation with mask longer than 64 bits"
-        );
+    pub fn rotate_left(self, rotate_by: usize, word_len: usize) -> Result<Self, WordError> {
+        if word_len > Self::MAX_LENGTH {
+            return Err(WordError::Overflow);
+        }
 
         // No cyclic rotations
-        assert!(
-            rotate_by <= word_len,
-            "Word: attempted rotation longer than mask"
-        );
+        if rotate_by > word_len {
+            return Err(WordError::RotationLongerThanLength);
+        }
 
-        assert!(
-            self.len() <= word_len,
-            "Word: attempted rotation on word longer than mask"
-        );
+        if self.len() > word_len {
+            return Err(WordError::WordLongerThanLength);
+        }
 
         // Idempotent operation
         if word_len == rotate_by || (word_len == Self::MAX_LENGTH && rotate_by == 0) {
-            return self;
+            return Ok(self);
         }
 
         let right_mask = Self::ones(word_len) >> rotate_by;
@@ -66,58 +54,59 @@ impl Word {
         let left = self & left_mask;
         let right = self & right_mask;
 
-        right << rotate_by | left >> (word_len - rotate_by)
+        Ok(right << rotate_by | left >> (word_len - rotate_by))
     }
 
-    pub fn permute_bits(self, word_len: usize, permutation_array: &[usize]) -> Self {
-        assert!(
-            word_len <= Self::MAX_LENGTH && permutation_array.len() <= Self::MAX_LENGTH,
-            "Word: attempted permutation would result in overflow"
-        );
-
-        assert!(
-            !permutation_array.is_empty(),
-            "Word: attempted permutation with empty permutation array"
-        );
-
-        assert!(
-            self.len() <= word_len,
-            "Word: word length parameter less than actual word length"
-        );
-
-        for entry in permutation_array.iter() {
-            assert!(
-                *entry != 0 && *entry <= word_len,
-                "Word: attempted permutation with indices out of bound"
-            );
+    pub fn permute_bits(
+        self,
+        word_len: usize,
+        permutation_array: &[usize],
+    ) -> Result<Self, WordError> {
+        if word_len > Self::MAX_LENGTH || permutation_array.len() > Self::MAX_LENGTH {
+            return Err(WordError::Overflow);
+        }
+
+        if permutation_array.is_empty() {
+            return Err(WordError::EmptyPermutation);
+        }
+
+        if self.len() > word_len {
+            return Err(WordError::WordLongerThanLength);
+        }
+
+        if let Some(&entry) = permutation_array
+            .iter()
+            .find(|&&entry| entry == 0 || entry > word_len)
+        {
+            return Err(WordError::IndexOutOfBounds(entry));
         }
 
         let mut output = Self::zero();
 
         for (i, &j) in permutation_array.iter().enumerate() {
-            if j <= word_len {
-                let bit = (self >> (word_len - j)) & Self::one();
-                let position = permutation_array.len() - i - 1;
+            let bit = (self >> (word_len - j)) & Self::one();
+            let position = permutation_array.len() - i - 1;
 
-                output |= bit << position;
-            }
+            output |= bit << position;
         }
 
-        output
+        Ok(output)
     }
 
-    pub fn substitute_bytes(self, word_len_bytes: usize, substitution_array: &[u8; 256]) -> Self {
+    pub fn substitute_bytes(
+        self,
+        word_len_bytes: usize,
+        substitution_array: &[u8; 256],
+    ) -> Result<Self, WordError> {
         const BITS_IN_WORD: usize = Word::MAX_LENGTH / u8::BITS as usize;
 
-        assert!(
-            word_len_bytes <= BITS_IN_WORD,
-            "Word: attempted substitution with word length longer than 8 bytes"
-        );
+        if word_len_bytes > BITS_IN_WORD {
+            return Err(WordError::Overflow);
+        }
 
-        assert!(
-            word_len_bytes * u8::BITS as usize >= self.len(),
-            "Word: attempted substitution with word length less than actual word length"
-        );
+        if word_len_bytes * (u8::BITS as usize) < self.len() {
+            return Err(WordError::WordLongerThanLength);
+        }
 
         let index_from = BITS_IN_WORD - word_len_bytes;
         let mut bytes: [u8; BITS_IN_WORD] = self.into();
@@ -127,36 +116,42 @@ impl Word {
             *byte = substitution_array[index];
         }
 
-        Word::from(bytes)
+        Ok(Word::from(bytes))
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::word::tests::*;
+    use crate::word::{error::WordError, tests::*};
     use crate::Word;
 
     mod split {
         use super::*;
 
         #[test]
-        #[should_panic]
         fn subword_len_0() {
-            Word::from(WORD_LEN_47).split(0usize);
+            let left = Word::from(WORD_LEN_47).split(0usize);
+            let right = Err(WordError::InvalidSubwordLength);
+
+            assert_eq!(left, right);
         }
 
         #[test]
-        #[should_panic]
         fn subword_len_shorter_than_half_of_actual_word() {
             const WORD_LEN: usize = 16;
 
-            Word::ones(WORD_LEN).split(WORD_LEN / 2 - 1);
+            let left = Word::ones(WORD_LEN).split(WORD_LEN / 2 - 1);
+            let right = Err(WordError::WordLongerThanLength);
+
+            assert_eq!(left, right);
         }
 
         #[test]
-        #[should_panic]
         fn subword_len_too_long() {
-            Word::from(WORD_LEN_47).split(Word::MAX_LENGTH + 1);
+            let left = Word::from(WORD_LEN_47).split(Word::MAX_LENGTH + 1);
+            let right = Err(WordError::InvalidSubwordLength);
+
+            assert_eq!(left, right);
         }
 
         #[test]
@@ -164,7 +159,7 @@ mod tests {
             const SW_LEN: usize = 28;
             let w = Word::from(WORD_LEN_52);
 
-            let (left_l, left_r) = w.split(SW_LEN);
+            let (left_l, left_r) = w.split(SW_LEN).unwrap();
             let (right_l, right_r) = (
                 Word::from(0b0000_1011_1101_0101_0111_0111_0001u64),
                 Word::from(0b0001_0011_1101_0100_0011_0101_1000u64),
@@ -184,7 +179,7 @@ mod tests {
 
         #[test]
         fn trivial_ones_max_len() {
-            let (left_l, left_r) = Word::MAX.split(Word::MAX_LENGTH / 2);
+            let (left_l, left_r) = Word::MAX.split(Word::MAX_LENGTH / 2).unwrap();
             let (right_l, right_r) = (
                 Word::ones(Word::MAX_LENGTH / 2),
                 Word::ones(Word::MAX_LENGTH / 2),
@@ -198,39 +193,47 @@ mod tests {
         use super::*;
 
         #[test]
-        #[should_panic]
         fn subword_len_0() {
             const SW_LEN: usize = 0;
             let rhs = Word::one();
 
-            Word::ones(Word::MAX_LENGTH / 2).concatenate(rhs, SW_LEN);
+            let left = Word::ones(Word::MAX_LENGTH / 2).concatenate(rhs, SW_LEN);
+            let right = Err(WordError::InvalidSubwordLength);
+
+            assert_eq!(left, right);
         }
 
         #[test]
-        #[should_panic]
         fn subword_len_too_long() {
             const SW_LEN: usize = 33;
             let rhs = Word::one();
 
-            Word::ones(Word::MAX_LENGTH / 2).concatenate(rhs, SW_LEN);
+            let left = Word::ones(Word::MAX_LENGTH / 2).concatenate(rhs, SW_LEN);
+            let right = Err(WordError::InvalidSubwordLength);
+
+            assert_eq!(left, right);
         }
 
         #[test]
-        #[should_panic]
         fn lhs_too_long() {
             const SW_LEN: usize = 32;
             let rhs = Word::one();
 
-            Word::ones(Word::MAX_LENGTH / 2 + 1).concatenate(rhs, SW_LEN);
+            let left = Word::ones(Word::MAX_LENGTH / 2 + 1).concatenate(rhs, SW_LEN);
+            let right = Err(WordError::WordLongerThanLength);
+
+            assert_eq!(left, right);
         }
 
         #[test]
-        #[should_panic]
         fn rhs_too_long() {
             const SW_LEN: usize = 32;
             let rhs = Word::ones(Word::MAX_LENGTH / 2 + 1);
 
-            Word::ones(Word::MAX_LENGTH / 2).concatenate(rhs, SW_LEN);
+            let left = Word::ones(Word::MAX_LENGTH / 2).concatenate(rhs, SW_LEN);
+            let right = Err(WordError::WordLongerThanLength);
+
+            assert_eq!(left, right);
         }
 
         #[test]
@@ -239,7 +242,9 @@ mod tests {
             const LEFT_SW: u64 = 0b0000_1011_1101_0101_0111_0111_0001;
             const RIGHT_SW: u64 = 0b0001_0011_1101_0100_0011_0101_1000;
 
-            let left = Word::from(LEFT_SW).concatenate(Word::from(RIGHT_SW), SW_LEN);
+            let left = Word::from(LEFT_SW)
+                .concatenate(Word::from(RIGHT_SW), SW_LEN)
+                .unwrap();
             let right = Word::from(WORD_LEN_52);
 
             assert_eq!(left, right);
@@ -253,7 +258,9 @@ mod tests {
             const WORD: u64 =
                 0b0000_0000_1011_1101_0101_0111_0111_0001_0000_0001_0011_1101_0100_0011_0101_1000;
 
-            let left = Word::from(LEFT_SW).concatenate(Word::from(RIGHT_SW), SW_LEN);
+            let left = Word::from(LEFT_SW)
+                .concatenate(Word::from(RIGHT_SW), SW_LEN)
+                .unwrap();
             let right = Word::from(WORD);
 
             assert_eq!(left, right);
@@ -261,7 +268,7 @@ mod tests {
 
         #[test]
         fn min_subwords() {
-            let left = Word::MIN.concatenate(Word::MIN, Word::MIN.len());
+            let left = Word::MIN.concatenate(Word::MIN, Word::MIN.len()).unwrap();
             let right = Word::MIN;
 
             assert_eq!(left, right);
@@ -270,7 +277,9 @@ mod tests {
         #[test]
         fn max_subwords() {
             const SW_LEN: usize = Word::MAX_LENGTH / 2;
-            let left = Word::ones(SW_LEN).concatenate(Word::ones(SW_LEN), SW_LEN);
+            let left = Word::ones(SW_LEN)
+                .concatenate(Word::ones(SW_LEN), SW_LEN)
+                .unwrap();
             let right = Word::MAX;
 
             assert_eq!(left, right);
@@ -281,33 +290,39 @@ mod tests {
         use super::*;
 
         #[test]
-        #[should_panic]
         fn rotation_longer_than_mask() {
             const WORD_LEN: usize = 28;
             const ROTATE_BY: usize = WORD_LEN + 1;
 
             let w = Word::from(WORD_LEN_47);
-            w.rotate_left(ROTATE_BY, WORD_LEN);
+            let left = w.rotate_left(ROTATE_BY, WORD_LEN);
+            let right = Err(WordError::RotationLongerThanLength);
+
+            assert_eq!(left, right);
         }
 
         #[test]
-        #[should_panic]
         fn rotation_shorter_than_word() {
             const WORD_LEN: usize = 28;
             const ROTATE_BY: usize = 22;
 
             let w = Word::from(WORD_LEN_47);
-            w.rotate_left(ROTATE_BY, WORD_LEN);
+            let left = w.rotate_left(ROTATE_BY, WORD_LEN);
+            let right = Err(WordError::WordLongerThanLength);
+
+            assert_eq!(left, right);
         }
 
         #[test]
-        #[should_panic]
         fn mask_too_long() {
             const WORD_LEN: usize = Word::MAX_LENGTH + 1;
             const ROTATE_BY: usize = 28;
 
             let w = Word::from(WORD_LEN_47);
-            w.rotate_left(ROTATE_BY, WORD_LEN);
+            let left = w.rotate_left(ROTATE_BY, WORD_LEN);
+            let right = Err(WordError::Overflow);
+
+            assert_eq!(left, right);
         }
 
         #[test]
@@ -316,7 +331,7 @@ mod tests {
 
             let w = Word::from(WORD_LEN_47);
 
-            let left = w.rotate_left(ROTATE_BY, w.len());
+            let left = w.rotate_left(ROTATE_BY, w.len()).unwrap();
             let right = w;
 
             assert_eq!(left, right);
@@ -326,7 +341,7 @@ mod tests {
         fn full_rotation() {
             let w = Word::from(WORD_LEN_47);
 
-            let left = w.rotate_left(w.len(), w.len());
+            let left = w.rotate_left(w.len(), w.len()).unwrap();
             let right = w;
 
             assert_eq!(left, right);
@@ -339,7 +354,7 @@ mod tests {
 
             let w = Word::from(WORD_LEN_47);
 
-            let left = w.rotate_left(ROTATE_BY, WORD_LEN);
+            let left = w.rotate_left(ROTATE_BY, WORD_LEN).unwrap();
             let right = w;
 
             assert_eq!(left, right);
@@ -350,7 +365,9 @@ mod tests {
             const WORD_LEN: usize = 28;
             const ROTATE_BY: usize = 4;
 
-            let left = Word::from(WORD_LEN_28).rotate_left(ROTATE_BY, WORD_LEN);
+            let left = Word::from(WORD_LEN_28)
+                .rotate_left(ROTATE_BY, WORD_LEN)
+                .unwrap();
             let right = Word::from(0b0000_1100_1100_1010_1010_1001_1111u64);
 
             assert_eq!(left, right);
@@ -361,7 +378,9 @@ mod tests {
             const WORD_LEN: usize = 64;
             const ROTATE_BY: usize = WORD_LEN - 1;
 
-            let left = Word::from(WORD_LEN_60).rotate_left(ROTATE_BY, WORD_LEN);
+            let left = Word::from(WORD_LEN_60)
+                .rotate_left(ROTATE_BY, WORD_LEN)
+                .unwrap();
             let right = Word::from(
                 0b1000_0101_0111_0101_0010_1110_0111_0001_0000_1001_1011_0101_0000_0110_0101_1000u64
             );
@@ -374,75 +393,90 @@ mod tests {
         use super::*;
 
         #[test]
-        #[should_panic]
         fn word_len_zero() {
             const WORD_LEN: usize = 0;
             const ARRAY_LEN: usize = 4;
             const PERMUTATION_ARRAY: [usize; ARRAY_LEN] = [10; ARRAY_LEN];
 
-            Word::from(WORD_LEN_28).permute_bits(WORD_LEN, &PERMUTATION_ARRAY);
+            let left = Word::from(WORD_LEN_28).permute_bits(WORD_LEN, &PERMUTATION_ARRAY);
+            let right = Err(WordError::WordLongerThanLength);
+
+            assert_eq!(left, right);
         }
 
         #[test]
-        #[should_panic]
         fn word_len_longer_than_max() {
             const WORD_LEN: usize = Word::MAX_LENGTH + 1;
             const ARRAY_LEN: usize = 4;
             const PERMUTATION_ARRAY: [usize; ARRAY_LEN] = [10; ARRAY_LEN];
 
-            Word::from(WORD_LEN_28).permute_bits(WORD_LEN, &PERMUTATION_ARRAY);
+            let left = Word::from(WORD_LEN_28).permute_bits(WORD_LEN, &PERMUTATION_ARRAY);
+            let right = Err(WordError::Overflow);
+
+            assert_eq!(left, right);
         }
 
         #[test]
-        #[should_panic]
         fn permutation_array_empty() {
             const EMPTY_PERMUTATION_ARRAY: [usize; 0] = [];
 
-            Word::from(WORD_LEN_28).permute_bits(30, &EMPTY_PERMUTATION_ARRAY);
+            let left = Word::from(WORD_LEN_28).permute_bits(30, &EMPTY_PERMUTATION_ARRAY);
+            let right = Err(WordError::EmptyPermutation);
+
+            assert_eq!(left, right);
         }
 
         #[test]
-        #[should_panic]
         fn permutation_array_too_long() {
             const WORD_LEN: usize = 30;
             const ARRAY_LEN: usize = Word::MAX_LENGTH + 1;
             const TOO_LONG_PERMUTATION_ARRAY: [usize; ARRAY_LEN] = [6; ARRAY_LEN];
 
-            Word::from(WORD_LEN_28).permute_bits(WORD_LEN, &TOO_LONG_PERMUTATION_ARRAY);
+            let left = Word::from(WORD_LEN_28).permute_bits(WORD_LEN, &TOO_LONG_PERMUTATION_ARRAY);
+            let right = Err(WordError::Overflow);
+
+            assert_eq!(left, right);
         }
 
         #[test]
-        #[should_panic]
         fn permutation_array_with_zero_entries() {
             const WORD_LEN: usize = 30;
             const ARRAY_LEN: usize = 16;
             const PERMUTATION_ARRAY_WITH_ZEROS: [usize; ARRAY_LEN] = [0; ARRAY_LEN];
 
-            Word::from(WORD_LEN_28).permute_bits(WORD_LEN, &PERMUTATION_ARRAY_WITH_ZEROS);
+            let left =
+                Word::from(WORD_LEN_28).permute_bits(WORD_LEN, &PERMUTATION_ARRAY_WITH_ZEROS);
+            let right = Err(WordError::IndexOutOfBounds(0));
+
+            assert_eq!(left, right);
         }
 
         #[test]
-        #[should_panic]
         fn permutation_array_with_entries_larger_than_word_len() {
             const WORD_LEN: usize = 30;
             const ARRAY_LEN: usize = 13;
             const PERMUTATION_ARRAY_WITH_TOO_LARGE_ENTRIES: [usize; ARRAY_LEN] =
                 [WORD_LEN + 1; ARRAY_LEN];
 
-            Word::from(WORD_LEN_28)
+            let left = Word::from(WORD_LEN_28)
                 .permute_bits(WORD_LEN, &PERMUTATION_ARRAY_WITH_TOO_LARGE_ENTRIES);
+            let right = Err(WordError::IndexOutOfBounds(WORD_LEN + 1));
+
+            assert_eq!(left, right);
         }
 
         #[test]
-        #[should_panic]
         fn permutation_array_with_entries_larger_than_max_word_len() {
             const WORD_LEN: usize = 30;
             const ARRAY_LEN: usize = 13;
             const PERMUTATION_ARRAY_WITH_TOO_LARGE_ENTRIES: [usize; ARRAY_LEN] =
                 [Word::MAX_LENGTH + 1; ARRAY_LEN];
 
-            Word::from(WORD_LEN_28)
+            let left = Word::from(WORD_LEN_28)
                 .permute_bits(WORD_LEN, &PERMUTATION_ARRAY_WITH_TOO_LARGE_ENTRIES);
+            let right = Err(WordError::IndexOutOfBounds(Word::MAX_LENGTH + 1));
+
+            assert_eq!(left, right);
         }
 
         #[test]
@@ -454,7 +488,7 @@ mod tests {
             let w = Word::from(0b1100_1010u64);
 
             let left = w.permute_bits(WORD_LEN, &PERMUTATION_ARRAY);
-            let right = w;
+            let right = Ok(w);
 
             assert_eq!(left, right);
         }
@@ -466,7 +500,7 @@ mod tests {
             const PERMUTATION_ARRAY: [usize; ARRAY_LEN] = [8, 7, 6, 5, 4, 3, 2, 1];
 
             let left = Word::from(0b1100_1010u64).permute_bits(WORD_LEN, &PERMUTATION_ARRAY);
-            let right = Word::from(0b0101_0011u64);
+            let right = Ok(Word::from(0b0101_0011u64));
 
             assert_eq!(left, right);
         }
@@ -479,7 +513,7 @@ mod tests {
                 [8, 7, 6, 5, 4, 3, 2, 1, 1, 2, 3, 4, 5, 6, 7, 8];
 
             let left = Word::from(0b1100_1010u64).permute_bits(WORD_LEN, &PERMUTATION_ARRAY);
-            let right = Word::from(0b0101_0011_1100_1010u64);
+            let right = Ok(Word::from(0b0101_0011_1100_1010u64));
 
             assert_eq!(left, right);
         }
@@ -492,7 +526,7 @@ mod tests {
                 [5, 6, 7, 8, 1, 2, 3, 4, 5, 6, 7, 8, 1, 2, 3, 4];
 
             let left = Word::from(0xau64).permute_bits(WORD_LEN, &PERMUTATION_ARRAY);
-            let right = Word::from(0xA0A0u64);
+            let right = Ok(Word::from(0xA0A0u64));
 
             assert_eq!(left, right);
         }
@@ -508,14 +542,17 @@ mod tests {
 
             let left =
                 Word::from(0xAAAA_AAAA_AAAA_AAAAu64).permute_bits(WORD_LEN, &PERMUTATION_ARRAY);
-            let right = Word::from(0xFFFF_FFFFu64);
+            let right = Ok(Word::from(0xFFFF_FFFFu64));
 
             assert_eq!(left, right);
         }
     }
 
     mod substitute_bytes {
-        use crate::{word::tests::WORD_LEN_60, Word};
+        use crate::{
+            word::{error::WordError, tests::WORD_LEN_60},
+            Word,
+        };
 
         const SUBSTITUTION_ARRAY: [u8; 256] = [
             0x63, 0x7C, 0x77, 0x7B, 0xF2, 0x6B, 0x6F, 0xC5, 0x30, 0x01, 0x67, 0x2B, 0xFE, 0xD7,
@@ -540,20 +577,26 @@ mod tests {
         ];
 
         #[test]
-        #[should_panic(expected = "word length longer than 8 bytes")]
         fn word_len_too_long() {
-            Word::from(0xFFFF_FFFFu64).substitute_bytes(9, &SUBSTITUTION_ARRAY);
+            let left = Word::from(0xFFFF_FFFFu64).substitute_bytes(9, &SUBSTITUTION_ARRAY);
+            let right = Err(WordError::Overflow);
+
+            assert_eq!(left, right);
         }
 
         #[test]
-        #[should_panic(expected = "word length less than actual word length")]
         fn word_len_less_than_actual_word_len() {
-            Word::from(0xFFFF_FFFFu64).substitute_bytes(3, &SUBSTITUTION_ARRAY);
+            let left = Word::from(0xFFFF_FFFFu64).substitute_bytes(3, &SUBSTITUTION_ARRAY);
+            let right = Err(WordError::WordLongerThanLength);
+
+            assert_eq!(left, right);
         }
 
         #[test]
         fn trivial_zero_6_bytes() {
-            let left = Word::zero().substitute_bytes(6, &SUBSTITUTION_ARRAY);
+            let left = Word::zero()
+                .substitute_bytes(6, &SUBSTITUTION_ARRAY)
+                .unwrap();
             let right = Word::from([0x0u8, 0x0, 0x63, 0x63, 0x63, 0x63, 0x63, 0x63]);
 
             assert_eq!(left, right);
@@ -561,7 +604,9 @@ mod tests {
 
         #[test]
         fn trivial_zero_full_len() {
-            let left = Word::zero().substitute_bytes(8, &SUBSTITUTION_ARRAY);
+            let left = Word::zero()
+                .substitute_bytes(8, &SUBSTITUTION_ARRAY)
+                .unwrap();
             let right = Word::from([0x63u8; 8]);
 
             assert_eq!(left, right);
@@ -569,7 +614,9 @@ mod tests {
 
         #[test]
         fn trivial_all_ones_5_bytes() {
-            let left = Word::from(u32::MAX).substitute_bytes(5, &SUBSTITUTION_ARRAY);
+            let left = Word::from(u32::MAX)
+                .substitute_bytes(5, &SUBSTITUTION_ARRAY)
+                .unwrap();
             let right = Word::from([0x0u8, 0x0, 0x0, 0x63, 0x16, 0x16, 0x16, 0x16]);
 
             assert_eq!(left, right);
@@ -577,7 +624,7 @@ mod tests {
 
         #[test]
         fn trivial_all_ones_full_len() {
-            let left = Word::MAX.substitute_bytes(8, &SUBSTITUTION_ARRAY);
+            let left = Word::MAX.substitute_bytes(8, &SUBSTITUTION_ARRAY).unwrap();
             let right = Word::from([0x16u8; 8]);
 
             assert_eq!(left, right);
@@ -585,7 +632,9 @@ mod tests {
 
         #[test]
         fn non_trivial_full_len() {
-            let left = Word::from(WORD_LEN_60).substitute_bytes(8, &SUBSTITUTION_ARRAY);
+            let left = Word::from(WORD_LEN_60)
+                .substitute_bytes(8, &SUBSTITUTION_ARRAY)
+                .unwrap();
             let right = Word::from(0x6787_4A98_7D02_FEC8u64);
 
             assert_eq!(left, right);
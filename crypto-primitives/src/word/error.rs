@@ -0,0 +1,45 @@
+use std::fmt::Display;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WordError {
+    Overflow,
+    EmptyPermutation,
+    WordLongerThanLength,
+    IndexOutOfBounds(usize),
+    InvalidSubwordLength,
+    RotationLongerThanLength,
+}
+
+impl Display for WordError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Overflow => write!(f, "Word: attempted operation would result in overflow"),
+            Self::EmptyPermutation => {
+                write!(
+                    f,
+                    "Word: attempted permutation with empty permutation array"
+                )
+            }
+            Self::WordLongerThanLength => {
+                write!(
+                    f,
+                    "Word: word length parameter less than actual word length"
+                )
+            }
+            Self::IndexOutOfBounds(index) => write!(
+                f,
+                "Word: attempted permutation with index {} out of bound",
+                index
+            ),
+            Self::InvalidSubwordLength => write!(
+                f,
+                "Word: subword length must be between 1 and half the maximum word length"
+            ),
+            Self::RotationLongerThanLength => {
+                write!(f, "Word: attempted rotation longer than word length")
+            }
+        }
+    }
+}
+
+impl std::error::Error for WordError {}
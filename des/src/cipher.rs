@@ -1,68 +1,127 @@
-use super::{consts::*, DesCipher, DesKey, Word};
+use crypto_primitives::block_cipher::BlockCipher;
+
+use super::{check_key, concatenate, consts::*, permute, split, DesCipher, DesKey, KeyError, Word};
+
+impl BlockCipher for DesCipher {
+    const BLOCK_SIZE: usize = BLOCK_LENGTH / 8;
+    const KEY_SIZE: usize = KEY_LENGTH / 8;
+
+    type KeyError = KeyError;
+
+    fn new(key: &[u8]) -> Result<Self, KeyError> {
+        DesCipher::new(key)
+    }
+
+    fn encrypt_block(&self, block: &mut [u8]) {
+        let ciphertext: [u8; 8] = self.encrypt_word(Self::block_word(block)).into();
+
+        block.copy_from_slice(&ciphertext);
+    }
+
+    fn decrypt_block(&self, block: &mut [u8]) {
+        let plaintext: [u8; 8] = self.decrypt_word(Self::block_word(block)).into();
+
+        block.copy_from_slice(&plaintext);
+    }
+}
 
 impl DesCipher {
-    pub fn new(init_key: &[u8; 8]) -> Self {
-        Self {
-            key: DesKey::new(init_key),
-        }
+    pub fn new(init_key: &[u8]) -> Result<Self, KeyError> {
+        Ok(Self {
+            key: DesKey::new(&Self::key_block(init_key)?),
+            legacy: false,
+        })
+    }
+
+    pub fn new_checked(init_key: &[u8]) -> Result<Self, KeyError> {
+        check_key(&Self::key_block(init_key)?)?;
+
+        Self::new(init_key)
     }
 
-    pub fn encrypt(&self, plaintext_block: &[u8; 8]) -> [u8; 8] {
-        self.encrypt_block(Word::from(plaintext_block)).into()
+    // The round function as it was before it followed FIPS 46-3, only to read old ciphertexts
+    pub fn new_legacy(init_key: &[u8]) -> Result<Self, KeyError> {
+        Ok(Self {
+            key: DesKey::new(&Self::key_block(init_key)?),
+            legacy: true,
+        })
     }
 
-    pub fn decrypt(&self, ciphertext_block: &[u8; 8]) -> [u8; 8] {
-        self.decrypt_block(Word::from(ciphertext_block)).into()
+    fn key_block(init_key: &[u8]) -> Result<[u8; 8], KeyError> {
+        init_key
+            .try_into()
+            .map_err(|_| KeyError::Length(init_key.len()))
     }
 
-    fn encrypt_block(&self, plaintext: Word) -> Word {
+    fn block_word(block: &[u8]) -> Word {
+        let block: &[u8; 8] = block.try_into().expect("DES: Block must be 8 bytes long");
+
+        Word::from(block)
+    }
+
+    fn encrypt_word(&self, plaintext: Word) -> Word {
         let mut ciphertext = Self::initial_permutation(plaintext);
 
         for round_key in self.key.iter() {
-            ciphertext = Self::feistel_network_round(ciphertext, *round_key);
+            ciphertext = self.feistel_network_round(ciphertext, *round_key);
         }
 
-        Self::final_permutation(ciphertext)
+        Self::final_permutation(self.preoutput_block(ciphertext))
     }
 
-    fn decrypt_block(&self, ciphertext: Word) -> Word {
+    fn decrypt_word(&self, ciphertext: Word) -> Word {
         let mut plaintext = Self::initial_permutation(ciphertext);
 
         for round_key in self.key.iter().rev() {
-            plaintext = DesCipher::feistel_network_round(plaintext, *round_key);
+            plaintext = self.feistel_network_round(plaintext, *round_key);
         }
 
-        Self::final_permutation(plaintext)
+        Self::final_permutation(self.preoutput_block(plaintext))
     }
 
-    fn feistel_network_round(data: Word, key: Word) -> Word {
-        let (left, right) = data.split(BLOCK_LENGTH / 2);
+    fn feistel_network_round(&self, data: Word, key: Word) -> Word {
+        let (left, right) = split(data, BLOCK_LENGTH / 2);
 
         let new_left = right;
-        let new_right = left ^ Self::feistel_function(right, key);
+        let new_right = left ^ Self::feistel_function(right, key, self.legacy);
 
-        new_right.concatenate(new_left, BLOCK_LENGTH / 2)
+        match self.legacy {
+            true => concatenate(new_right, new_left, BLOCK_LENGTH / 2),
+            false => concatenate(new_left, new_right, BLOCK_LENGTH / 2),
+        }
     }
 
-    fn feistel_function(data: Word, key: Word) -> Word {
+    // The halves are swapped back after the last round, so that decryption is encryption
+    // with the round keys reversed
+    fn preoutput_block(&self, data: Word) -> Word {
+        if self.legacy {
+            return data;
+        }
+
+        let (left, right) = split(data, BLOCK_LENGTH / 2);
+
+        concatenate(right, left, BLOCK_LENGTH / 2)
+    }
+
+    fn feistel_function(data: Word, key: Word, legacy: bool) -> Word {
         let expanded_data = Self::apply_ebox(data);
 
-        let substituted_data = Self::apply_sboxes(expanded_data ^ key);
+        let substituted_data = Self::apply_sboxes(expanded_data ^ key, legacy);
 
         Self::apply_pbox(substituted_data)
     }
 
     fn initial_permutation(data: Word) -> Word {
-        data.permute_bits(IP_TABLE.len(), &IP_TABLE)
+        permute(data, IP_TABLE.len(), &IP_TABLE)
     }
 
     fn apply_ebox(data: Word) -> Word {
         const HALF_BLOCK_LENGTH: usize = BLOCK_LENGTH / 2;
 
-        data.permute_bits(HALF_BLOCK_LENGTH, &EBOX)
+        permute(data, HALF_BLOCK_LENGTH, &EBOX)
     }
 
-    fn apply_sboxes(data: Word) -> Word {
+    fn apply_sboxes(data: Word, legacy: bool) -> Word {
         const INPUT_LENGTH: usize = COMPRESSED_KEY_LENGTH;
         const INPUT_SUBWORD_LENGTH: usize = INPUT_LENGTH / SBOX_ARRAY.len();
         const OUTPUT_SUBWORD_LENGTH: usize = 4;
@@ -73,7 +132,12 @@ impl DesCipher {
         for (i, sbox) in SBOX_ARRAY.iter().rev().enumerate() {
             let subword: u64 = (data >> (i * INPUT_SUBWORD_LENGTH) & MASK).into();
 
-            let substitution = Word::from(sbox[subword as usize]);
+            // The outer bits pick the row and the inner four the column
+            let sbox_index = match legacy {
+                true => subword,
+                false => ((subword >> 4 & 0b10) | (subword & 1)) << 4 | (subword >> 1 & 0b1111),
+            };
+            let substitution = Word::from(sbox[sbox_index as usize]);
 
             output |= substitution << (i * OUTPUT_SUBWORD_LENGTH);
         }
@@ -82,11 +146,11 @@ impl DesCipher {
     }
 
     fn apply_pbox(data: Word) -> Word {
-        data.permute_bits(PBOX.len(), &PBOX)
+        permute(data, PBOX.len(), &PBOX)
     }
 
     fn final_permutation(data: Word) -> Word {
-        data.permute_bits(FP_TABLE.len(), &FP_TABLE)
+        permute(data, FP_TABLE.len(), &FP_TABLE)
     }
 }
 
@@ -213,14 +277,72 @@ mod tests {
     mod block_encrypt_and_decrypt {
         use super::*;
 
+        // Worked example from J. Orlin Grabbe, "The DES Algorithm Illustrated"
+        const KEY: [u8; 8] = [0x13, 0x34, 0x57, 0x79, 0x9B, 0xBC, 0xDF, 0xF1];
+        const PLAINTEXT: u64 = 0x0123_4567_89AB_CDEF;
+        const CIPHERTEXT: u64 = 0x85E8_1354_0F0A_B405;
+
+        #[test]
+        fn test_encrypt_block() {
+            let des = DesCipher::new(&KEY).unwrap();
+            let left = des.encrypt_word(Word::from(PLAINTEXT));
+            let right = Word::from(CIPHERTEXT);
+
+            assert_eq!(left, right);
+        }
+
+        #[test]
+        fn test_decrypt_block() {
+            let des = DesCipher::new(&KEY).unwrap();
+            let left = des.decrypt_word(Word::from(CIPHERTEXT));
+            let right = Word::from(PLAINTEXT);
+
+            assert_eq!(left, right);
+        }
+
+        #[test]
+        fn test_encrypt_and_decrypt_block() {
+            let des = DesCipher::new(&KEY).unwrap();
+            let left = des.decrypt_word(des.encrypt_word(Word::from(PLAINTEXT)));
+            let right = Word::from(PLAINTEXT);
+
+            assert_eq!(left, right);
+        }
+
+        // FIPS 81 Appendix B, "Now is t" under 0123456789ABCDEF
+        #[test]
+        fn test_encrypt_fips_81_block() {
+            let des = DesCipher::new(&[0x01, 0x23, 0x45, 0x67, 0x89, 0xAB, 0xCD, 0xEF]).unwrap();
+            let mut block = [0x4E, 0x6F, 0x77, 0x20, 0x69, 0x73, 0x20, 0x74];
+
+            des.encrypt_block(&mut block);
+
+            let left = block;
+            let right = [0x3F, 0xA4, 0x0E, 0x8A, 0x98, 0x4D, 0x48, 0x15];
+
+            assert_eq!(left, right);
+        }
+
+        #[should_panic]
+        #[test]
+        fn partial_block_rejected() {
+            let des = DesCipher::new(&KEY).unwrap();
+
+            des.encrypt_block(&mut [0x4E, 0x6F, 0x77, 0x20]);
+        }
+    }
+
+    mod legacy_block_encrypt_and_decrypt {
+        use super::*;
+
         const KEY: [u8; 8] = [0x0A, 0xEA, 0x5C, 0xE2, 0x13, 0x6A, 0x0C, 0xB1];
         const PLAINTEXT: u64 = 0x00E4_CF83_2D26;
         const CIPHERTEXT: u64 = 0x0400_00E4_CBC6_3936;
 
         #[test]
         fn test_encrypt_block() {
-            let des = DesCipher::new(&KEY);
-            let left = des.encrypt_block(Word::from(PLAINTEXT));
+            let des = DesCipher::new_legacy(&KEY).unwrap();
+            let left = des.encrypt_word(Word::from(PLAINTEXT));
             let right = Word::from(CIPHERTEXT);
 
             assert_eq!(left, right);
@@ -228,8 +350,8 @@ mod tests {
 
         #[test]
         fn test_decrypt_block() {
-            let des = DesCipher::new(&KEY);
-            let left = des.decrypt_block(Word::from(CIPHERTEXT));
+            let des = DesCipher::new_legacy(&KEY).unwrap();
+            let left = des.decrypt_word(Word::from(CIPHERTEXT));
             let right = Word::from(PLAINTEXT);
 
             assert_eq!(left, right);
@@ -237,12 +359,45 @@ mod tests {
 
         #[test]
         fn test_encrypt_and_decrypt_block() {
-            let des = DesCipher::new(&KEY);
-            let left = des.decrypt_block(des.encrypt_block(Word::from(PLAINTEXT)));
+            let des = DesCipher::new_legacy(&KEY).unwrap();
+            let left = des.decrypt_word(des.encrypt_word(Word::from(PLAINTEXT)));
             let right = Word::from(PLAINTEXT);
 
             assert_eq!(left, right);
         }
+
+        #[test]
+        fn differs_from_standard() {
+            let left = DesCipher::new_legacy(&KEY)
+                .unwrap()
+                .encrypt_word(Word::from(PLAINTEXT));
+            let right = DesCipher::new(&KEY)
+                .unwrap()
+                .encrypt_word(Word::from(PLAINTEXT));
+
+            assert_ne!(left, right);
+        }
+    }
+
+    #[test]
+    fn weak_key_rejected() {
+        let left = DesCipher::new_checked(&[0xFE; 8]).err();
+        let right = Some(KeyError::Weak);
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn wrong_key_length_rejected() {
+        let left = DesCipher::new(&[0x13; 7]).err();
+        let right = Some(KeyError::Length(7));
+
+        assert_eq!(left, right);
+
+        let left = DesCipher::new_legacy(&[0x13; 16]).err();
+        let right = Some(KeyError::Length(16));
+
+        assert_eq!(left, right);
     }
 
     #[test]
@@ -253,9 +408,21 @@ mod tests {
         let input = Word::from(INPUT);
         let key = Word::from(KEY);
 
-        let left = DesCipher::feistel_function(input, key);
+        let left = DesCipher::feistel_function(input, key, true);
         let right = Word::from(0b0100_0000_0011_0011_0011_0100_1000_0000u64);
 
         assert_eq!(left, right);
     }
+
+    #[test]
+    fn feistel_function_first_round() {
+        let left = DesCipher::feistel_function(
+            Word::from(0xF0AA_F0AAu64),
+            Word::from(0x1B02_EFFC_7072u64),
+            false,
+        );
+        let right = Word::from(0x234A_A9BBu64);
+
+        assert_eq!(left, right);
+    }
 }
@@ -1,6 +1,93 @@
-use std::slice::Iter;
+use std::{
+    fmt::{self, Display, Formatter},
+    slice::Iter,
+};
+
+use super::{concatenate, consts::*, permute, split, DesKey, Word};
+
+const PARITY_MASK: u8 = 0xFE;
+
+#[rustfmt::skip]
+const WEAK_KEYS: [[u8; 8]; 4] = [
+    [0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01],
+    [0xFE, 0xFE, 0xFE, 0xFE, 0xFE, 0xFE, 0xFE, 0xFE],
+    [0xE0, 0xE0, 0xE0, 0xE0, 0xF1, 0xF1, 0xF1, 0xF1],
+    [0x1F, 0x1F, 0x1F, 0x1F, 0x0E, 0x0E, 0x0E, 0x0E],
+];
+
+// Listed in pairs, encryption under one key is decryption under the other
+#[rustfmt::skip]
+const SEMI_WEAK_KEYS: [[u8; 8]; 12] = [
+    [0x01, 0xFE, 0x01, 0xFE, 0x01, 0xFE, 0x01, 0xFE],
+    [0xFE, 0x01, 0xFE, 0x01, 0xFE, 0x01, 0xFE, 0x01],
+    [0x1F, 0xE0, 0x1F, 0xE0, 0x0E, 0xF1, 0x0E, 0xF1],
+    [0xE0, 0x1F, 0xE0, 0x1F, 0xF1, 0x0E, 0xF1, 0x0E],
+    [0x01, 0xE0, 0x01, 0xE0, 0x01, 0xF1, 0x01, 0xF1],
+    [0xE0, 0x01, 0xE0, 0x01, 0xF1, 0x01, 0xF1, 0x01],
+    [0x1F, 0xFE, 0x1F, 0xFE, 0x0E, 0xFE, 0x0E, 0xFE],
+    [0xFE, 0x1F, 0xFE, 0x1F, 0xFE, 0x0E, 0xFE, 0x0E],
+    [0x01, 0x1F, 0x01, 0x1F, 0x01, 0x0E, 0x01, 0x0E],
+    [0x1F, 0x01, 0x1F, 0x01, 0x0E, 0x01, 0x0E, 0x01],
+    [0xE0, 0xFE, 0xE0, 0xFE, 0xF1, 0xFE, 0xF1, 0xFE],
+    [0xFE, 0xE0, 0xFE, 0xE0, 0xFE, 0xF1, 0xFE, 0xF1],
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyError {
+    Length(usize),
+    Weak,
+    SemiWeak,
+}
+
+impl Display for KeyError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Length(len) => write!(f, "DES key is {} bytes long instead of 8", len),
+            Self::Weak => write!(f, "weak DES key, encryption is its own inverse"),
+            Self::SemiWeak => write!(
+                f,
+                "semi-weak DES key, another key decrypts what it encrypts"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for KeyError {}
 
-use super::{consts::*, DesKey, Word};
+// The least significant bit of every byte makes the number of set bits odd
+pub fn set_odd_parity(key: &mut [u8; 8]) {
+    for byte in key.iter_mut() {
+        let key_bits = *byte & PARITY_MASK;
+
+        *byte = key_bits | u8::from(key_bits.count_ones().is_multiple_of(2));
+    }
+}
+
+pub fn has_odd_parity(key: &[u8; 8]) -> bool {
+    key.iter().all(|byte| byte.count_ones() % 2 == 1)
+}
+
+pub fn check_key(key: &[u8; 8]) -> Result<(), KeyError> {
+    if WEAK_KEYS.iter().any(|weak_key| same_key(weak_key, key)) {
+        return Err(KeyError::Weak);
+    }
+
+    if SEMI_WEAK_KEYS
+        .iter()
+        .any(|semi_weak_key| same_key(semi_weak_key, key))
+    {
+        return Err(KeyError::SemiWeak);
+    }
+
+    Ok(())
+}
+
+// Parity bits are dropped by PC-1, so keys differing only there are the same key
+pub fn same_key(left: &[u8; 8], right: &[u8; 8]) -> bool {
+    left.iter()
+        .zip(right)
+        .all(|(left_byte, right_byte)| left_byte & PARITY_MASK == right_byte & PARITY_MASK)
+}
 
 impl DesKey {
     pub(super) fn new(init_key: &[u8; 8]) -> Self {
@@ -16,14 +103,14 @@ impl DesKey {
     fn expand_round_keys(init_key: Word) -> [Word; NUM_ROUNDS] {
         let key = Self::permutation_choice_1(init_key);
 
-        let (mut c, mut d) = key.split(SUBKEY_LENGTH);
+        let (mut c, mut d) = split(key, SUBKEY_LENGTH);
 
         let round_keys: Vec<_> = (0..NUM_ROUNDS)
             .map(|i| {
-                c = c.rotate_left(SHIFT_VALUES[i], SUBKEY_LENGTH);
-                d = d.rotate_left(SHIFT_VALUES[i], SUBKEY_LENGTH);
+                c = Self::rotate_left(c, SHIFT_VALUES[i]);
+                d = Self::rotate_left(d, SHIFT_VALUES[i]);
 
-                Self::permutation_choice_2(c.concatenate(d, SUBKEY_LENGTH))
+                Self::permutation_choice_2(concatenate(c, d, SUBKEY_LENGTH))
             })
             .collect();
 
@@ -33,12 +120,18 @@ impl DesKey {
             .expect("🙀🧨 DES key generation failed. This was not supposed to happen.")
     }
 
+    // Shifts never exceed two bits and the halves stay within their 28 bits
+    fn rotate_left(half: Word, rotate_by: usize) -> Word {
+        half.rotate_left(rotate_by, SUBKEY_LENGTH)
+            .expect("🙀🧨 DES key rotation out of bounds. This was not supposed to happen.")
+    }
+
     fn permutation_choice_1(key: Word) -> Word {
-        key.permute_bits(KEY_LENGTH, &PC1_TABLE)
+        permute(key, KEY_LENGTH, &PC1_TABLE)
     }
 
     fn permutation_choice_2(key: Word) -> Word {
-        key.permute_bits(REDUCED_KEY_LENGTH, &PC2_TABLE)
+        permute(key, REDUCED_KEY_LENGTH, &PC2_TABLE)
     }
 }
 
@@ -73,7 +166,7 @@ mod tests {
         fn parity_bits_only() {
             let mut w = Word::one();
             for i in 1..8 {
-                w |= Word::one() << i * 8u8;
+                w |= Word::one() << (i * 8u8);
             }
 
             let left = DesKey::permutation_choice_1(w);
@@ -127,6 +220,73 @@ mod tests {
         }
     }
 
+    mod key_checks {
+        use super::*;
+
+        #[test]
+        fn weak_keys_repeat_round_keys() {
+            for weak_key in WEAK_KEYS {
+                let round_keys = DesKey::new(&weak_key).round_keys;
+
+                let left = round_keys;
+                let right = [round_keys[0]; NUM_ROUNDS];
+
+                assert_eq!(left, right);
+            }
+        }
+
+        #[test]
+        fn semi_weak_pairs_reverse_round_keys() {
+            for pair in SEMI_WEAK_KEYS.chunks(2) {
+                let mut left = DesKey::new(&pair[0]).round_keys;
+                left.reverse();
+                let right = DesKey::new(&pair[1]).round_keys;
+
+                assert_eq!(left, right);
+            }
+        }
+
+        #[test]
+        fn weak_keys_rejected_regardless_of_parity() {
+            let left = check_key(&[0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]);
+            let right = Err(KeyError::Weak);
+
+            assert_eq!(left, right);
+
+            let left = check_key(&[0x1E, 0xE1, 0x1E, 0xE1, 0x0F, 0xF0, 0x0F, 0xF0]);
+            let right = Err(KeyError::SemiWeak);
+
+            assert_eq!(left, right);
+        }
+
+        #[test]
+        fn regular_key_accepted() {
+            let left = check_key(&[0x13, 0x34, 0x57, 0x79, 0x9B, 0xBC, 0xDF, 0xF1]);
+            let right = Ok(());
+
+            assert_eq!(left, right);
+        }
+
+        #[test]
+        fn parity_adjusted() {
+            let mut key = [0x12, 0x35, 0x56, 0x78, 0x9B, 0xBC, 0xDE, 0xF1];
+            set_odd_parity(&mut key);
+
+            let left = key;
+            let right = [0x13, 0x34, 0x57, 0x79, 0x9B, 0xBC, 0xDF, 0xF1];
+
+            assert_eq!(left, right);
+            assert!(has_odd_parity(&key));
+        }
+
+        #[test]
+        fn tables_have_odd_parity() {
+            for key in WEAK_KEYS.iter().chain(SEMI_WEAK_KEYS.iter()) {
+                assert!(has_odd_parity(key));
+            }
+        }
+    }
+
     mod expand_round_keys {
         use super::*;
 
@@ -5,10 +5,30 @@ mod key;
 use consts::*;
 use crypto_primitives::Word;
 
+pub use key::{check_key, has_odd_parity, same_key, set_odd_parity, KeyError};
+
 pub struct DesCipher {
     key: DesKey,
+    legacy: bool,
 }
 
 struct DesKey {
     round_keys: [Word; NUM_ROUNDS],
 }
+
+// Every table is a constant, so a failed permutation is a bug in the tables
+fn permute(data: Word, word_len: usize, table: &[usize]) -> Word {
+    data.permute_bits(word_len, table)
+        .expect("🙀🧨 DES permutation table out of bounds. This was not supposed to happen.")
+}
+
+// Blocks and keys come out of the tables at their fixed lengths, so the halves always fit
+fn split(data: Word, half_len: usize) -> (Word, Word) {
+    data.split(half_len)
+        .expect("🙀🧨 DES halves out of bounds. This was not supposed to happen.")
+}
+
+fn concatenate(left: Word, right: Word, half_len: usize) -> Word {
+    left.concatenate(right, half_len)
+        .expect("🙀🧨 DES halves out of bounds. This was not supposed to happen.")
+}
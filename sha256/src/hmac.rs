@@ -0,0 +1,107 @@
+use super::{Sha256, SHA256_BLOCK_LEN, SHA256_DIGEST_LEN};
+
+const INNER_PAD: u8 = 0x36;
+const OUTER_PAD: u8 = 0x5C;
+
+// HMAC-SHA256 (RFC 2104), keys longer than a block are hashed first
+#[derive(Clone)]
+pub struct HmacSha256 {
+    inner: Sha256,
+    outer_key: [u8; SHA256_BLOCK_LEN],
+}
+
+impl HmacSha256 {
+    pub fn new(key: &[u8]) -> Self {
+        let mut block_key = [0u8; SHA256_BLOCK_LEN];
+
+        match key.len() > SHA256_BLOCK_LEN {
+            true => block_key[..SHA256_DIGEST_LEN].copy_from_slice(&Sha256::digest(key)),
+            false => block_key[..key.len()].copy_from_slice(key),
+        }
+
+        let mut inner = Sha256::new();
+        inner.update(&block_key.map(|byte| byte ^ INNER_PAD));
+
+        Self {
+            inner,
+            outer_key: block_key.map(|byte| byte ^ OUTER_PAD),
+        }
+    }
+
+    pub fn mac(key: &[u8], data: &[u8]) -> [u8; SHA256_DIGEST_LEN] {
+        let mut hmac = Self::new(key);
+        hmac.update(data);
+
+        hmac.finalize()
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        self.inner.update(data);
+    }
+
+    pub fn finalize(self) -> [u8; SHA256_DIGEST_LEN] {
+        let mut outer = Sha256::new();
+        outer.update(&self.outer_key);
+        outer.update(&self.inner.finalize());
+
+        outer.finalize()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // RFC 4231, test case 1
+    #[test]
+    fn short_key() {
+        let left = HmacSha256::mac(&[0x0B; 20], b"Hi There");
+        let right = [
+            0xB0, 0x34, 0x4C, 0x61, 0xD8, 0xDB, 0x38, 0x53, 0x5C, 0xA8, 0xAF, 0xCE, 0xAF, 0x0B,
+            0xF1, 0x2B, 0x88, 0x1D, 0xC2, 0x00, 0xC9, 0x83, 0x3D, 0xA7, 0x26, 0xE9, 0x37, 0x6C,
+            0x2E, 0x32, 0xCF, 0xF7,
+        ];
+
+        assert_eq!(left, right);
+    }
+
+    // RFC 4231, test case 2
+    #[test]
+    fn key_shorter_than_mac() {
+        let left = HmacSha256::mac(b"Jefe", b"what do ya want for nothing?");
+        let right = [
+            0x5B, 0xDC, 0xC1, 0x46, 0xBF, 0x60, 0x75, 0x4E, 0x6A, 0x04, 0x24, 0x26, 0x08, 0x95,
+            0x75, 0xC7, 0x5A, 0x00, 0x3F, 0x08, 0x9D, 0x27, 0x39, 0x83, 0x9D, 0xEC, 0x58, 0xB9,
+            0x64, 0xEC, 0x38, 0x43,
+        ];
+
+        assert_eq!(left, right);
+    }
+
+    // RFC 4231, test case 6
+    #[test]
+    fn key_longer_than_block() {
+        let left = HmacSha256::mac(
+            &[0xAA; 131],
+            b"Test Using Larger Than Block-Size Key - Hash Key First",
+        );
+        let right = [
+            0x60, 0xE4, 0x31, 0x59, 0x1E, 0xE0, 0xB6, 0x7F, 0x0D, 0x8A, 0x26, 0xAA, 0xCB, 0xF5,
+            0xB7, 0x7F, 0x8E, 0x0B, 0xC6, 0x21, 0x37, 0x28, 0xC5, 0x14, 0x05, 0x46, 0x04, 0x0F,
+            0x0E, 0xE3, 0x7F, 0x54,
+        ];
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn cloned_key_state() {
+        let hmac = HmacSha256::new(b"Jefe");
+
+        let mut left = hmac.clone();
+        left.update(b"what do ya want for nothing?");
+        let right = HmacSha256::mac(b"Jefe", b"what do ya want for nothing?");
+
+        assert_eq!(left.finalize(), right);
+    }
+}
@@ -0,0 +1,244 @@
+mod consts;
+pub mod hmac;
+
+use consts::*;
+
+pub const SHA256_DIGEST_LEN: usize = 32;
+pub const SHA256_BLOCK_LEN: usize = 64;
+
+#[derive(Clone)]
+pub struct Sha256 {
+    state: [u32; 8],
+    buffer: Vec<u8>,
+    message_len: u64,
+}
+
+impl Sha256 {
+    pub fn new() -> Self {
+        Self {
+            state: SHA256_INITIAL_STATE,
+            buffer: Vec::with_capacity(SHA256_BLOCK_LEN),
+            message_len: 0,
+        }
+    }
+
+    pub fn digest(data: &[u8]) -> [u8; SHA256_DIGEST_LEN] {
+        let mut sha256 = Self::new();
+        sha256.update(data);
+
+        sha256.finalize()
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        self.message_len += data.len() as u64;
+
+        let mut data = data;
+
+        if !self.buffer.is_empty() {
+            let fill_len = data.len().min(SHA256_BLOCK_LEN - self.buffer.len());
+            self.buffer.extend_from_slice(&data[..fill_len]);
+            data = &data[fill_len..];
+
+            if self.buffer.len() < SHA256_BLOCK_LEN {
+                return;
+            }
+
+            let block = std::mem::take(&mut self.buffer);
+            self.compress(&block);
+        }
+
+        let mut blocks = data.chunks_exact(SHA256_BLOCK_LEN);
+
+        for block in blocks.by_ref() {
+            self.compress(block);
+        }
+
+        self.buffer.extend_from_slice(blocks.remainder());
+    }
+
+    pub fn finalize(mut self) -> [u8; SHA256_DIGEST_LEN] {
+        let message_bits = self.message_len.wrapping_mul(u8::BITS as u64);
+
+        // A single 1 bit, zeros up to 8 bytes short of a block, then the message length in bits
+        let mut padding = vec![0x80];
+        let padded_len = (self.buffer.len() + 1 + 8).next_multiple_of(SHA256_BLOCK_LEN);
+        padding.resize(padded_len - self.buffer.len() - 8, 0);
+        padding.extend_from_slice(&message_bits.to_be_bytes());
+
+        let message_len = self.message_len;
+        self.update(&padding);
+        self.message_len = message_len;
+
+        let mut digest = [0u8; SHA256_DIGEST_LEN];
+
+        for (bytes, word) in digest.chunks_exact_mut(4).zip(self.state) {
+            bytes.copy_from_slice(&word.to_be_bytes());
+        }
+
+        digest
+    }
+
+    fn compress(&mut self, block: &[u8]) {
+        let mut schedule = [0u32; 64];
+
+        for (word, bytes) in schedule.iter_mut().zip(block.chunks_exact(4)) {
+            *word = u32::from_be_bytes(bytes.try_into().unwrap());
+        }
+
+        for i in 16..64 {
+            let s0 = schedule[i - 15].rotate_right(7)
+                ^ schedule[i - 15].rotate_right(18)
+                ^ (schedule[i - 15] >> 3);
+            let s1 = schedule[i - 2].rotate_right(17)
+                ^ schedule[i - 2].rotate_right(19)
+                ^ (schedule[i - 2] >> 10);
+
+            schedule[i] = schedule[i - 16]
+                .wrapping_add(s0)
+                .wrapping_add(schedule[i - 7])
+                .wrapping_add(s1);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = self.state;
+
+        for (round_constant, word) in SHA256_ROUND_CONSTANTS.iter().zip(schedule) {
+            let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+            let choice = (e & f) ^ (!e & g);
+            let temp1 = h
+                .wrapping_add(s1)
+                .wrapping_add(choice)
+                .wrapping_add(*round_constant)
+                .wrapping_add(word);
+            let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+            let majority = (a & b) ^ (a & c) ^ (b & c);
+            let temp2 = s0.wrapping_add(majority);
+
+            (h, g, f, e, d, c, b, a) = (
+                g,
+                f,
+                e,
+                d.wrapping_add(temp1),
+                c,
+                b,
+                a,
+                temp1.wrapping_add(temp2),
+            );
+        }
+
+        for (word, value) in self.state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+            *word = word.wrapping_add(value);
+        }
+    }
+}
+
+impl Default for Sha256 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // FIPS 180-4 examples and NIST CAVP vectors
+    #[test]
+    fn digest_empty() {
+        let left = Sha256::digest(b"");
+        let right = [
+            0xE3, 0xB0, 0xC4, 0x42, 0x98, 0xFC, 0x1C, 0x14, 0x9A, 0xFB, 0xF4, 0xC8, 0x99, 0x6F,
+            0xB9, 0x24, 0x27, 0xAE, 0x41, 0xE4, 0x64, 0x9B, 0x93, 0x4C, 0xA4, 0x95, 0x99, 0x1B,
+            0x78, 0x52, 0xB8, 0x55,
+        ];
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn digest_abc() {
+        let left = Sha256::digest(b"abc");
+        let right = [
+            0xBA, 0x78, 0x16, 0xBF, 0x8F, 0x01, 0xCF, 0xEA, 0x41, 0x41, 0x40, 0xDE, 0x5D, 0xAE,
+            0x22, 0x23, 0xB0, 0x03, 0x61, 0xA3, 0x96, 0x17, 0x7A, 0x9C, 0xB4, 0x10, 0xFF, 0x61,
+            0xF2, 0x00, 0x15, 0xAD,
+        ];
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn digest_two_blocks() {
+        let left = Sha256::digest(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq");
+        let right = [
+            0x24, 0x8D, 0x6A, 0x61, 0xD2, 0x06, 0x38, 0xB8, 0xE5, 0xC0, 0x26, 0x93, 0x0C, 0x3E,
+            0x60, 0x39, 0xA3, 0x3C, 0xE4, 0x59, 0x64, 0xFF, 0x21, 0x67, 0xF6, 0xEC, 0xED, 0xD4,
+            0x19, 0xDB, 0x06, 0xC1,
+        ];
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn digest_million_a() {
+        let left = Sha256::digest(&vec![b'a'; 1_000_000]);
+        let right = [
+            0xCD, 0xC7, 0x6E, 0x5C, 0x99, 0x14, 0xFB, 0x92, 0x81, 0xA1, 0xC7, 0xE2, 0x84, 0xD7,
+            0x3E, 0x67, 0xF1, 0x80, 0x9A, 0x48, 0xA4, 0x97, 0x20, 0x0E, 0x04, 0x6D, 0x39, 0xCC,
+            0xC7, 0x11, 0x2C, 0xD0,
+        ];
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn update_in_pieces() {
+        let message: Vec<u8> = (0..=255).cycle().take(1000).collect();
+
+        let mut sha256 = Sha256::new();
+        for piece in message.chunks(37) {
+            sha256.update(piece);
+        }
+
+        let left = sha256.finalize();
+        let right = Sha256::digest(&message);
+
+        assert_eq!(left, right);
+    }
+
+    // 55 bytes is the longest message whose padding fits in the same block
+    #[test]
+    fn padding_boundaries() {
+        let digests = [
+            (
+                55,
+                [
+                    0x9F, 0x43, 0x90, 0xF8, 0xD3, 0x0C, 0x2D, 0xD9, 0x2E, 0xC9, 0xF0, 0x95, 0xB6,
+                    0x5E, 0x2B, 0x9A, 0xE9, 0xB0, 0xA9, 0x25, 0xA5, 0x25, 0x8E, 0x24, 0x1C, 0x9F,
+                    0x1E, 0x91, 0x0F, 0x73, 0x43, 0x18,
+                ],
+            ),
+            (
+                56,
+                [
+                    0xB3, 0x54, 0x39, 0xA4, 0xAC, 0x6F, 0x09, 0x48, 0xB6, 0xD6, 0xF9, 0xE3, 0xC6,
+                    0xAF, 0x0F, 0x5F, 0x59, 0x0C, 0xE2, 0x0F, 0x1B, 0xDE, 0x70, 0x90, 0xEF, 0x79,
+                    0x70, 0x68, 0x6E, 0xC6, 0x73, 0x8A,
+                ],
+            ),
+            (
+                64,
+                [
+                    0xFF, 0xE0, 0x54, 0xFE, 0x7A, 0xE0, 0xCB, 0x6D, 0xC6, 0x5C, 0x3A, 0xF9, 0xB6,
+                    0x1D, 0x52, 0x09, 0xF4, 0x39, 0x85, 0x1D, 0xB4, 0x3D, 0x0B, 0xA5, 0x99, 0x73,
+                    0x37, 0xDF, 0x15, 0x46, 0x68, 0xEB,
+                ],
+            ),
+        ];
+
+        for (len, right) in digests {
+            let left = Sha256::digest(&vec![b'a'; len]);
+
+            assert_eq!(left, right);
+        }
+    }
+}
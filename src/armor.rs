@@ -0,0 +1,575 @@
+use std::io::{self, BufRead, BufReader, Chain, Cursor, Read, Write};
+
+use crate::{
+    error::{Result, SymciphError},
+    io::read_full,
+    ArmorSchemes,
+};
+
+pub(crate) const MESSAGE_LABEL: &str = "MESSAGE";
+pub(crate) const KEY_LABEL: &str = "KEY";
+
+const PEM_PREFIX: &[u8] = b"-----BEGIN SYMCIPH ";
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+// Both encodings give 64 characters per line
+const HEX_LINE_BYTES: usize = 32;
+const BASE64_LINE_BYTES: usize = 48;
+// Long enough to tell armor from binary, which has next to no chance of being all text
+const DETECT_LEN: usize = 0x40;
+// CRC24 from RFC 4880, the checksum of OpenPGP armor
+const CRC24_INIT: u32 = 0xB704CE;
+const CRC24_POLY: u32 = 0x1864CFB;
+
+fn crc24(mut crc: u32, data: &[u8]) -> u32 {
+    for byte in data {
+        crc ^= u32::from(*byte) << 16;
+
+        for _ in 0..8 {
+            crc <<= 1;
+
+            if crc & 0x1000000 != 0 {
+                crc ^= CRC24_POLY;
+            }
+        }
+    }
+
+    crc & 0xFFFFFF
+}
+
+fn hex_encode(data: &[u8]) -> Vec<u8> {
+    const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+    data.iter()
+        .flat_map(|byte| {
+            [
+                HEX_DIGITS[usize::from(byte >> 4)],
+                HEX_DIGITS[usize::from(byte & 0x0F)],
+            ]
+        })
+        .collect()
+}
+
+fn base64_encode(data: &[u8]) -> Vec<u8> {
+    data.chunks(3)
+        .flat_map(|group| {
+            let bits = group.iter().enumerate().fold(0u32, |bits, (i, byte)| {
+                bits | u32::from(*byte) << (16 - 8 * i)
+            });
+
+            (0..4).map(move |i| match i <= group.len() {
+                true => BASE64_ALPHABET[(bits >> (18 - 6 * i) & 0x3F) as usize],
+                false => b'=',
+            })
+        })
+        .collect()
+}
+
+fn hex_value(symbol: u8) -> Result<u8> {
+    match symbol {
+        b'0'..=b'9' => Ok(symbol - b'0'),
+        b'a'..=b'f' => Ok(symbol - b'a' + 10),
+        b'A'..=b'F' => Ok(symbol - b'A' + 10),
+        _ => Err(invalid_symbol_error("hex")),
+    }
+}
+
+fn base64_value(symbol: u8) -> Result<u8> {
+    match BASE64_ALPHABET.iter().position(|&digit| digit == symbol) {
+        Some(value) => Ok(value as u8),
+        None => Err(invalid_symbol_error("Base64")),
+    }
+}
+
+fn invalid_symbol_error(encoding: &str) -> SymciphError {
+    SymciphError::Format(format!("📦🙀: Invalid {} character in armor.", encoding))
+}
+
+fn truncated_armor_error() -> SymciphError {
+    SymciphError::Format("📦🙀: Armor is truncated.".to_string())
+}
+
+// Hex digits are Base64 digits too, so hex is tried first
+fn detect(prefix: &[u8]) -> Option<ArmorSchemes> {
+    let is_text = |is_digit: fn(&u8) -> bool| {
+        prefix
+            .iter()
+            .all(|byte| is_digit(byte) || byte.is_ascii_whitespace())
+    };
+
+    if prefix.starts_with(PEM_PREFIX) {
+        Some(ArmorSchemes::Pem)
+    } else if prefix.is_empty() {
+        None
+    } else if is_text(u8::is_ascii_hexdigit) {
+        Some(ArmorSchemes::Hex)
+    } else if is_text(|byte| BASE64_ALPHABET.contains(byte) || *byte == b'=') {
+        Some(ArmorSchemes::Base64)
+    } else {
+        None
+    }
+}
+
+/// Armors everything written to it as hex, Base64 or a PEM-style block
+///
+/// PEM-style blocks open with `-----BEGIN SYMCIPH MESSAGE-----` and `Name: value` header
+/// fields, and close with a CRC24 checksum of the data. The armor is only complete once
+/// [`ArmoringWriter::finish`] is called.
+pub struct ArmoringWriter<W: Write> {
+    inner: W,
+    armor: ArmorSchemes,
+    label: &'static str,
+    pending: Vec<u8>,
+    crc: u32,
+}
+
+impl<W: Write> ArmoringWriter<W> {
+    pub fn new(inner: W, armor: ArmorSchemes, fields: &[(&str, &str)]) -> Result<Self> {
+        Self::with_label(inner, armor, MESSAGE_LABEL, fields)
+    }
+
+    pub(crate) fn with_label(
+        mut inner: W,
+        armor: ArmorSchemes,
+        label: &'static str,
+        fields: &[(&str, &str)],
+    ) -> Result<Self> {
+        if let ArmorSchemes::Pem = armor {
+            writeln!(inner, "-----BEGIN SYMCIPH {}-----", label)?;
+
+            for (name, value) in fields {
+                writeln!(inner, "{}: {}", name, value)?;
+            }
+
+            writeln!(inner)?;
+        }
+
+        Ok(Self {
+            inner,
+            armor,
+            label,
+            pending: Vec::new(),
+            crc: CRC24_INIT,
+        })
+    }
+
+    fn write_line(&mut self, data: &[u8]) -> io::Result<()> {
+        let line = match self.armor {
+            ArmorSchemes::Hex => hex_encode(data),
+            ArmorSchemes::Base64 | ArmorSchemes::Pem => base64_encode(data),
+        };
+
+        self.inner.write_all(&line)?;
+        self.inner.write_all(b"\n")
+    }
+
+    pub fn finish(mut self) -> Result<W> {
+        if !self.pending.is_empty() {
+            let pending = std::mem::take(&mut self.pending);
+            self.write_line(&pending)?;
+        }
+
+        if let ArmorSchemes::Pem = self.armor {
+            let checksum = base64_encode(&self.crc.to_be_bytes()[1..]);
+
+            writeln!(self.inner, "={}", String::from_utf8_lossy(&checksum))?;
+            writeln!(self.inner, "-----END SYMCIPH {}-----", self.label)?;
+        }
+
+        self.inner.flush()?;
+
+        Ok(self.inner)
+    }
+}
+
+impl<W: Write> Write for ArmoringWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let line_bytes = match self.armor {
+            ArmorSchemes::Hex => HEX_LINE_BYTES,
+            ArmorSchemes::Base64 | ArmorSchemes::Pem => BASE64_LINE_BYTES,
+        };
+
+        self.crc = crc24(self.crc, buf);
+        self.pending.extend_from_slice(buf);
+
+        while self.pending.len() >= line_bytes {
+            let line: Vec<u8> = self.pending.drain(..line_bytes).collect();
+            self.write_line(&line)?;
+        }
+
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Reads through hex, Base64 or PEM-style armor, detected from the start of the input
+///
+/// Input that is not armored is passed through untouched. PEM-style header fields are
+/// available from [`DearmoringReader::fields`] and the checksum is verified at the end.
+pub struct DearmoringReader<R: Read> {
+    inner: BufReader<Chain<Cursor<Vec<u8>>, R>>,
+    armor: Option<ArmorSchemes>,
+    label: String,
+    fields: Vec<(String, String)>,
+    symbols: Vec<u8>,
+    padded: bool,
+    decoded: Vec<u8>,
+    position: usize,
+    crc: u32,
+    finished: bool,
+}
+
+impl<R: Read> DearmoringReader<R> {
+    pub fn new(mut inner: R) -> Result<Self> {
+        let mut prefix = vec![0u8; DETECT_LEN];
+        let prefix_len = read_full(&mut inner, &mut prefix)?;
+        prefix.truncate(prefix_len);
+
+        let mut reader = Self {
+            armor: detect(&prefix),
+            inner: BufReader::new(Cursor::new(prefix).chain(inner)),
+            label: String::new(),
+            fields: Vec::new(),
+            symbols: Vec::new(),
+            padded: false,
+            decoded: Vec::new(),
+            position: 0,
+            crc: CRC24_INIT,
+            finished: false,
+        };
+
+        if let Some(ArmorSchemes::Pem) = reader.armor {
+            reader.read_pem_header()?;
+        }
+
+        Ok(reader)
+    }
+
+    /// Armor the input was found to be in, or `None` for binary input
+    pub fn armor(&self) -> Option<&ArmorSchemes> {
+        self.armor.as_ref()
+    }
+
+    /// Header fields of a PEM-style block in the order they appear
+    pub fn fields(&self) -> &[(String, String)] {
+        &self.fields
+    }
+
+    fn read_line(&mut self) -> Result<Option<String>> {
+        let mut line = Vec::new();
+
+        match self.inner.read_until(b'\n', &mut line)? {
+            0 => Ok(None),
+            _ => Ok(Some(String::from_utf8_lossy(&line).trim().to_string())),
+        }
+    }
+
+    fn read_pem_header(&mut self) -> Result<()> {
+        let begin = self.read_line()?.unwrap_or_default();
+
+        self.label = match begin
+            .strip_prefix("-----BEGIN SYMCIPH ")
+            .and_then(|begin| begin.strip_suffix("-----"))
+        {
+            Some(label) => label.to_string(),
+            None => {
+                return Err(SymciphError::Format(
+                    "📦🙀: Malformed armor begin line.".to_string(),
+                ))
+            }
+        };
+
+        loop {
+            match self.read_line()?.as_deref() {
+                Some("") => return Ok(()),
+                Some(line) => match line.split_once(": ") {
+                    Some((name, value)) => {
+                        self.fields.push((name.to_string(), value.to_string()));
+                    }
+                    None => {
+                        return Err(SymciphError::Format(format!(
+                            "📦🙀: Malformed armor header line {}.",
+                            line
+                        )))
+                    }
+                },
+                None => return Err(truncated_armor_error()),
+            }
+        }
+    }
+
+    // Decodes every whole group of symbols, the rest waits for the next line or read
+    fn decode_symbols(&mut self, text: &[u8]) -> Result<()> {
+        self.symbols
+            .extend(text.iter().filter(|byte| !byte.is_ascii_whitespace()));
+
+        let group_len = match self.armor {
+            Some(ArmorSchemes::Hex) => 2,
+            _ => 4,
+        };
+        let whole_len = self.symbols.len() - self.symbols.len() % group_len;
+        let groups: Vec<u8> = self.symbols.drain(..whole_len).collect();
+
+        for group in groups.chunks(group_len) {
+            match self.armor {
+                Some(ArmorSchemes::Hex) => self
+                    .decoded
+                    .push(hex_value(group[0])? << 4 | hex_value(group[1])?),
+                _ => self.decode_base64_group(group)?,
+            }
+        }
+
+        Ok(())
+    }
+
+    fn decode_base64_group(&mut self, group: &[u8]) -> Result<()> {
+        if self.padded {
+            return Err(SymciphError::Format(
+                "📦🙀: Armor continues past the Base64 padding.".to_string(),
+            ));
+        }
+
+        let padding_len = group
+            .iter()
+            .rev()
+            .take_while(|&&symbol| symbol == b'=')
+            .count();
+
+        if padding_len > 2 {
+            return Err(invalid_symbol_error("Base64"));
+        }
+
+        let mut bits = 0u32;
+
+        for symbol in &group[..4 - padding_len] {
+            bits = bits << 6 | u32::from(base64_value(*symbol)?);
+        }
+
+        bits <<= 6 * padding_len;
+        self.decoded
+            .extend_from_slice(&bits.to_be_bytes()[1..4 - padding_len]);
+        self.padded = padding_len > 0;
+
+        Ok(())
+    }
+
+    fn fill(&mut self) -> Result<()> {
+        self.decoded.clear();
+        self.position = 0;
+
+        match self.armor {
+            Some(ArmorSchemes::Pem) => self.fill_pem(),
+            _ => {
+                let mut text = [0u8; 0x1000];
+
+                match self.inner.read(&mut text)? {
+                    0 if self.symbols.is_empty() => {
+                        self.finished = true;
+
+                        Ok(())
+                    }
+                    0 => Err(truncated_armor_error()),
+                    text_len => self.decode_symbols(&text[..text_len]),
+                }
+            }
+        }
+    }
+
+    fn fill_pem(&mut self) -> Result<()> {
+        let line = self.read_line()?.ok_or_else(truncated_armor_error)?;
+
+        let Some(checksum) = line.strip_prefix('=') else {
+            if line.starts_with("-----END ") {
+                return Err(SymciphError::Format(
+                    "📦🙀: Armor has no checksum.".to_string(),
+                ));
+            }
+
+            self.decode_symbols(line.as_bytes())?;
+            self.crc = crc24(self.crc, &self.decoded);
+
+            return Ok(());
+        };
+
+        if !self.symbols.is_empty() {
+            return Err(truncated_armor_error());
+        }
+
+        // The checksum is the last thing decoded, it is not part of the data
+        self.padded = false;
+        self.decode_symbols(checksum.as_bytes())?;
+        let checksum = std::mem::take(&mut self.decoded);
+
+        if !self.symbols.is_empty() || checksum[..] != self.crc.to_be_bytes()[1..] {
+            return Err(SymciphError::Format(
+                "📦🙀: Armor checksum does not match, the data was altered or damaged.".to_string(),
+            ));
+        }
+
+        let end = loop {
+            match self.read_line()? {
+                Some(line) if line.is_empty() => continue,
+                Some(line) => break line,
+                None => return Err(truncated_armor_error()),
+            }
+        };
+
+        if end != format!("-----END SYMCIPH {}-----", self.label) {
+            return Err(SymciphError::Format(
+                "📦🙀: Armor end line does not match the begin line.".to_string(),
+            ));
+        }
+
+        self.finished = true;
+
+        Ok(())
+    }
+}
+
+impl<R: Read> Read for DearmoringReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.armor.is_none() {
+            return self.inner.read(buf);
+        }
+
+        while self.position == self.decoded.len() && !self.finished {
+            self.fill()?;
+        }
+
+        let bytes_read = buf.len().min(self.decoded.len() - self.position);
+        buf[..bytes_read].copy_from_slice(&self.decoded[self.position..][..bytes_read]);
+        self.position += bytes_read;
+
+        Ok(bytes_read)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn helper_armor(armor: ArmorSchemes, data: &[u8]) -> Vec<u8> {
+        let mut writer = ArmoringWriter::new(Vec::new(), armor, &[("Cipher", "aes128")]).unwrap();
+
+        for piece in data.chunks(5) {
+            writer.write_all(piece).unwrap();
+        }
+
+        writer.finish().unwrap()
+    }
+
+    fn helper_dearmor(armored: &[u8]) -> Result<Vec<u8>> {
+        let mut data = Vec::new();
+        DearmoringReader::new(armored)?.read_to_end(&mut data)?;
+
+        Ok(data)
+    }
+
+    #[test]
+    fn crc24_check_value() {
+        let left = crc24(CRC24_INIT, b"123456789");
+        let right = 0x21CF02;
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn base64_rfc4648_vectors() {
+        for (data, encoded) in [
+            (&b""[..], &b""[..]),
+            (b"f", b"Zg=="),
+            (b"fo", b"Zm8="),
+            (b"foo", b"Zm9v"),
+            (b"foob", b"Zm9vYg=="),
+            (b"fooba", b"Zm9vYmE="),
+            (b"foobar", b"Zm9vYmFy"),
+        ] {
+            let left = base64_encode(data);
+            let right = encoded.to_vec();
+
+            assert_eq!(left, right);
+
+            let left = helper_dearmor(&[encoded, b"\n"].concat()).unwrap();
+            let right = data.to_vec();
+
+            assert_eq!(left, right);
+        }
+    }
+
+    #[test]
+    fn round_trip_in_every_armor() {
+        let data: Vec<u8> = (0..=255).cycle().take(1000).collect();
+
+        for armor in [ArmorSchemes::Hex, ArmorSchemes::Base64, ArmorSchemes::Pem] {
+            let left = helper_dearmor(&helper_armor(armor, &data)).unwrap();
+            let right = data.clone();
+
+            assert_eq!(left, right);
+        }
+    }
+
+    #[test]
+    fn armor_is_detected() {
+        let data = b"\x00\x01binary\xFF";
+
+        for (armor, expected) in [
+            (ArmorSchemes::Hex, Some(ArmorSchemes::Hex)),
+            (ArmorSchemes::Base64, Some(ArmorSchemes::Base64)),
+            (ArmorSchemes::Pem, Some(ArmorSchemes::Pem)),
+        ] {
+            let armored = helper_armor(armor, data);
+            let reader = DearmoringReader::new(&armored[..]).unwrap();
+
+            let left = reader.armor().cloned();
+            let right = expected;
+
+            assert_eq!(left, right);
+        }
+
+        let left = helper_dearmor(data).unwrap();
+        let right = data.to_vec();
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn pem_carries_fields() {
+        let armored = helper_armor(ArmorSchemes::Pem, b"data");
+        let reader = DearmoringReader::new(&armored[..]).unwrap();
+
+        let left = reader.fields().to_vec();
+        let right = vec![("Cipher".to_string(), "aes128".to_string())];
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn pem_checksum_catches_damage() {
+        let mut armored = helper_armor(ArmorSchemes::Pem, &[0x42; 100]);
+        // First body character, right after the blank line ending the header
+        let body_start = armored.windows(2).position(|pair| pair == b"\n\n").unwrap() + 2;
+        armored[body_start] = match armored[body_start] {
+            b'A' => b'B',
+            _ => b'A',
+        };
+
+        let left = helper_dearmor(&armored);
+
+        assert!(matches!(left, Err(SymciphError::Format(_))));
+    }
+
+    #[test]
+    fn truncated_armor_fails() {
+        for armor in [ArmorSchemes::Base64, ArmorSchemes::Pem] {
+            let armored = helper_armor(armor, &[0x42; 100]);
+
+            // Cuts into the last group, plain Base64 cut at a group boundary still decodes
+            let left = helper_dearmor(&armored[..armored.len() - 3]);
+
+            assert!(matches!(left, Err(SymciphError::Format(_))));
+        }
+    }
+}
@@ -1,12 +1,25 @@
+mod check_key;
 mod decrypt;
 mod encrypt;
 mod generate_key;
+mod kdf_bench;
 
-use std::io::Result;
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader, BufWriter, Read, Stdout, Write},
+};
+
+use clap::ValueEnum;
+
+use crate::{
+    error::{Result, SymciphError},
+    pipeline::LazyFile,
+};
 
 use super::Commands;
 
-const READ_BUFFER_LEN: usize = 2;
+// Stands for stdin or stdout in place of a path
+const STDIO_PATH: &str = "-";
 
 trait Command {
     fn run(&self) -> Result<()>;
@@ -17,9 +30,138 @@ impl Commands {
         let cmd: Box<&dyn Command> = match self {
             Commands::Encrypt(encrypt) => Box::new(encrypt),
             Commands::Decrypt(decrypt) => Box::new(decrypt),
+            Commands::CheckKey(check_key) => Box::new(check_key),
             Commands::GenerateKey(generate_key) => Box::new(generate_key),
+            Commands::KdfBench(kdf_bench) => Box::new(kdf_bench),
         };
 
         cmd.run()
     }
 }
+
+fn read_password(password_fd: Option<u32>, confirm: bool) -> Result<String> {
+    let password = match password_fd {
+        Some(fd) => {
+            let mut line = String::new();
+            BufReader::new(File::open(fd_path(fd))?).read_line(&mut line)?;
+
+            line.trim_end_matches(['\n', '\r']).to_string()
+        }
+        None => {
+            let password = rpassword::prompt_password("Password: ")?;
+
+            if confirm && rpassword::prompt_password("Confirm password: ")? != password {
+                return Err(SymciphError::InvalidInput(
+                    "🔑🙀: Passwords do not match.".to_string(),
+                ));
+            }
+
+            password
+        }
+    };
+
+    if password.is_empty() {
+        return Err(SymciphError::InvalidInput(
+            "🔑🙀: Password cannot be empty.".to_string(),
+        ));
+    }
+
+    Ok(password)
+}
+
+// Names as given on the command line, for PEM-style armor header fields
+fn value_name(value: &impl ValueEnum) -> String {
+    value
+        .to_possible_value()
+        .map(|value| value.get_name().to_string())
+        .unwrap_or_default()
+}
+
+// Inherited descriptors show up under /dev/fd, which spares handling raw descriptors
+fn fd_path(fd: u32) -> String {
+    format!("/dev/fd/{}", fd)
+}
+
+fn key_source(key_path: Option<&String>, key_fd: Option<u32>) -> Option<String> {
+    match (key_path, key_fd) {
+        (_, Some(fd)) => Some(fd_path(fd)),
+        (Some(key_path), None) if key_path == STDIO_PATH => Some("/dev/stdin".to_string()),
+        (Some(key_path), None) => Some(key_path.clone()),
+        (None, None) => None,
+    }
+}
+
+// Stdin carries a single stream, the data would otherwise be read as a key or password
+fn check_stdin_use(
+    input_path: &str,
+    key_source: Option<&str>,
+    password_fd: Option<u32>,
+) -> Result<()> {
+    let stdin_uses = [
+        input_path == STDIO_PATH,
+        matches!(key_source, Some("/dev/stdin" | "/dev/fd/0")),
+        password_fd == Some(0),
+    ];
+
+    match stdin_uses.iter().filter(|&&stdin_use| stdin_use).count() {
+        0 | 1 => Ok(()),
+        _ => Err(SymciphError::InvalidInput(
+            "📝🙀: Only one of the input, the key and the password can be read from stdin."
+                .to_string(),
+        )),
+    }
+}
+
+fn open_input(input_path: &str) -> Result<Box<dyn Read>> {
+    match input_path {
+        STDIO_PATH => Ok(Box::new(io::stdin().lock())),
+        _ => Ok(Box::new(File::open(input_path)?)),
+    }
+}
+
+// Nothing but data ever goes to stdout, diagnostics go to stderr and prompts to the terminal
+enum Output {
+    Stdout(BufWriter<Stdout>),
+    File(LazyFile),
+}
+
+impl Output {
+    // Files are only created on the first write, so a failed tag check leaves nothing behind
+    fn new(output_path: &str) -> Self {
+        match output_path {
+            STDIO_PATH => Output::Stdout(BufWriter::new(io::stdout())),
+            _ => Output::File(LazyFile::new(output_path)),
+        }
+    }
+
+    // Created up front instead, for commands that always leave an output file behind
+    fn create(output_path: &str) -> Result<Self> {
+        let mut output = Self::new(output_path);
+        output.finish()?;
+
+        Ok(output)
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        match self {
+            Output::Stdout(stdout) => Ok(stdout.flush()?),
+            Output::File(file) => file.create(),
+        }
+    }
+}
+
+impl Write for Output {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Output::Stdout(stdout) => stdout.write(buf),
+            Output::File(file) => file.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Output::Stdout(stdout) => stdout.flush(),
+            Output::File(file) => file.flush(),
+        }
+    }
+}
@@ -0,0 +1,29 @@
+use crate::{
+    error::{Result, SymciphError},
+    io::read_key,
+    pipeline::check_key_strength,
+};
+
+use super::{super::CheckKey, Command};
+
+impl Command for CheckKey {
+    fn run(&self) -> Result<()> {
+        let key = read_key(&self.key)?;
+
+        if !self.mode.supports_key_size(&self.cipher, key.len()) {
+            return Err(SymciphError::KeyLength(
+                "🔑🙀: Key length does not match the selected cipher and mode.".to_string(),
+            ));
+        }
+
+        check_key_strength(&self.mode, &self.cipher, &key)?;
+
+        // DES ignores parity bits, so keys without them still work here but not everywhere
+        match self.cipher.has_key_parity(&key) {
+            true => println!("Key is sound."),
+            false => println!("Key is sound, but its parity bits are not set."),
+        }
+
+        Ok(())
+    }
+}
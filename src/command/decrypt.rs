@@ -1,41 +1,124 @@
-use std::{fs::File, io::Result};
+use std::{fs::File, io::Seek};
+
+use clap::ValueEnum;
 
 use crate::{
-    io::{read_chunks, read_key, write_chunks},
-    BLOCK_CHUNK_SIZE,
+    error::{Result, SymciphError},
+    io::read_key,
+    pipeline::check_password_support,
+    DearmoringReader, Decryptor,
 };
 
-use super::{super::Decrypt, Command, READ_BUFFER_LEN};
+use super::{
+    super::{Decrypt, EncryptionSchemes},
+    check_stdin_use, key_source, open_input, read_password, Command, Output, STDIO_PATH,
+};
 
 impl Command for Decrypt {
     fn run(&self) -> Result<()> {
-        let mut input_file = File::open(&self.input_path)?;
-        let mut output_file = File::create(&self.output_path)?;
+        let (cipher, input_path, output_path, key_path) = self.split_positionals()?;
+
+        let decryptor = Decryptor::new()
+            .raw(self.raw)
+            .mode(self.mode.clone())
+            .padding(self.padding.clone())
+            .aad(self.aad.as_bytes())
+            .counter_bits(self.counter_bits)
+            .nonce_len(self.nonce_len)
+            .sector_size(self.sector_size)
+            .start_sector(self.start_sector)
+            .segment_size(self.segment_size);
 
-        let key = read_key(&self.key)?;
-        let encryption_scheme = self.cipher.strategy(&key);
+        let decryptor = match cipher {
+            Some(cipher) => decryptor.cipher(cipher),
+            None => decryptor,
+        };
 
-        let read_buffer = &mut [[0u8; BLOCK_CHUNK_SIZE]; READ_BUFFER_LEN];
+        let decryptor = match self.tag_len {
+            Some(tag_len) => decryptor.tag_len(tag_len),
+            None => decryptor,
+        };
 
-        loop {
-            let (num_chunks, bytes_read) = read_chunks(&mut input_file, read_buffer)?;
+        let key_source = key_source(key_path, self.key_fd);
+        check_stdin_use(input_path, key_source.as_deref(), self.password_fd)?;
 
-            if num_chunks == 0 {
-                break;
+        let decryptor = match key_source {
+            Some(key_source) => decryptor.key(&read_key(&key_source)?.concat()),
+            None => {
+                // Refused before prompting, headerless files have nowhere to keep the salt
+                check_password_support(self.raw)?;
+
+                decryptor.password(&read_password(self.password_fd, false)?)
             }
+        };
+
+        let mut output = Output::new(output_path);
+
+        if self.offset > 0 || self.length.is_some() {
+            if input_path == STDIO_PATH {
+                return Err(SymciphError::InvalidInput(
+                    "🧩🙀: Byte ranges need a seekable input, stdin cannot be used.".to_string(),
+                ));
+            }
+
+            let mut input_file = File::open(input_path)?;
+
+            if DearmoringReader::new(&mut input_file)?.armor().is_some() {
+                return Err(SymciphError::InvalidInput(
+                    "🧩🙀: Byte ranges need a binary input, armored ciphertext cannot be seeked."
+                        .to_string(),
+                ));
+            }
+
+            input_file.rewind()?;
+            decryptor.decrypt_range(&mut input_file, &mut output, self.offset, self.length)?;
 
-            write_chunks(
-                &mut output_file,
-                encryption_scheme
-                    .decrypt(&read_buffer[..num_chunks])
-                    .as_slice(),
-            )?;
+            return output.finish();
+        }
+
+        let mut input = DearmoringReader::new(open_input(input_path)?)?;
+
+        match input.armor() {
+            // Binary files go through decrypt_file, which knows when to create the plaintext file
+            None if input_path != STDIO_PATH && output_path != STDIO_PATH => {
+                decryptor.decrypt_file(input_path, output_path)
+            }
+            _ => {
+                decryptor.decrypt(&mut input, &mut output)?;
 
-            if bytes_read < BLOCK_CHUNK_SIZE {
-                break;
+                output.finish()
             }
         }
+    }
+}
 
-        Ok(())
+impl Decrypt {
+    // The cipher may be left out in front of the paths when the container header names it,
+    // and the key file path is left out when the key is derived from a password or read from
+    // a file descriptor
+    fn split_positionals(
+        &self,
+    ) -> Result<(Option<EncryptionSchemes>, &str, &str, Option<&String>)> {
+        let key_path_len =
+            match self.password || self.password_fd.is_some() || self.key_fd.is_some() {
+                true => 0,
+                false => 1,
+            };
+
+        let (paths, key_path) = self
+            .positionals
+            .split_at(self.positionals.len().saturating_sub(key_path_len));
+
+        match paths {
+            [cipher, input_path, output_path] => {
+                let cipher = EncryptionSchemes::from_str(cipher, true).map_err(|_| {
+                    SymciphError::InvalidInput(format!("🧩🙀: Unknown cipher {}.", cipher))
+                })?;
+
+                Ok((Some(cipher), input_path, output_path, key_path.first()))
+            }
+            [input_path, output_path] => Ok((None, input_path, output_path, key_path.first())),
+            _ => Err(SymciphError::InvalidInput("🧩🙀: Expected [CIPHER] INPUT_PATH OUTPUT_PATH, followed by KEY unless --password or --key-fd is given.".to_string())),
+        }
     }
 }
@@ -1,41 +1,84 @@
-use std::{fs::File, io::Result};
+use std::fs::metadata;
 
 use crate::{
-    io::{read_chunks, read_key, write_chunks},
-    BLOCK_CHUNK_SIZE,
+    error::Result,
+    io::read_key,
+    pipeline::{check_encrypt_support, check_password_support},
+    ArmoringWriter, Encryptor,
 };
 
-use super::{super::Encrypt, Command, READ_BUFFER_LEN};
+use super::{
+    super::Encrypt, check_stdin_use, key_source, open_input, read_password, value_name, Command,
+    Output, STDIO_PATH,
+};
 
 impl Command for Encrypt {
     fn run(&self) -> Result<()> {
-        let mut input_file = File::open(&self.input_path)?;
-        let mut output_file = File::create(&self.output_path)?;
+        check_encrypt_support(&self.cipher)?;
+
+        let key_source = key_source(self.key.as_ref(), self.key_fd);
+        check_stdin_use(&self.input_path, key_source.as_deref(), self.password_fd)?;
+
+        let mut input = open_input(&self.input_path)?;
 
-        let key = read_key(&self.key)?;
-        let encryption_scheme = self.cipher.strategy(&key);
+        let encryptor = Encryptor::new(self.cipher.clone())
+            .mode(self.mode.clone())
+            .padding(self.padding.clone())
+            .kdf(self.kdf.clone())
+            .iterations(self.iterations)
+            .memory_cost(self.memory_cost)
+            .time_cost(self.time_cost)
+            .parallelism(self.parallelism)
+            .raw(self.raw)
+            .aad(self.aad.as_bytes())
+            .counter_bits(self.counter_bits)
+            .nonce_len(self.nonce_len)
+            .sector_size(self.sector_size)
+            .start_sector(self.start_sector)
+            .segment_size(self.segment_size);
+
+        let encryptor = match self.tag_len {
+            Some(tag_len) => encryptor.tag_len(tag_len),
+            None => encryptor,
+        };
+
+        // Refused before the output file is created, so nothing is left behind
+        if self.input_path != STDIO_PATH {
+            encryptor.check_input_len(metadata(&self.input_path)?.len())?;
+        }
 
-        let read_buffer = &mut [[0u8; BLOCK_CHUNK_SIZE]; READ_BUFFER_LEN];
+        let mut output = Output::create(&self.output_path)?;
 
-        loop {
-            let (num_chunks, bytes_read) = read_chunks(&mut input_file, read_buffer)?;
+        let encryptor = match (self.password || self.password_fd.is_some(), &key_source) {
+            (true, _) => {
+                // Refused before prompting, there would be nowhere to keep the salt anyway
+                check_password_support(self.raw)?;
 
-            if num_chunks == 0 {
-                break;
+                encryptor.password(&read_password(self.password_fd, true)?)
             }
+            (false, Some(key_source)) => encryptor.key(&read_key(key_source)?.concat()),
+            // clap requires a key unless a password is used, so this will never happen
+            (false, None) => unreachable!(
+                "🙀🧨 Encrypt: Neither key file nor password. This was not supposed to happen."
+            ),
+        };
 
-            write_chunks(
-                &mut output_file,
-                encryption_scheme
-                    .encrypt(&read_buffer[..num_chunks])
-                    .as_slice(),
-            )?;
+        match &self.armor {
+            Some(armor) => {
+                let cipher = value_name(&self.cipher);
+                let mode = value_name(&self.mode);
+                let mut armored_output = ArmoringWriter::new(
+                    &mut output,
+                    armor.clone(),
+                    &[("Cipher", &cipher), ("Mode", &mode)],
+                )?;
 
-            if bytes_read < BLOCK_CHUNK_SIZE {
-                break;
+                encryptor.encrypt(&mut input, &mut armored_output)?;
+                armored_output.finish()?;
             }
+            None => encryptor.encrypt(&mut input, &mut output)?,
         }
 
-        Ok(())
+        output.finish()
     }
 }
@@ -1,48 +1,74 @@
-use std::{
-    fs::remove_file,
-    io::{Error, ErrorKind, Result},
-    time::{SystemTime, UNIX_EPOCH},
-};
-
-use rand::Rng;
+use std::{fs::remove_file, io::Error};
 
-use crate::{io::write_key, DataChunk};
+use crate::{
+    drbg::CtrDrbg,
+    error::{Result, SymciphError},
+    io::{write_armored_key, write_key},
+    mode::to_chunks,
+    pipeline::{check_key_strength, check_key_support},
+    EncryptionSchemes, BLOCK_CHUNK_SIZE,
+};
 
-use super::{super::GenerateKey, Command};
+use super::{super::GenerateKey, value_name, Command};
 
 impl Command for GenerateKey {
     fn run(&self) -> Result<()> {
-        let key_chunks_num = self.cipher.key_size();
-        let mut generated_key: Vec<DataChunk> = Vec::new();
+        // Keying options 1, 2 and 3 take three, two and one DES keys
+        let key_chunks_num = match (&self.cipher, self.keying_option) {
+            (EncryptionSchemes::Tdes, keying_option) => {
+                self.mode.key_size(&self.cipher) / self.cipher.key_size()
+                    * usize::from(4 - keying_option.unwrap_or(1))
+            }
+            (_, Some(_)) => {
+                return Err(SymciphError::InvalidInput(
+                    "🧩🙀: Keying options only apply to 3DES keys.".to_string(),
+                ))
+            }
+            (_, None) => self.mode.key_size(&self.cipher),
+        };
 
-        for _ in 0..key_chunks_num {
-            generated_key.push(generate_key_chunk());
-        }
+        // A key for a pair that encrypt rejects would be of no use
+        check_key_support(&self.mode, &self.cipher, key_chunks_num)?;
+
+        let mut drbg = CtrDrbg::new(self.prediction_resistance)?;
+
+        // Weak DES keys are rare enough that drawing a fresh key is cheaper than repairing one
+        let generated_key = loop {
+            let mut key = to_chunks(&drbg.generate(key_chunks_num * BLOCK_CHUNK_SIZE, &[])?);
+            self.cipher.set_key_parity(&mut key);
+
+            if check_key_strength(&self.mode, &self.cipher, &key).is_ok() {
+                break key;
+            }
+        };
 
-        if let Err(write_key_error) = write_key(&self.output_path, &generated_key) {
+        let written = match &self.armor {
+            Some(armor) => {
+                let cipher = value_name(&self.cipher);
+                let mode = value_name(&self.mode);
+
+                write_armored_key(
+                    &self.output_path,
+                    &generated_key,
+                    armor.clone(),
+                    &[("Cipher", &cipher), ("Mode", &mode)],
+                )
+            }
+            None => write_key(&self.output_path, &generated_key),
+        };
+
+        if let Err(write_key_error) = written {
             let deletion_status_message = match remove_file(&self.output_path) {
                 Ok(_) => "Incomplete file deleted",
                 Err(_) => "Attempted to delete file but failed 😩",
             };
 
-            return Err(Error::new(
-                ErrorKind::InvalidData,
-                format!("{} {}", write_key_error, deletion_status_message,),
-            ));
+            return Err(SymciphError::Io(Error::other(format!(
+                "{} {}",
+                write_key_error, deletion_status_message,
+            ))));
         }
 
         Ok(())
     }
 }
-
-fn generate_key_chunk() -> DataChunk {
-    let mut rng = rand::thread_rng();
-    let mask = u64::MAX as u128;
-
-    let now = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .expect("⏱️🪄😵‍💫: System time before Unix time. The OS is misconfigured")
-        .as_nanos();
-
-    ((now & mask) as u64 ^ rng.gen::<u64>()).to_be_bytes()
-}
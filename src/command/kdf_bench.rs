@@ -0,0 +1,79 @@
+use std::time::{Duration, Instant};
+
+use crate::{
+    error::Result,
+    kdf::{Kdf, KdfCost},
+    pipeline::{MAX_ITERATIONS, MAX_TIME_COST},
+};
+
+use super::{
+    super::{KdfBench, KdfSchemes},
+    Command,
+};
+
+const BENCH_PASSWORD: &[u8] = b"correct horse battery staple";
+const BENCH_KEY_LEN: usize = 32;
+const BENCH_ITERATIONS: u32 = 10_000;
+
+impl Command for KdfBench {
+    fn run(&self) -> Result<()> {
+        let target = Duration::from_millis(self.target_ms);
+        let mut cost = KdfCost {
+            iterations: BENCH_ITERATIONS,
+            memory_kib: self.memory_cost,
+            time_cost: 1,
+            parallelism: self.parallelism,
+        };
+
+        // Memory-hard functions shed memory until a single pass fits in the target
+        if !matches!(self.kdf, KdfSchemes::Pbkdf2) {
+            while cost.memory_kib / 2 >= 8 * cost.parallelism && time(&self.kdf.kdf(&cost)) > target
+            {
+                cost.memory_kib /= 2;
+            }
+        }
+
+        // The time left over buys iterations or passes, both of which cost linear time
+        let scale = target.as_secs_f64() / time(&self.kdf.kdf(&cost)).as_secs_f64();
+
+        let flags = match self.kdf {
+            KdfSchemes::Pbkdf2 => {
+                cost.iterations =
+                    ((BENCH_ITERATIONS as f64 * scale) as u32).clamp(1000, MAX_ITERATIONS);
+
+                format!("--kdf pbkdf2 --iterations {}", cost.iterations)
+            }
+            KdfSchemes::Scrypt => {
+                cost.parallelism = (cost.parallelism as f64 * scale).clamp(1.0, 255.0) as u32;
+
+                format!(
+                    "--kdf scrypt --memory-cost {} --parallelism {}",
+                    cost.memory_kib, cost.parallelism
+                )
+            }
+            KdfSchemes::Argon2id => {
+                cost.time_cost = (scale as u32).clamp(1, MAX_TIME_COST);
+
+                format!(
+                    "--kdf argon2id --memory-cost {} --time-cost {} --parallelism {}",
+                    cost.memory_kib, cost.time_cost, cost.parallelism
+                )
+            }
+        };
+
+        println!(
+            "{} ({} ms on this machine)",
+            flags,
+            time(&self.kdf.kdf(&cost)).as_millis()
+        );
+
+        Ok(())
+    }
+}
+
+fn time(kdf: &Kdf) -> Duration {
+    let start = Instant::now();
+    kdf.derive_key(BENCH_PASSWORD, BENCH_KEY_LEN);
+
+    start.elapsed()
+}
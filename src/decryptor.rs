@@ -0,0 +1,572 @@
+use std::{
+    fs::File,
+    io::{self, Read, Seek, SeekFrom, Write},
+    path::Path,
+};
+
+use crate::{
+    error::{Result, SymciphError},
+    header::{Header, KDF_NONE},
+    io::{read_full, read_iv, DecryptingReader},
+    kdf::Kdf,
+    mode::{segment_counter_error, to_chunks, CtrMode, STREAM_TAG_LEN},
+    pipeline::{
+        check_counter_bits, check_message_len, check_mode_support, check_option,
+        check_password_support, check_sector_size, check_segment_size, check_stealing_len,
+        default_tag_len, key_chunks, password_key, process_batch, LazyFile, Secret, Transform,
+        DEFAULT_COUNTER_BITS, DEFAULT_NONCE_LEN, DEFAULT_SECTOR_SIZE, DEFAULT_SEGMENT_SIZE,
+    },
+    DataChunk, EncryptionModes, EncryptionSchemes, PaddingSchemes, BLOCK_CHUNK_SIZE,
+};
+
+const RANGE_BUFFER_LEN: usize = 0x1000;
+
+/// Decrypts containers written by `Encryptor` or `symciph encrypt`, and headerless files
+/// when `raw` is set.
+///
+/// Containers name their cipher, mode and options in the header, so only the key or password
+/// is needed. Headerless files need every option to match the ones used to encrypt them.
+pub struct Decryptor {
+    cipher: Option<EncryptionSchemes>,
+    mode: EncryptionModes,
+    padding: PaddingSchemes,
+    secret: Option<Secret>,
+    raw: bool,
+    aad: Vec<u8>,
+    counter_bits: usize,
+    nonce_len: usize,
+    tag_len: Option<usize>,
+    sector_size: usize,
+    start_sector: u64,
+    segment_size: usize,
+}
+
+// Everything needed to decrypt the payload once the header and key are in place
+struct Opened {
+    header: Header,
+    key: Vec<DataChunk>,
+    aad: Vec<u8>,
+}
+
+impl Decryptor {
+    pub fn new() -> Self {
+        Self {
+            cipher: None,
+            mode: EncryptionModes::Ecb,
+            padding: PaddingSchemes::Pkcs7,
+            secret: None,
+            raw: false,
+            aad: Vec::new(),
+            counter_bits: DEFAULT_COUNTER_BITS,
+            nonce_len: DEFAULT_NONCE_LEN,
+            tag_len: None,
+            sector_size: DEFAULT_SECTOR_SIZE,
+            start_sector: 0,
+            segment_size: DEFAULT_SEGMENT_SIZE,
+        }
+    }
+
+    /// Cipher to decrypt with, required for headerless files and checked against the header otherwise.
+    pub fn cipher(mut self, cipher: EncryptionSchemes) -> Self {
+        self.cipher = Some(cipher);
+        self
+    }
+
+    /// Key bytes, as read from a key file.
+    pub fn key(mut self, key: &[u8]) -> Self {
+        self.secret = Some(Secret::Key(key.to_vec()));
+        self
+    }
+
+    /// Password the key was derived from, the KDF and its parameters come from the header.
+    pub fn password(mut self, password: &str) -> Self {
+        self.secret = Some(Secret::Password(password.to_string()));
+        self
+    }
+
+    /// Reads a headerless file with only the IV or nonce in front of the ciphertext.
+    pub fn raw(mut self, raw: bool) -> Self {
+        self.raw = raw;
+        self
+    }
+
+    /// Mode of operation of a headerless file.
+    pub fn mode(mut self, mode: EncryptionModes) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Padding of a headerless file.
+    pub fn padding(mut self, padding: PaddingSchemes) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// Additional authenticated data in AEAD modes.
+    pub fn aad(mut self, aad: &[u8]) -> Self {
+        self.aad = aad.to_vec();
+        self
+    }
+
+    /// Counter size in bits of a headerless file in CTR mode.
+    pub fn counter_bits(mut self, counter_bits: usize) -> Self {
+        self.counter_bits = counter_bits;
+        self
+    }
+
+    /// Nonce length in bytes of a headerless file in AEAD modes.
+    pub fn nonce_len(mut self, nonce_len: usize) -> Self {
+        self.nonce_len = nonce_len;
+        self
+    }
+
+    /// Authentication tag length in bytes of a headerless file in AEAD modes, 16 or the cipher
+    /// block size if shorter by default.
+    pub fn tag_len(mut self, tag_len: usize) -> Self {
+        self.tag_len = Some(tag_len);
+        self
+    }
+
+    /// Data unit size in bytes of a headerless file in XTS mode.
+    pub fn sector_size(mut self, sector_size: usize) -> Self {
+        self.sector_size = sector_size;
+        self
+    }
+
+    /// Number of the first sector of a headerless file in XTS mode.
+    pub fn start_sector(mut self, start_sector: u64) -> Self {
+        self.start_sector = start_sector;
+        self
+    }
+
+    /// Plaintext segment size in bytes of a headerless file in GCM-STREAM mode.
+    pub fn segment_size(mut self, segment_size: usize) -> Self {
+        self.segment_size = segment_size;
+        self
+    }
+
+    /// Decrypts the input file into the output file. In GCM, CCM, OCB, EAX, SIV and GCM-SIV
+    /// modes the output file is only created once the tag verifies.
+    pub fn decrypt_file(
+        &self,
+        input_path: impl AsRef<Path>,
+        output_path: impl AsRef<Path>,
+    ) -> Result<()> {
+        let mut input_file = File::open(input_path)?;
+        let opened = self.open(&mut input_file)?;
+        let mut output_file = LazyFile::new(output_path);
+
+        if !opened.header.mode.is_authenticated() {
+            output_file.create()?;
+        }
+
+        io::copy(
+            &mut DecryptingReader::from_transform(input_file, transform(opened)?),
+            &mut output_file,
+        )?;
+
+        output_file.create()
+    }
+
+    /// Decrypts everything read from the input into the output.
+    pub fn decrypt(&self, input: &mut impl Read, output: &mut impl Write) -> Result<()> {
+        io::copy(&mut self.reader(input)?, output)?;
+
+        Ok(())
+    }
+
+    /// Wraps a reader to decrypt everything read from it, the container header or the IV
+    /// is read from it right away.
+    pub fn reader<R: Read>(&self, mut inner: R) -> Result<DecryptingReader<R>> {
+        let opened = self.open(&mut inner)?;
+
+        Ok(DecryptingReader::from_transform(inner, transform(opened)?))
+    }
+
+    /// Decrypts `length` plaintext bytes from `offset` on, or up to the end of the plaintext,
+    /// in CTR, XTS and GCM-STREAM modes. Only the blocks, sectors or segments covering the
+    /// range are read, the input is seeked past the rest.
+    pub fn decrypt_range(
+        &self,
+        input: &mut (impl Read + Seek),
+        output: &mut impl Write,
+        offset: u64,
+        length: Option<u64>,
+    ) -> Result<()> {
+        let Opened { header, key, aad } = self.open(input)?;
+
+        if !header.mode.is_seekable() {
+            return Err(SymciphError::InvalidInput(
+                "🧩🙀: Byte ranges can only be decrypted in CTR, XTS and GCM-STREAM modes."
+                    .to_string(),
+            ));
+        }
+
+        let data_start = input.stream_position()?;
+        let data_len = input.seek(SeekFrom::End(0))?.saturating_sub(data_start);
+
+        let unit_len = header.unit_size as u64;
+        let sealed_segment_len = unit_len + STREAM_TAG_LEN as u64;
+        // Every segment carries a tag, an empty input is still sealed as one empty segment
+        let num_segments = data_len.div_ceil(sealed_segment_len).max(1);
+
+        let plaintext_len = match header.mode.is_segmented() {
+            true => data_len.saturating_sub(num_segments * STREAM_TAG_LEN as u64),
+            false => data_len,
+        };
+
+        if offset > plaintext_len {
+            return Err(SymciphError::InvalidInput(
+                "🧩🙀: Range starts past the end of the plaintext.".to_string(),
+            ));
+        }
+
+        let end = length.map_or(plaintext_len, |length| {
+            plaintext_len.min(offset.saturating_add(length))
+        });
+
+        if end == offset {
+            return Ok(());
+        }
+
+        if header.mode.is_sector_based() {
+            let first_sector = offset / unit_len;
+            let num_sectors = end.div_ceil(unit_len) - first_sector;
+            let sector_mode = header.mode.sector_mode(&header.cipher, &key)?;
+
+            input.seek(SeekFrom::Start(data_start + first_sector * unit_len))?;
+
+            let sectors = Transform::sectors(
+                header.unit_size,
+                header.start_sector + first_sector,
+                move |sector_number, sector| sector_mode.decrypt_sector(sector_number, sector),
+            );
+
+            io::copy(
+                &mut DecryptingReader::from_transform(input.take(num_sectors * unit_len), sectors),
+                &mut RangeWriter::new(output, offset - first_sector * unit_len, end - offset),
+            )?;
+
+            return Ok(());
+        }
+
+        if header.mode.is_segmented() {
+            let first_segment = offset / unit_len;
+            let last_segment = (end - 1) / unit_len;
+            let first_segment_number =
+                u32::try_from(first_segment).map_err(|_| segment_counter_error())?;
+            let mut stream_mode = header
+                .mode
+                .segmented_mode(&header.cipher, &key, &header.iv)?;
+            let mut range_output =
+                RangeWriter::new(output, offset - first_segment * unit_len, end - offset);
+            let mut segment = vec![0u8; sealed_segment_len as usize];
+
+            input.seek(SeekFrom::Start(
+                data_start + first_segment * sealed_segment_len,
+            ))?;
+            stream_mode.seek(first_segment_number);
+
+            for segment_index in first_segment..=last_segment {
+                let bytes_read = read_full(input, &mut segment)?;
+
+                let plaintext = stream_mode
+                    .open_segment(
+                        &aad,
+                        &segment[..bytes_read],
+                        segment_index == num_segments - 1,
+                    )?
+                    .ok_or_else(segment_authentication_error)?;
+
+                range_output.write_all(&plaintext)?;
+            }
+
+            return Ok(());
+        }
+
+        let mut ctr_mode = CtrMode::new(
+            header.cipher.strategy(&key)?,
+            &to_chunks(&header.iv),
+            header.counter_bits,
+        )?;
+        let mut buffer = vec![0u8; RANGE_BUFFER_LEN];
+        let mut remaining = end - offset;
+
+        input.seek(SeekFrom::Start(data_start + offset))?;
+        ctr_mode.seek(offset);
+
+        while remaining > 0 {
+            let read_len = buffer.len().min(remaining as usize);
+            let bytes_read = read_full(input, &mut buffer[..read_len])?;
+
+            ctr_mode.apply_keystream(&mut buffer[..bytes_read])?;
+            output.write_all(&buffer[..bytes_read])?;
+
+            if bytes_read < read_len {
+                break;
+            }
+
+            remaining -= bytes_read as u64;
+        }
+
+        Ok(())
+    }
+
+    // Reads the header, or the IV or nonce of a headerless file, and settles the key
+    fn open(&self, input: &mut impl Read) -> Result<Opened> {
+        if let Some(Secret::Password(_)) = self.secret {
+            check_password_support(self.raw)?;
+        }
+
+        let header = match self.raw {
+            true => self.raw_header(input)?,
+            false => self.container_header(input)?,
+        };
+
+        let key = match (Kdf::from_header(header.kdf_id, &header.kdf_params)?, &self.secret) {
+            (Some(kdf), Some(Secret::Password(password))) => {
+                password_key(&kdf, password, &header.mode, &header.cipher)?
+            }
+            (None, Some(Secret::Key(key))) => key_chunks(key)?,
+            (Some(_), Some(Secret::Key(_))) => {
+                return Err(SymciphError::InvalidInput("🔑🙀: Key was derived from a password, decrypt with --password instead of a key file.".to_string()))
+            }
+            (None, Some(Secret::Password(_))) => {
+                return Err(SymciphError::InvalidInput("🔑🙀: Key was not derived from a password, a key file is required.".to_string()))
+            }
+            (_, None) => {
+                return Err(SymciphError::InvalidInput(
+                    "🔑🙀: Neither a key nor a password was given.".to_string(),
+                ))
+            }
+        };
+
+        check_mode_support(
+            &header.mode,
+            &header.cipher,
+            key.len(),
+            header.counter_bits,
+            header.iv.len(),
+            header.tag_len,
+        )?;
+
+        let aad = match self.raw {
+            true => self.aad.clone(),
+            false => [header.to_bytes(), self.aad.clone()].concat(),
+        };
+
+        Ok(Opened { header, key, aad })
+    }
+
+    // Headerless files take everything but the IV or nonce from the options
+    fn raw_header(&self, input: &mut impl Read) -> Result<Header> {
+        let cipher = self.cipher.clone().ok_or_else(|| {
+            SymciphError::InvalidInput(
+                "🧩🙀: Headerless files need the cipher to be given.".to_string(),
+            )
+        })?;
+
+        check_option("Counter bits", check_counter_bits(self.counter_bits))?;
+        check_option("Sector size", check_sector_size(self.sector_size))?;
+        check_option("Segment size", check_segment_size(self.segment_size))?;
+
+        let iv = match self.mode.is_authenticated() || self.mode.is_segmented() {
+            true => {
+                let mut nonce = vec![0u8; self.nonce_len];
+
+                if read_full(input, &mut nonce)? < self.nonce_len {
+                    return Err(SymciphError::Format(
+                        "🎲🙀: Input file too short to contain the nonce and tag.".to_string(),
+                    ));
+                }
+
+                nonce
+            }
+            false => read_iv(input, self.mode.iv_size(cipher.block_size()))?.concat(),
+        };
+
+        Ok(Header {
+            tag_len: self.tag_len.unwrap_or_else(|| default_tag_len(&cipher)),
+            cipher,
+            mode: self.mode.clone(),
+            padding: self.padding.clone(),
+            counter_bits: self.counter_bits,
+            unit_size: match self.mode.is_segmented() {
+                true => self.segment_size,
+                false => self.sector_size,
+            },
+            start_sector: self.start_sector,
+            kdf_id: KDF_NONE,
+            kdf_params: Vec::new(),
+            iv,
+        })
+    }
+
+    fn container_header(&self, input: &mut impl Read) -> Result<Header> {
+        let header = Header::read(input)?;
+
+        if self
+            .cipher
+            .as_ref()
+            .is_some_and(|cipher| *cipher != header.cipher)
+        {
+            return Err(SymciphError::InvalidInput(
+                "🧩🙀: Cipher does not match the one named in the container header.".to_string(),
+            ));
+        }
+
+        Ok(header)
+    }
+}
+
+impl Default for Decryptor {
+    fn default() -> Self {
+        Decryptor::new()
+    }
+}
+
+fn transform(Opened { header, key, aad }: Opened) -> Result<Transform> {
+    if header.mode.is_segmented() {
+        let mut stream_mode = header
+            .mode
+            .segmented_mode(&header.cipher, &key, &header.iv)?;
+
+        // Verified segments are released right away, a failure stops before anything unverified
+        return Ok(Transform::units(
+            header.unit_size + STREAM_TAG_LEN,
+            move |segment, last_segment| {
+                stream_mode
+                    .open_segment(&aad, segment, last_segment)?
+                    .ok_or_else(segment_authentication_error)
+            },
+        ));
+    }
+
+    if header.mode.is_authenticated() {
+        let (mode, cipher, nonce_len) =
+            (header.mode.clone(), header.cipher.clone(), header.iv.len());
+        let tag_len = header.tag_len;
+        let mut authenticated_mode =
+            header
+                .mode
+                .authenticated_mode(&header.cipher, &key, &header.iv, tag_len)?;
+
+        // Nothing is released unless the tag verifies
+        return Ok(Transform::whole(move |ciphertext, _| {
+            if ciphertext.len() < tag_len {
+                return Err(SymciphError::Format(
+                    "🎲🙀: Input file too short to contain the nonce and tag.".to_string(),
+                ));
+            }
+
+            let message_len = (ciphertext.len() - tag_len) as u64;
+            check_message_len(&mode, &cipher, 0, nonce_len, 0, message_len)?;
+
+            authenticated_mode.open(&aad, ciphertext).ok_or_else(|| {
+                SymciphError::Authentication(
+                    "🔏🙀: Authentication failed, ciphertext or associated data was tampered with."
+                        .to_string(),
+                )
+            })
+        }));
+    }
+
+    if header.mode.is_sector_based() {
+        let sector_mode = header.mode.sector_mode(&header.cipher, &key)?;
+
+        return Ok(Transform::sectors(
+            header.unit_size,
+            header.start_sector,
+            move |sector_number, sector| sector_mode.decrypt_sector(sector_number, sector),
+        ));
+    }
+
+    let encryption_scheme = header.cipher.strategy(&key)?;
+    let iv = to_chunks(&header.iv);
+    let block_len = encryption_scheme.block_size() * BLOCK_CHUNK_SIZE;
+
+    if header.mode.is_ciphertext_stealing() {
+        let stealing_mode = header.mode.stealing_mode(encryption_scheme, &iv)?;
+
+        return Ok(Transform::whole(move |ciphertext, _| {
+            check_stealing_len(ciphertext.len(), block_len)?;
+
+            Ok(stealing_mode.decrypt(ciphertext))
+        }));
+    }
+
+    let mut encryption_mode = header
+        .mode
+        .mode(encryption_scheme, &iv, header.counter_bits)?;
+    let preserves_length = header.mode.preserves_length();
+    let padding = header.padding;
+
+    Ok(Transform::blocks(block_len, move |batch, last_batch| {
+        if !preserves_length && !batch.len().is_multiple_of(block_len) {
+            return Err(SymciphError::BlockAlignment(
+                "🧩🙀: Ciphertext is not a multiple of the block size.".to_string(),
+            ));
+        }
+
+        let mut plaintext = process_batch(batch, |chunks| encryption_mode.decrypt(chunks))?;
+
+        // Only the final block of the last batch carries padding
+        if last_batch && !preserves_length {
+            let last_block_start = plaintext.len().saturating_sub(block_len);
+            let data_len = padding.unpad(&plaintext[last_block_start..])?;
+
+            plaintext.truncate(last_block_start + data_len);
+        }
+
+        Ok(plaintext)
+    }))
+}
+
+/// Decrypts a container written by `encrypt_file` or `symciph encrypt` with a key.
+pub fn decrypt_file(
+    input_path: impl AsRef<Path>,
+    output_path: impl AsRef<Path>,
+    key: &[u8],
+) -> Result<()> {
+    Decryptor::new()
+        .key(key)
+        .decrypt_file(input_path, output_path)
+}
+
+fn segment_authentication_error() -> SymciphError {
+    SymciphError::Authentication("🔏🙀: Segment authentication failed, the stream was truncated, reordered or tampered with.".to_string())
+}
+
+// Drops the decrypted bytes in front of the range and everything past its end
+struct RangeWriter<'a, W: Write> {
+    output: &'a mut W,
+    skip: u64,
+    remaining: u64,
+}
+
+impl<'a, W: Write> RangeWriter<'a, W> {
+    fn new(output: &'a mut W, skip: u64, remaining: u64) -> Self {
+        Self {
+            output,
+            skip,
+            remaining,
+        }
+    }
+}
+
+impl<W: Write> Write for RangeWriter<'_, W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let skipped = buf.len().min(self.skip as usize);
+        let kept = (buf.len() - skipped).min(self.remaining as usize);
+
+        self.output.write_all(&buf[skipped..skipped + kept])?;
+        self.skip -= skipped as u64;
+        self.remaining -= kept as u64;
+
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.output.flush()
+    }
+}
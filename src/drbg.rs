@@ -0,0 +1,317 @@
+use std::io::{Error, ErrorKind, Result};
+
+use aes::Aes256Cipher;
+use crypto_primitives::block_cipher::BlockCipher;
+
+const KEY_LEN: usize = Aes256Cipher::KEY_SIZE;
+const BLOCK_LEN: usize = Aes256Cipher::BLOCK_SIZE;
+pub const SEED_LEN: usize = KEY_LEN + BLOCK_LEN;
+// SP 800-90A Table 3 limits for CTR_DRBG with AES-256 and no derivation function
+const RESEED_INTERVAL: u64 = 1 << 48;
+const MAX_REQUEST_LEN: usize = 1 << 16;
+
+const AES_KEY_LENGTH_BUG: &str =
+    "🙀🧨 DRBG: Key not 256 bits long. This was not supposed to happen.";
+
+type EntropySource = fn(&mut [u8]) -> Result<()>;
+
+// NIST SP 800-90A CTR_DRBG over AES-256 without a derivation function, so
+// entropy input is full seed length and never needs to be conditioned
+pub struct CtrDrbg {
+    cipher: Aes256Cipher,
+    v: [u8; BLOCK_LEN],
+    reseed_counter: u64,
+    prediction_resistance: bool,
+    entropy_source: EntropySource,
+}
+
+impl CtrDrbg {
+    pub fn new(prediction_resistance: bool) -> Result<Self> {
+        Self::with_entropy_source(os_entropy, prediction_resistance)
+    }
+
+    fn with_entropy_source(
+        entropy_source: EntropySource,
+        prediction_resistance: bool,
+    ) -> Result<Self> {
+        let mut entropy = [0u8; SEED_LEN];
+        entropy_source(&mut entropy)?;
+
+        let mut drbg = Self::instantiate(&entropy, &[])?;
+        drbg.prediction_resistance = prediction_resistance;
+        drbg.entropy_source = entropy_source;
+
+        Ok(drbg)
+    }
+
+    pub fn instantiate(entropy: &[u8; SEED_LEN], personalization: &[u8]) -> Result<Self> {
+        let mut drbg = Self {
+            cipher: Aes256Cipher::new(&[0u8; KEY_LEN]).expect(AES_KEY_LENGTH_BUG),
+            v: [0u8; BLOCK_LEN],
+            reseed_counter: 1,
+            prediction_resistance: false,
+            entropy_source: os_entropy,
+        };
+
+        drbg.update(&seed_material(entropy, personalization)?);
+
+        Ok(drbg)
+    }
+
+    pub fn reseed(&mut self, entropy: &[u8; SEED_LEN], additional_input: &[u8]) -> Result<()> {
+        self.update(&seed_material(entropy, additional_input)?);
+        self.reseed_counter = 1;
+
+        Ok(())
+    }
+
+    pub fn generate(&mut self, len: usize, additional_input: &[u8]) -> Result<Vec<u8>> {
+        if len > MAX_REQUEST_LEN {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!(
+                    "🎲🙀: At most {} random bytes per request, {} requested.",
+                    MAX_REQUEST_LEN, len
+                ),
+            ));
+        }
+
+        // Additional input is consumed by the reseed, so generation proceeds without it
+        let additional_input =
+            match self.prediction_resistance || self.reseed_counter > RESEED_INTERVAL {
+                true => {
+                    let mut entropy = [0u8; SEED_LEN];
+                    (self.entropy_source)(&mut entropy)?;
+                    self.reseed(&entropy, additional_input)?;
+
+                    [0u8; SEED_LEN]
+                }
+                false => {
+                    let additional_input = seed_material(&[0u8; SEED_LEN], additional_input)?;
+
+                    if additional_input != [0u8; SEED_LEN] {
+                        self.update(&additional_input);
+                    }
+
+                    additional_input
+                }
+            };
+
+        let mut output = Vec::with_capacity(len + BLOCK_LEN);
+
+        while output.len() < len {
+            output.extend(self.next_block());
+        }
+
+        output.truncate(len);
+        self.update(&additional_input);
+        self.reseed_counter += 1;
+
+        Ok(output)
+    }
+
+    fn next_block(&mut self) -> [u8; BLOCK_LEN] {
+        self.v = (u128::from_be_bytes(self.v).wrapping_add(1)).to_be_bytes();
+
+        let mut block = self.v;
+        self.cipher.encrypt_block(&mut block);
+
+        block
+    }
+
+    fn update(&mut self, provided_data: &[u8; SEED_LEN]) {
+        let mut temp = [self.next_block(), self.next_block(), self.next_block()].concat();
+
+        for (byte, provided_byte) in temp.iter_mut().zip(provided_data) {
+            *byte ^= provided_byte;
+        }
+
+        self.cipher = Aes256Cipher::new(&temp[..KEY_LEN]).expect(AES_KEY_LENGTH_BUG);
+        self.v.copy_from_slice(&temp[KEY_LEN..]);
+    }
+}
+
+// Without a derivation function the string is XORed into the entropy input
+fn seed_material(entropy: &[u8; SEED_LEN], input: &[u8]) -> Result<[u8; SEED_LEN]> {
+    if input.len() > SEED_LEN {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            format!(
+                "🎲🙀: DRBG input is {} bytes, at most {} bytes are supported.",
+                input.len(),
+                SEED_LEN
+            ),
+        ));
+    }
+
+    let mut seed_material = *entropy;
+
+    for (byte, input_byte) in seed_material.iter_mut().zip(input) {
+        *byte ^= input_byte;
+    }
+
+    Ok(seed_material)
+}
+
+fn os_entropy(entropy: &mut [u8]) -> Result<()> {
+    getrandom::getrandom(entropy).map_err(|os_error| {
+        Error::other(format!(
+            "🎲🙀: Operating system entropy unavailable: {}",
+            os_error
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use sha256::Sha256;
+
+    use super::*;
+
+    fn helper_get_bytes(hex: &str) -> Vec<u8> {
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    fn helper_get_seed(hex: &str) -> [u8; SEED_LEN] {
+        helper_get_bytes(hex).try_into().unwrap()
+    }
+
+    fn helper_counting_entropy(entropy: &mut [u8]) -> Result<()> {
+        for (i, byte) in entropy.iter_mut().enumerate() {
+            *byte = i as u8;
+        }
+
+        Ok(())
+    }
+
+    // CAVP CTR_DRBG.rsp, [AES-256 no df] [PredictionResistance = False], COUNT = 0
+    #[test]
+    fn cavp_aes256_no_df_known_answer() {
+        let mut drbg = CtrDrbg::instantiate(
+            &helper_get_seed("e4bc23c5089a19d86f4119cb3fa08c0a4991e0a1def17e101e4c14d9c323460a7c2fb58e0b086c6c57b55f56cae25bad"),
+            &[],
+        )
+        .unwrap();
+
+        assert_eq!(drbg.reseed_counter, 1);
+
+        drbg.reseed(
+            &helper_get_seed("fd85a836bba85019881e8c6bad23c9061adc75477659acaea8e4a01dfe07a1832dad1c136f59d70f8653a5dc118663d6"),
+            &[],
+        )
+        .unwrap();
+        drbg.generate(64, &[]).unwrap();
+
+        let left = drbg.generate(64, &[]).unwrap();
+        let right = helper_get_bytes("b2cb8905c05e5950ca31895096be29ea3d5a3b82b269495554eb80fe07de43e193b9e7c3ece73b80e062b1c1f68202fbb1c52a040ea2478864295282234aaada");
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn largest_request_known_answer() {
+        let mut drbg = CtrDrbg::instantiate(&[0u8; SEED_LEN], &[]).unwrap();
+
+        let left = Sha256::digest(&drbg.generate(MAX_REQUEST_LEN, &[]).unwrap());
+        let right =
+            helper_get_bytes("69781596cac03f6a6ded221e26d07549a04b91583cf4e36dff41bfb9f8a81c2b");
+
+        assert_eq!(left.as_slice(), right.as_slice());
+    }
+
+    #[test]
+    fn reseed_counter_counts_requests() {
+        let mut drbg = CtrDrbg::instantiate(&[0u8; SEED_LEN], &[]).unwrap();
+
+        drbg.generate(16, &[]).unwrap();
+        drbg.generate(16, &[]).unwrap();
+
+        let left = drbg.reseed_counter;
+        let right = 3;
+
+        assert_eq!(left, right);
+
+        drbg.reseed(&[0u8; SEED_LEN], &[]).unwrap();
+
+        let left = drbg.reseed_counter;
+        let right = 1;
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn exhausted_reseed_counter_reseeds() {
+        let mut drbg = CtrDrbg::with_entropy_source(helper_counting_entropy, false).unwrap();
+        drbg.reseed_counter = RESEED_INTERVAL + 1;
+
+        drbg.generate(16, &[]).unwrap();
+
+        let left = drbg.reseed_counter;
+        let right = 2;
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn prediction_resistance_reseeds_every_request() {
+        let mut drbg = CtrDrbg::with_entropy_source(helper_counting_entropy, true).unwrap();
+        let mut entropy = [0u8; SEED_LEN];
+        helper_counting_entropy(&mut entropy).unwrap();
+        let mut expected = CtrDrbg::instantiate(&entropy, &[]).unwrap();
+
+        for _ in 0..3 {
+            expected.reseed(&entropy, &[]).unwrap();
+
+            let left = drbg.generate(32, &[]).unwrap();
+            let right = expected.generate(32, &[]).unwrap();
+
+            assert_eq!(left, right);
+        }
+
+        let left = drbg.reseed_counter;
+        let right = 2;
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn personalization_and_additional_input_change_output() {
+        let mut plain = CtrDrbg::instantiate(&[0u8; SEED_LEN], &[]).unwrap();
+        let mut personalized = CtrDrbg::instantiate(&[0u8; SEED_LEN], b"symciph").unwrap();
+
+        assert_ne!(
+            plain.generate(32, &[]).unwrap(),
+            personalized.generate(32, &[]).unwrap()
+        );
+        assert_ne!(
+            plain.generate(32, &[]).unwrap(),
+            personalized.generate(32, b"symciph").unwrap()
+        );
+    }
+
+    #[test]
+    fn oversized_request_rejected() {
+        let mut drbg = CtrDrbg::instantiate(&[0u8; SEED_LEN], &[]).unwrap();
+
+        assert!(drbg.generate(MAX_REQUEST_LEN + 1, &[]).is_err());
+    }
+
+    #[test]
+    fn oversized_additional_input_rejected() {
+        let mut drbg = CtrDrbg::instantiate(&[0u8; SEED_LEN], &[]).unwrap();
+
+        assert!(drbg.generate(16, &[0u8; SEED_LEN + 1]).is_err());
+        assert!(CtrDrbg::instantiate(&[0u8; SEED_LEN], &[0u8; SEED_LEN + 1]).is_err());
+    }
+
+    #[test]
+    fn os_seeded_instances_differ() {
+        let left = CtrDrbg::new(false).unwrap().generate(32, &[]).unwrap();
+        let right = CtrDrbg::new(false).unwrap().generate(32, &[]).unwrap();
+
+        assert_ne!(left, right);
+    }
+}
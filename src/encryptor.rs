@@ -0,0 +1,422 @@
+use std::{
+    fs::File,
+    io::{self, Read, Write},
+    path::Path,
+};
+
+use crate::{
+    error::{Result, SymciphError},
+    header::{Header, KDF_NONE},
+    io::EncryptingWriter,
+    kdf::{Kdf, KdfCost},
+    mode::to_chunks,
+    pipeline::{
+        check_counter_bits, check_encrypt_support, check_iterations, check_key_strength,
+        check_memory_cost, check_message_len, check_mode_support, check_option, check_parallelism,
+        check_password_support, check_sector_size, check_segment_size, check_stealing_len,
+        check_time_cost, default_tag_len, key_chunks, password_key, process_batch, LazyFile,
+        Secret, Transform, DEFAULT_COUNTER_BITS, DEFAULT_ITERATIONS, DEFAULT_MEMORY_COST,
+        DEFAULT_NONCE_LEN, DEFAULT_PARALLELISM, DEFAULT_SECTOR_SIZE, DEFAULT_SEGMENT_SIZE,
+        DEFAULT_TIME_COST,
+    },
+    DataChunk, EncryptionModes, EncryptionSchemes, KdfSchemes, PaddingSchemes, BLOCK_CHUNK_SIZE,
+};
+
+/// Encrypts streams and files with a chosen cipher, mode and key or password.
+///
+/// The defaults match those of `symciph encrypt`: ECB mode with PKCS#7 padding, written
+/// as a container whose header records everything needed to decrypt it.
+///
+/// ```
+/// use std::io::Cursor;
+///
+/// use symciph::{Decryptor, EncryptionModes, EncryptionSchemes, Encryptor};
+///
+/// let key = [0x2Bu8; 16];
+/// let mut ciphertext = Vec::new();
+/// let mut plaintext = Vec::new();
+///
+/// Encryptor::new(EncryptionSchemes::Aes128)
+///     .mode(EncryptionModes::Gcm)
+///     .key(&key)
+///     .encrypt(&mut Cursor::new(b"attack at dawn"), &mut ciphertext)?;
+///
+/// Decryptor::new()
+///     .key(&key)
+///     .decrypt(&mut Cursor::new(ciphertext), &mut plaintext)?;
+///
+/// assert_eq!(plaintext, b"attack at dawn");
+/// # Ok::<(), symciph::SymciphError>(())
+/// ```
+pub struct Encryptor {
+    cipher: EncryptionSchemes,
+    mode: EncryptionModes,
+    padding: PaddingSchemes,
+    secret: Option<Secret>,
+    kdf: KdfSchemes,
+    iterations: u32,
+    memory_cost: u32,
+    time_cost: u32,
+    parallelism: u32,
+    raw: bool,
+    aad: Vec<u8>,
+    counter_bits: usize,
+    nonce_len: usize,
+    tag_len: Option<usize>,
+    sector_size: usize,
+    start_sector: u64,
+    segment_size: usize,
+}
+
+impl Encryptor {
+    pub fn new(cipher: EncryptionSchemes) -> Self {
+        Self {
+            cipher,
+            mode: EncryptionModes::Ecb,
+            padding: PaddingSchemes::Pkcs7,
+            secret: None,
+            kdf: KdfSchemes::Argon2id,
+            iterations: DEFAULT_ITERATIONS,
+            memory_cost: DEFAULT_MEMORY_COST,
+            time_cost: DEFAULT_TIME_COST,
+            parallelism: DEFAULT_PARALLELISM,
+            raw: false,
+            aad: Vec::new(),
+            counter_bits: DEFAULT_COUNTER_BITS,
+            nonce_len: DEFAULT_NONCE_LEN,
+            tag_len: None,
+            sector_size: DEFAULT_SECTOR_SIZE,
+            start_sector: 0,
+            segment_size: DEFAULT_SEGMENT_SIZE,
+        }
+    }
+
+    /// Mode of operation, ECB by default.
+    pub fn mode(mut self, mode: EncryptionModes) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Padding of the final block in ECB, CBC and PCBC modes.
+    pub fn padding(mut self, padding: PaddingSchemes) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// Key bytes, as read from a key file.
+    pub fn key(mut self, key: &[u8]) -> Self {
+        self.secret = Some(Secret::Key(key.to_vec()));
+        self
+    }
+
+    /// Derives the key from a password, its salt and cost are stored in the container header.
+    pub fn password(mut self, password: &str) -> Self {
+        self.secret = Some(Secret::Password(password.to_string()));
+        self
+    }
+
+    /// Key derivation function applied to the password, Argon2id by default.
+    pub fn kdf(mut self, kdf: KdfSchemes) -> Self {
+        self.kdf = kdf;
+        self
+    }
+
+    /// PBKDF2 iteration count, between 1000 and 2^24.
+    pub fn iterations(mut self, iterations: u32) -> Self {
+        self.iterations = iterations;
+        self
+    }
+
+    /// Memory in KiB used by Argon2id and scrypt, at most 4 GiB.
+    pub fn memory_cost(mut self, memory_cost: u32) -> Self {
+        self.memory_cost = memory_cost;
+        self
+    }
+
+    /// Number of passes over memory in Argon2id, at most 32.
+    pub fn time_cost(mut self, time_cost: u32) -> Self {
+        self.time_cost = time_cost;
+        self
+    }
+
+    /// Number of lanes in Argon2id and of independent mixes in scrypt.
+    pub fn parallelism(mut self, parallelism: u32) -> Self {
+        self.parallelism = parallelism;
+        self
+    }
+
+    /// Writes only the IV or nonce in front of the ciphertext instead of a container header.
+    pub fn raw(mut self, raw: bool) -> Self {
+        self.raw = raw;
+        self
+    }
+
+    /// Additional authenticated data in AEAD modes.
+    pub fn aad(mut self, aad: &[u8]) -> Self {
+        self.aad = aad.to_vec();
+        self
+    }
+
+    /// Counter size in bits in CTR mode.
+    pub fn counter_bits(mut self, counter_bits: usize) -> Self {
+        self.counter_bits = counter_bits;
+        self
+    }
+
+    /// Nonce length in bytes in AEAD modes.
+    pub fn nonce_len(mut self, nonce_len: usize) -> Self {
+        self.nonce_len = nonce_len;
+        self
+    }
+
+    /// Authentication tag length in bytes in AEAD modes, 16 or the cipher block size if shorter
+    /// by default.
+    pub fn tag_len(mut self, tag_len: usize) -> Self {
+        self.tag_len = Some(tag_len);
+        self
+    }
+
+    /// Data unit size in bytes in XTS mode.
+    pub fn sector_size(mut self, sector_size: usize) -> Self {
+        self.sector_size = sector_size;
+        self
+    }
+
+    /// Number of the first sector in XTS mode.
+    pub fn start_sector(mut self, start_sector: u64) -> Self {
+        self.start_sector = start_sector;
+        self
+    }
+
+    /// Plaintext segment size in bytes in GCM-STREAM mode.
+    pub fn segment_size(mut self, segment_size: usize) -> Self {
+        self.segment_size = segment_size;
+        self
+    }
+
+    /// Encrypts the input file into the output file, which is only created once encryption starts.
+    pub fn encrypt_file(
+        &self,
+        input_path: impl AsRef<Path>,
+        output_path: impl AsRef<Path>,
+    ) -> Result<()> {
+        let mut input_file = File::open(input_path)?;
+        self.check_input_len(input_file.metadata()?.len())?;
+
+        let mut output_file = LazyFile::new(output_path);
+
+        self.encrypt(&mut input_file, &mut output_file)?;
+
+        output_file.create()
+    }
+
+    /// Encrypts everything read from the input into the output.
+    pub fn encrypt(&self, input: &mut impl Read, output: &mut impl Write) -> Result<()> {
+        let mut writer = self.writer(output)?;
+
+        io::copy(input, &mut writer)?;
+        writer.finish()?;
+
+        Ok(())
+    }
+
+    /// Wraps a writer to encrypt everything written to it, the container header or the IV
+    /// is written to it right away.
+    pub fn writer<W: Write>(&self, mut inner: W) -> Result<EncryptingWriter<W>> {
+        check_encrypt_support(&self.cipher)?;
+        self.check_options()?;
+
+        let (kdf, key) = match &self.secret {
+            Some(Secret::Password(password)) => {
+                check_password_support(self.raw)?;
+
+                let kdf = self.kdf.kdf(&KdfCost {
+                    iterations: self.iterations,
+                    memory_kib: self.memory_cost,
+                    time_cost: self.time_cost,
+                    parallelism: self.parallelism,
+                });
+
+                if !kdf.is_valid() {
+                    return Err(SymciphError::InvalidInput("🔑🙀: Key derivation parameters out of range, Argon2id needs 8 KiB of memory per lane and scrypt at most 64 GiB across all lanes.".to_string()));
+                }
+
+                let key = password_key(&kdf, password, &self.mode, &self.cipher)?;
+
+                (Some(kdf), key)
+            }
+            Some(Secret::Key(key)) => (None, key_chunks(key)?),
+            None => {
+                return Err(SymciphError::InvalidInput(
+                    "🔑🙀: Neither a key nor a password was given.".to_string(),
+                ))
+            }
+        };
+
+        let tag_len = self
+            .tag_len
+            .unwrap_or_else(|| default_tag_len(&self.cipher));
+
+        check_mode_support(
+            &self.mode,
+            &self.cipher,
+            key.len(),
+            self.counter_bits,
+            self.nonce_len,
+            tag_len,
+        )?;
+        check_key_strength(&self.mode, &self.cipher, &key)?;
+
+        let header = Header {
+            cipher: self.cipher.clone(),
+            mode: self.mode.clone(),
+            padding: self.padding.clone(),
+            counter_bits: self.counter_bits,
+            tag_len,
+            unit_size: match self.mode.is_segmented() {
+                true => self.segment_size,
+                false => self.sector_size,
+            },
+            start_sector: self.start_sector,
+            kdf_id: kdf.as_ref().map_or(KDF_NONE, Kdf::id),
+            kdf_params: kdf.as_ref().map_or(Vec::new(), Kdf::params),
+            iv: match self.mode.is_authenticated() || self.mode.is_segmented() {
+                true => self.mode.generate_nonce(self.nonce_len),
+                false => self.mode.generate_iv(self.cipher.block_size()).concat(),
+            },
+        };
+
+        // Headerless files only carry the IV or nonce, containers authenticate their header
+        let (prefix, aad) = match self.raw {
+            true => (header.iv.clone(), self.aad.clone()),
+            false => {
+                let header_bytes = header.to_bytes();
+
+                (
+                    header_bytes.clone(),
+                    [header_bytes, self.aad.clone()].concat(),
+                )
+            }
+        };
+
+        inner.write_all(&prefix)?;
+
+        Ok(EncryptingWriter::from_transform(
+            inner,
+            self.transform(&header, &key, aad)?,
+        ))
+    }
+
+    fn transform(&self, header: &Header, key: &[DataChunk], aad: Vec<u8>) -> Result<Transform> {
+        if self.mode.is_segmented() {
+            let mut stream_mode = self.mode.segmented_mode(&self.cipher, key, &header.iv)?;
+
+            return Ok(Transform::units(
+                header.unit_size,
+                move |segment, last_segment| stream_mode.seal_segment(&aad, segment, last_segment),
+            ));
+        }
+
+        if self.mode.is_authenticated() {
+            let mut authenticated_mode =
+                self.mode
+                    .authenticated_mode(&self.cipher, key, &header.iv, header.tag_len)?;
+
+            let (mode, cipher, nonce_len) =
+                (self.mode.clone(), self.cipher.clone(), self.nonce_len);
+
+            return Ok(Transform::whole(move |plaintext, _| {
+                check_message_len(&mode, &cipher, 0, nonce_len, 0, plaintext.len() as u64)?;
+
+                Ok(authenticated_mode.seal(&aad, plaintext))
+            }));
+        }
+
+        if self.mode.is_sector_based() {
+            let sector_mode = self.mode.sector_mode(&self.cipher, key)?;
+
+            return Ok(Transform::sectors(
+                self.sector_size,
+                self.start_sector,
+                move |sector_number, sector| sector_mode.encrypt_sector(sector_number, sector),
+            ));
+        }
+
+        let encryption_scheme = self.cipher.strategy(key)?;
+        let iv = to_chunks(&header.iv);
+        let block_len = encryption_scheme.block_size() * BLOCK_CHUNK_SIZE;
+
+        if self.mode.is_ciphertext_stealing() {
+            let stealing_mode = self.mode.stealing_mode(encryption_scheme, &iv)?;
+
+            return Ok(Transform::whole(move |plaintext, _| {
+                check_stealing_len(plaintext.len(), block_len)?;
+
+                Ok(stealing_mode.encrypt(plaintext))
+            }));
+        }
+
+        let mut encryption_mode = self.mode.mode(encryption_scheme, &iv, self.counter_bits)?;
+        let preserves_length = self.mode.preserves_length();
+        let padding = self.padding.clone();
+
+        Ok(Transform::blocks(block_len, move |batch, last_batch| {
+            let plaintext = match last_batch && !preserves_length {
+                true => {
+                    let full_blocks_len = batch.len() - batch.len() % block_len;
+
+                    [
+                        &batch[..full_blocks_len],
+                        &padding.pad(&batch[full_blocks_len..], block_len)?[..],
+                    ]
+                    .concat()
+                }
+                false => batch.to_vec(),
+            };
+
+            process_batch(&plaintext, |chunks| encryption_mode.encrypt(chunks))
+        }))
+    }
+
+    // Inputs of known length are refused up front, before any header is written
+    pub(crate) fn check_input_len(&self, input_len: u64) -> Result<()> {
+        check_message_len(
+            &self.mode,
+            &self.cipher,
+            self.counter_bits,
+            self.nonce_len,
+            self.segment_size,
+            input_len,
+        )
+    }
+
+    // The CLI parsers reject these already, library callers get the same reasons as errors
+    fn check_options(&self) -> Result<()> {
+        check_option("Counter bits", check_counter_bits(self.counter_bits))?;
+        check_option("Sector size", check_sector_size(self.sector_size))?;
+        check_option("Segment size", check_segment_size(self.segment_size))?;
+
+        if let Some(Secret::Password(_)) = self.secret {
+            check_option("Iterations", check_iterations(self.iterations))?;
+            check_option("Memory cost", check_memory_cost(self.memory_cost))?;
+            check_option("Time cost", check_time_cost(self.time_cost))?;
+            check_option("Parallelism", check_parallelism(self.parallelism))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Encrypts a file with a key into a container that `decrypt_file` and `symciph decrypt` read back.
+pub fn encrypt_file(
+    input_path: impl AsRef<Path>,
+    output_path: impl AsRef<Path>,
+    cipher: EncryptionSchemes,
+    mode: EncryptionModes,
+    key: &[u8],
+) -> Result<()> {
+    Encryptor::new(cipher)
+        .mode(mode)
+        .key(key)
+        .encrypt_file(input_path, output_path)
+}
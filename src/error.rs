@@ -0,0 +1,171 @@
+use std::{
+    fmt::{self, Display, Formatter},
+    io,
+};
+
+use crypto_primitives::block_cipher::BlockLengthError;
+
+pub type Result<T> = std::result::Result<T, SymciphError>;
+
+#[derive(Debug)]
+pub enum SymciphError {
+    InvalidInput(String),
+    KeyLength(String),
+    UnsoundKey(String),
+    BlockAlignment(String),
+    Padding(String),
+    Authentication(String),
+    Format(String),
+    Io(io::Error),
+}
+
+impl SymciphError {
+    // Invalid input shares its status with the argument errors clap reports itself
+    pub const fn exit_code(&self) -> i32 {
+        match self {
+            Self::Io(_) => 1,
+            Self::InvalidInput(_) => 2,
+            Self::KeyLength(_) => 3,
+            Self::UnsoundKey(_) => 4,
+            Self::BlockAlignment(_) => 5,
+            Self::Padding(_) => 6,
+            Self::Authentication(_) => 7,
+            Self::Format(_) => 8,
+        }
+    }
+}
+
+impl Display for SymciphError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidInput(message)
+            | Self::KeyLength(message)
+            | Self::UnsoundKey(message)
+            | Self::BlockAlignment(message)
+            | Self::Padding(message)
+            | Self::Authentication(message)
+            | Self::Format(message) => write!(f, "{}", message),
+            Self::Io(io_error) => write!(f, "{}", io_error),
+        }
+    }
+}
+
+impl std::error::Error for SymciphError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(io_error) => Some(io_error),
+            _ => None,
+        }
+    }
+}
+
+// Stream adapters can only fail with io::Error, so other errors travel inside one and are
+// taken out again when converted back
+impl From<io::Error> for SymciphError {
+    fn from(io_error: io::Error) -> Self {
+        match io_error
+            .get_ref()
+            .is_some_and(|inner| inner.is::<SymciphError>())
+        {
+            true => *io_error
+                .into_inner()
+                .and_then(|inner| inner.downcast().ok())
+                .expect("🙀🧨 Error: Wrapped error went missing. This was not supposed to happen."),
+            false => Self::Io(io_error),
+        }
+    }
+}
+
+impl From<SymciphError> for io::Error {
+    fn from(error: SymciphError) -> Self {
+        match error {
+            SymciphError::Io(io_error) => io_error,
+            _ => io::Error::other(error),
+        }
+    }
+}
+
+impl From<aes::key::KeyError> for SymciphError {
+    fn from(key_error: aes::key::KeyError) -> Self {
+        Self::KeyLength(format!("🔑🙀: {}.", key_error))
+    }
+}
+
+impl From<BlockLengthError> for SymciphError {
+    fn from(block_error: BlockLengthError) -> Self {
+        Self::BlockAlignment(format!("🧩🙀: {}.", block_error))
+    }
+}
+
+impl From<des::KeyError> for SymciphError {
+    fn from(key_error: des::KeyError) -> Self {
+        match key_error {
+            des::KeyError::Length(_) => Self::KeyLength(format!("🔑🙀: {}.", key_error)),
+            _ => Self::UnsoundKey(format!("🔑🙀: Unsound key, {}.", key_error)),
+        }
+    }
+}
+
+impl From<triple_des::KeyError> for SymciphError {
+    fn from(key_error: triple_des::KeyError) -> Self {
+        match key_error {
+            triple_des::KeyError::Length(_) => Self::KeyLength(format!("🔑🙀: {}.", key_error)),
+            _ => Self::UnsoundKey(format!("🔑🙀: Unsound key, {}.", key_error)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exit_codes_distinct() {
+        let errors = [
+            SymciphError::Io(io::Error::other("")),
+            SymciphError::InvalidInput(String::new()),
+            SymciphError::KeyLength(String::new()),
+            SymciphError::UnsoundKey(String::new()),
+            SymciphError::BlockAlignment(String::new()),
+            SymciphError::Padding(String::new()),
+            SymciphError::Authentication(String::new()),
+            SymciphError::Format(String::new()),
+        ];
+
+        let mut left: Vec<_> = errors.iter().map(SymciphError::exit_code).collect();
+        left.sort();
+        left.dedup();
+        let right = errors.len();
+
+        assert_eq!(left.len(), right);
+        assert!(!left.contains(&0));
+    }
+
+    #[test]
+    fn errors_survive_io_round_trip() {
+        let error = SymciphError::Authentication("🔏🙀: Tampered.".to_string());
+
+        let left = SymciphError::from(io::Error::from(error));
+
+        assert!(
+            matches!(left, SymciphError::Authentication(ref message) if message == "🔏🙀: Tampered.")
+        );
+
+        let left = SymciphError::from(io::Error::other("disk on fire"));
+
+        assert!(matches!(left, SymciphError::Io(_)));
+    }
+
+    #[test]
+    fn key_errors_classified() {
+        let left = SymciphError::from(des::KeyError::Weak).exit_code();
+        let right = SymciphError::UnsoundKey(String::new()).exit_code();
+
+        assert_eq!(left, right);
+
+        let left = SymciphError::from(triple_des::KeyError::Length(20)).to_string();
+        let right = "🔑🙀: 3DES key is 20 bytes long instead of 8, 16 or 24.";
+
+        assert_eq!(left, right);
+    }
+}
@@ -0,0 +1,447 @@
+use std::io::{Error, ErrorKind, Read};
+
+use clap::ValueEnum;
+
+use super::{
+    error::{Result, SymciphError},
+    pipeline::{check_counter_bits, check_sector_size, check_segment_size, RangeCheck},
+    EncryptionModes, EncryptionSchemes, PaddingSchemes, BLOCK_CHUNK_SIZE,
+};
+
+const MAGIC: [u8; 4] = *b"SYM\x1A";
+const FORMAT_VERSION: u8 = 2;
+// Version 1 DES and 3DES containers were written with the legacy DES core and EEE
+const LEGACY_DES_VERSION: u8 = 1;
+
+pub const KDF_NONE: u8 = 0;
+pub const KDF_PBKDF2_SHA256: u8 = 1;
+pub const KDF_SCRYPT: u8 = 2;
+pub const KDF_ARGON2ID: u8 = 3;
+
+// Everything needed to decrypt besides the key, all integers big-endian:
+// magic(4) version(1) cipher(1) mode(1) padding(1) counter bits(1) tag length(1)
+// data unit size(4) start sector(8) KDF id(1) KDF parameters length(2) KDF parameters
+// IV or nonce length(1) IV or nonce
+// The data unit is the XTS sector or the STREAM segment, the tag, if any, trails the ciphertext
+pub struct Header {
+    pub cipher: EncryptionSchemes,
+    pub mode: EncryptionModes,
+    pub padding: PaddingSchemes,
+    pub counter_bits: usize,
+    pub tag_len: usize,
+    pub unit_size: usize,
+    pub start_sector: u64,
+    pub kdf_id: u8,
+    pub kdf_params: Vec<u8>,
+    pub iv: Vec<u8>,
+}
+
+impl Header {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = MAGIC.to_vec();
+
+        bytes.extend_from_slice(&[
+            FORMAT_VERSION,
+            self.cipher.id(),
+            self.mode.id(),
+            self.padding.id(),
+            self.counter_bits as u8,
+            self.tag_len as u8,
+        ]);
+        bytes.extend_from_slice(&(self.unit_size as u32).to_be_bytes());
+        bytes.extend_from_slice(&self.start_sector.to_be_bytes());
+        bytes.push(self.kdf_id);
+        bytes.extend_from_slice(&(self.kdf_params.len() as u16).to_be_bytes());
+        bytes.extend_from_slice(&self.kdf_params);
+        bytes.push(self.iv.len() as u8);
+        bytes.extend_from_slice(&self.iv);
+
+        bytes
+    }
+
+    pub fn read(input: &mut impl Read) -> Result<Self> {
+        let magic: [u8; 4] = read_array(input)?;
+
+        if magic != MAGIC {
+            return Err(SymciphError::Format(
+                "📦🙀: Input is not a symciph container, decrypt headerless files with --raw."
+                    .to_string(),
+            ));
+        }
+
+        let [version, cipher, mode, padding, counter_bits, tag_len] = read_array(input)?;
+
+        if !(LEGACY_DES_VERSION..=FORMAT_VERSION).contains(&version) {
+            return Err(SymciphError::Format(format!(
+                "📦🙀: Unsupported container format version {}.",
+                version
+            )));
+        }
+
+        let unit_size = u32::from_be_bytes(read_array(input)?);
+        let start_sector = u64::from_be_bytes(read_array(input)?);
+
+        let [kdf_id] = read_array(input)?;
+        let kdf_params_len = u16::from_be_bytes(read_array(input)?);
+        let kdf_params = read_vec(input, kdf_params_len as usize)?;
+
+        let [iv_len] = read_array(input)?;
+        let iv = read_vec(input, iv_len as usize)?;
+
+        let cipher = match (version, from_id(cipher)?) {
+            (LEGACY_DES_VERSION, EncryptionSchemes::Des) => EncryptionSchemes::DesLegacy,
+            (LEGACY_DES_VERSION, EncryptionSchemes::Tdes) => EncryptionSchemes::TdesLegacyEee,
+            (_, cipher) => cipher,
+        };
+
+        let header = Self {
+            cipher,
+            mode: from_id(mode)?,
+            padding: from_id(padding)?,
+            counter_bits: counter_bits as usize,
+            tag_len: tag_len as usize,
+            unit_size: unit_size as usize,
+            start_sector,
+            kdf_id,
+            kdf_params,
+            iv,
+        };
+        header.check_fields()?;
+
+        Ok(header)
+    }
+
+    // Holds the fields a mode relies on to the limits the options are checked against on encryption,
+    // fields the mode does not use are left alone
+    fn check_fields(&self) -> Result<()> {
+        let check = |name: &str, check: RangeCheck| {
+            check.map_err(|reason| {
+                SymciphError::Format(format!("📦🙀: Container {} {}.", name, reason))
+            })
+        };
+
+        match self.mode {
+            EncryptionModes::Ctr => {
+                check("counter bits", check_counter_bits(self.counter_bits))?;
+
+                if !self
+                    .mode
+                    .supports_counter_bits(&self.cipher, self.counter_bits)
+                {
+                    return Err(SymciphError::Format(
+                        "📦🙀: Container counter bits exceed the block size of its cipher."
+                            .to_string(),
+                    ));
+                }
+            }
+            EncryptionModes::Xts => check("sector size", check_sector_size(self.unit_size))?,
+            EncryptionModes::GcmStream => {
+                check("segment size", check_segment_size(self.unit_size))?
+            }
+            _ => {}
+        }
+
+        let iv_len_matches = match self.mode.is_authenticated() || self.mode.is_segmented() {
+            true => self
+                .mode
+                .supports_nonce_and_tag(&self.cipher, self.iv.len(), self.tag_len),
+            false => {
+                self.iv.len() == self.mode.iv_size(self.cipher.block_size()) * BLOCK_CHUNK_SIZE
+            }
+        };
+
+        if !iv_len_matches {
+            return Err(SymciphError::Format(
+                "📦🙀: Container IV, nonce or tag length does not fit its mode.".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+// Identifiers are part of the file format, so they are spelled out instead of derived from order
+trait ContainerId: ValueEnum {
+    fn id(&self) -> u8;
+}
+
+impl ContainerId for EncryptionSchemes {
+    fn id(&self) -> u8 {
+        match self {
+            Self::Des => 0x01,
+            Self::Tdes => 0x02,
+            Self::Aes128 => 0x03,
+            Self::Aes192 => 0x04,
+            Self::Aes256 => 0x05,
+            Self::DesLegacy => 0x06,
+            Self::TdesLegacyEee => 0x07,
+        }
+    }
+}
+
+impl ContainerId for EncryptionModes {
+    fn id(&self) -> u8 {
+        match self {
+            Self::Ecb => 0x01,
+            Self::Cbc => 0x02,
+            Self::CbcCs1 => 0x03,
+            Self::CbcCs2 => 0x04,
+            Self::CbcCs3 => 0x05,
+            Self::Pcbc => 0x06,
+            Self::Cfb => 0x07,
+            Self::Cfb8 => 0x08,
+            Self::Ofb => 0x09,
+            Self::Ctr => 0x0A,
+            Self::Gcm => 0x0B,
+            Self::Ccm => 0x0C,
+            Self::Ocb => 0x0D,
+            Self::Eax => 0x0E,
+            Self::Siv => 0x0F,
+            Self::GcmSiv => 0x10,
+            Self::Xts => 0x11,
+            Self::GcmStream => 0x12,
+        }
+    }
+}
+
+impl ContainerId for PaddingSchemes {
+    fn id(&self) -> u8 {
+        match self {
+            Self::Pkcs7 => 0x01,
+            Self::AnsiX923 => 0x02,
+            Self::Iso7816 => 0x03,
+            Self::Iso10126 => 0x04,
+            Self::None => 0x05,
+        }
+    }
+}
+
+fn from_id<T: ContainerId>(id: u8) -> Result<T> {
+    T::value_variants()
+        .iter()
+        .find(|variant| variant.id() == id)
+        .cloned()
+        .ok_or_else(|| {
+            SymciphError::Format(
+                "📦🙀: Container header names an unknown cipher, mode or padding.".to_string(),
+            )
+        })
+}
+
+fn read_array<const N: usize>(input: &mut impl Read) -> Result<[u8; N]> {
+    let mut bytes = [0u8; N];
+    input.read_exact(&mut bytes).map_err(truncated)?;
+
+    Ok(bytes)
+}
+
+fn read_vec(input: &mut impl Read, len: usize) -> Result<Vec<u8>> {
+    let mut bytes = vec![0u8; len];
+    input.read_exact(&mut bytes).map_err(truncated)?;
+
+    Ok(bytes)
+}
+
+fn truncated(err: Error) -> SymciphError {
+    match err.kind() {
+        ErrorKind::UnexpectedEof => {
+            SymciphError::Format("📦🙀: Input too short to contain a container header.".to_string())
+        }
+        _ => err.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn helper_get_header() -> Header {
+        Header {
+            cipher: EncryptionSchemes::Aes256,
+            mode: EncryptionModes::Gcm,
+            padding: PaddingSchemes::Iso7816,
+            counter_bits: 32,
+            tag_len: 16,
+            unit_size: 4096,
+            start_sector: 0x0102030405060708,
+            kdf_id: KDF_NONE,
+            kdf_params: vec![0xAA, 0xBB],
+            iv: vec![0x5A; 12],
+        }
+    }
+
+    #[test]
+    fn header_layout() {
+        let left = helper_get_header().to_bytes();
+        let right = [
+            [0x53, 0x59, 0x4D, 0x1A, 0x02, 0x05, 0x0B, 0x03, 0x20, 0x10].as_slice(),
+            &[0x00, 0x00, 0x10, 0x00],
+            &[0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08],
+            &[0x00, 0x00, 0x02, 0xAA, 0xBB],
+            &[0x0C],
+            &[0x5A; 12],
+        ]
+        .concat();
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn read_written_header() {
+        let bytes = helper_get_header().to_bytes();
+
+        let left = Header::read(&mut bytes.as_slice()).unwrap().to_bytes();
+        let right = bytes;
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn read_leaves_payload() {
+        let bytes = [helper_get_header().to_bytes(), vec![0xC0, 0xFF, 0xEE]].concat();
+        let mut input = bytes.as_slice();
+
+        Header::read(&mut input).unwrap();
+
+        assert_eq!(input, [0xC0, 0xFF, 0xEE]);
+    }
+
+    #[test]
+    fn read_wrong_magic() {
+        let mut bytes = helper_get_header().to_bytes();
+        bytes[0] ^= 0x01;
+
+        let left = Header::read(&mut bytes.as_slice());
+
+        assert!(left.is_err_and(|err| err.to_string().contains("--raw")));
+    }
+
+    #[test]
+    fn read_newer_version() {
+        let mut bytes = helper_get_header().to_bytes();
+        bytes[4] = FORMAT_VERSION + 1;
+
+        let left = Header::read(&mut bytes.as_slice());
+
+        assert!(left.is_err_and(|err| err.to_string().contains("version")));
+    }
+
+    #[test]
+    fn read_version_1_des_as_legacy() {
+        let mut header = helper_get_header();
+        header.cipher = EncryptionSchemes::Tdes;
+        let mut bytes = header.to_bytes();
+        bytes[4] = LEGACY_DES_VERSION;
+
+        let left = Header::read(&mut bytes.as_slice()).unwrap().cipher;
+        let right = EncryptionSchemes::TdesLegacyEee;
+
+        assert!(left == right);
+
+        bytes[5] = EncryptionSchemes::Aes256.id();
+
+        let left = Header::read(&mut bytes.as_slice()).unwrap().cipher;
+        let right = EncryptionSchemes::Aes256;
+
+        assert!(left == right);
+    }
+
+    #[test]
+    fn read_version_0() {
+        let mut bytes = helper_get_header().to_bytes();
+        bytes[4] = 0;
+
+        let left = Header::read(&mut bytes.as_slice());
+
+        assert!(left.is_err_and(|err| err.to_string().contains("version")));
+    }
+
+    #[test]
+    fn read_unknown_mode() {
+        let mut bytes = helper_get_header().to_bytes();
+        bytes[6] = 0xFF;
+
+        let left = Header::read(&mut bytes.as_slice());
+
+        assert!(left.is_err());
+    }
+
+    #[test]
+    fn read_counter_bits_out_of_range() {
+        let mut header = helper_get_header();
+        header.mode = EncryptionModes::Ctr;
+        header.iv = vec![0x5A; 16];
+
+        for counter_bits in [0, 12, 136, 200] {
+            header.counter_bits = counter_bits;
+
+            let left = Header::read(&mut header.to_bytes().as_slice());
+
+            assert!(matches!(left, Err(SymciphError::Format(_))));
+        }
+
+        header.cipher = EncryptionSchemes::Des;
+        header.counter_bits = 72;
+        header.iv = vec![0x5A; 8];
+
+        let left = Header::read(&mut header.to_bytes().as_slice());
+
+        assert!(matches!(left, Err(SymciphError::Format(_))));
+    }
+
+    #[test]
+    fn read_unit_size_out_of_range() {
+        let mut header = helper_get_header();
+
+        for (mode, iv_len) in [(EncryptionModes::Xts, 0), (EncryptionModes::GcmStream, 12)] {
+            header.mode = mode;
+            header.iv = vec![0x5A; iv_len];
+
+            for unit_size in [0, 0x100_0001] {
+                header.unit_size = unit_size;
+
+                let left = Header::read(&mut header.to_bytes().as_slice());
+
+                assert!(matches!(left, Err(SymciphError::Format(_))));
+            }
+        }
+    }
+
+    #[test]
+    fn read_iv_of_wrong_length() {
+        let mut header = helper_get_header();
+
+        for (mode, iv_len) in [
+            (EncryptionModes::Cbc, 8),
+            (EncryptionModes::Ctr, 15),
+            (EncryptionModes::Ecb, 16),
+            (EncryptionModes::Gcm, 16),
+            (EncryptionModes::GcmStream, 0),
+        ] {
+            header.mode = mode;
+            header.iv = vec![0x5A; iv_len];
+
+            let left = Header::read(&mut header.to_bytes().as_slice());
+
+            assert!(matches!(left, Err(SymciphError::Format(_))));
+        }
+    }
+
+    #[test]
+    fn read_truncated() {
+        let bytes = helper_get_header().to_bytes();
+
+        let left = Header::read(&mut &bytes[..bytes.len() - 1]);
+
+        assert!(matches!(left, Err(SymciphError::Format(_))));
+    }
+
+    #[test]
+    fn ids_are_unique() {
+        for (index, mode) in EncryptionModes::value_variants().iter().enumerate() {
+            for other_mode in &EncryptionModes::value_variants()[index + 1..] {
+                assert_ne!(mode.id(), other_mode.id());
+            }
+        }
+    }
+}
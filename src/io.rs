@@ -1,96 +1,82 @@
 use std::{
     fs::File,
-    io::{Error, ErrorKind, Read, Result, Write},
+    io::{self, Error, ErrorKind, Read, Write},
 };
 
-use crate::{DataChunk, BLOCK_CHUNK_SIZE};
+use crate::{
+    armor::{ArmoringWriter, DearmoringReader, KEY_LABEL},
+    error::{Result, SymciphError},
+    mode::to_chunks,
+    pipeline::Transform,
+    ArmorSchemes, DataChunk, Decryptor, EncryptionModes, EncryptionSchemes, Encryptor,
+    BLOCK_CHUNK_SIZE,
+};
+
+const STREAM_READ_LEN: usize = 0x2000;
 
-pub fn read_chunks(file: &mut File, buffer: &mut [DataChunk]) -> Result<(usize, usize)> {
-    let mut num_chunks = 0;
+// Fills the buffer unless the end of the file is reached first
+pub fn read_full(file: &mut impl Read, buffer: &mut [u8]) -> Result<usize> {
     let mut bytes_read = 0;
-    let buffer_len = buffer.len();
-
-    assert!(buffer_len > 0, "I/O Read: Buffer length cannot be 0");
-
-    for (chunk_idx, chunk) in buffer.iter_mut().enumerate() {
-        chunk.fill(0);
-        bytes_read = file.read(chunk)?;
-
-        if bytes_read < BLOCK_CHUNK_SIZE {
-            num_chunks = match bytes_read {
-                // No more data to read
-                0 if chunk_idx == 0 => 0,
-                // Previous read was full, this one is empty, but chunk_idx incremented
-                0 if chunk_idx > 0 => {
-                    bytes_read = BLOCK_CHUNK_SIZE;
-                    chunk_idx
-                }
-                _ => chunk_idx + 1,
-            };
 
-            break;
+    while bytes_read < buffer.len() {
+        match file.read(&mut buffer[bytes_read..])? {
+            0 => break,
+            read => bytes_read += read,
         }
-
-        num_chunks = buffer_len;
     }
 
-    Ok((num_chunks, bytes_read))
+    Ok(bytes_read)
 }
 
-pub fn write_chunks(file: &mut File, buffer: &[DataChunk]) -> Result<()> {
-    assert!(!buffer.is_empty(), "I/O Write: Buffer length cannot be 0");
+pub fn read_iv(file: &mut impl Read, iv_len: usize) -> Result<Vec<DataChunk>> {
+    let mut iv = vec![0u8; iv_len * BLOCK_CHUNK_SIZE];
 
-    for chunk in buffer.iter() {
-        let mut end_position = chunk.len();
+    if read_full(file, &mut iv)? < iv.len() {
+        return Err(SymciphError::Format(
+            "🎲🙀: Input file too short to contain the IV.".to_string(),
+        ));
+    }
 
-        for byte in chunk.iter().rev() {
-            match *byte {
-                0 => end_position -= 1,
-                _ => break,
-            }
-        }
+    Ok(to_chunks(&iv))
+}
 
-        let bytes_written = file.write(&chunk[..end_position])?;
+pub fn read_key(key_path: &String) -> Result<Vec<DataChunk>> {
+    let mut key_file = File::open(key_path)?;
+    let mut key_bytes = Vec::new();
+    key_file.read_to_end(&mut key_bytes)?;
 
-        if bytes_written < end_position {
-            return Err(Error::new(
-                ErrorKind::UnexpectedEof,
-                "📝🙀: Failed to write full chunk.",
-            ));
-        }
+    let key_bytes = dearmor_key(key_bytes)?;
+
+    if !key_bytes.len().is_multiple_of(BLOCK_CHUNK_SIZE) {
+        return Err(SymciphError::KeyLength(format!(
+            "🔑🙀: Key file is not a multiple of {} bytes.",
+            BLOCK_CHUNK_SIZE
+        )));
     }
 
-    Ok(())
+    Ok(to_chunks(&key_bytes))
 }
 
-// TODO: Express in terms of read_chunks()
-pub fn read_key(key_path: &String) -> Result<Vec<DataChunk>> {
-    let mut key_file = File::open(key_path)?;
-    let mut key: Vec<DataChunk> = Vec::new();
-    let mut chunk = [0u8; BLOCK_CHUNK_SIZE];
+// PEM-style key files are decoded or refused, while hex and Base64 that fail to decode into
+// whole key chunks are taken for a raw key that happens to look like text
+fn dearmor_key(key_bytes: Vec<u8>) -> Result<Vec<u8>> {
+    let mut reader = DearmoringReader::new(&key_bytes[..])?;
+    let mut key = Vec::new();
 
-    loop {
-        let bytes_read = key_file.read(&mut chunk)?;
+    match reader.armor() {
+        Some(ArmorSchemes::Pem) => {
+            reader.read_to_end(&mut key)?;
 
-        match bytes_read {
-            0 => break,
-            BLOCK_CHUNK_SIZE => key.push(chunk),
-            _ => {
-                return Err(Error::new(
-                    ErrorKind::InvalidData,
-                    format!(
-                        "🔑🙀: Key file is not a multiple of {} bytes.",
-                        BLOCK_CHUNK_SIZE
-                    ),
-                ))
-            }
+            Ok(key)
         }
+        Some(_) => match reader.read_to_end(&mut key) {
+            Ok(_) if !key.is_empty() && key.len().is_multiple_of(BLOCK_CHUNK_SIZE) => Ok(key),
+            _ => Ok(key_bytes),
+        },
+        None => Ok(key_bytes),
     }
-
-    Ok(key)
 }
 
-// TODO: Express in terms of write_chunks()
 pub fn write_key(key_path: &String, key: &[DataChunk]) -> Result<()> {
     let mut output_file = File::create(key_path)?;
     let mut buffer = [0; BLOCK_CHUNK_SIZE];
@@ -104,9 +90,148 @@ pub fn write_key(key_path: &String, key: &[DataChunk]) -> Result<()> {
             return Err(Error::new(
                 ErrorKind::InvalidData,
                 "📝🔑🙀: Failed to fully write generated key to file.",
-            ));
+            )
+            .into());
         }
     }
 
     Ok(())
 }
+
+pub fn write_armored_key(
+    key_path: &String,
+    key: &[DataChunk],
+    armor: ArmorSchemes,
+    fields: &[(&str, &str)],
+) -> Result<()> {
+    let mut writer = ArmoringWriter::with_label(File::create(key_path)?, armor, KEY_LABEL, fields)?;
+    writer.write_all(key.as_flattened())?;
+    writer.finish()?;
+
+    Ok(())
+}
+
+/// Encrypts everything written to it into the wrapped writer.
+///
+/// Partial blocks, sectors and segments are held back until more data follows them, so
+/// `finish` must be called once all data is written to process the final block and get the
+/// wrapped writer back. Errors other than I/O errors come wrapped in `io::Error` from
+/// `write`, converting back to `SymciphError` recovers them.
+pub struct EncryptingWriter<W: Write> {
+    inner: W,
+    transform: Transform,
+}
+
+impl<W: Write> EncryptingWriter<W> {
+    /// Writes a container with the given cipher, mode and key, see `Encryptor::writer` for
+    /// the other options.
+    pub fn new(
+        inner: W,
+        cipher: EncryptionSchemes,
+        mode: EncryptionModes,
+        key: &[u8],
+    ) -> Result<Self> {
+        Encryptor::new(cipher).mode(mode).key(key).writer(inner)
+    }
+
+    pub(crate) fn from_transform(inner: W, transform: Transform) -> Self {
+        Self { inner, transform }
+    }
+
+    /// Pads or seals what is left and returns the wrapped writer.
+    pub fn finish(mut self) -> Result<W> {
+        self.inner.write_all(&self.transform.finish()?)?;
+        self.inner.flush()?;
+
+        Ok(self.inner)
+    }
+}
+
+impl<W: Write> Write for EncryptingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let output = self.transform.push(buf)?;
+        self.inner.write_all(&output)?;
+
+        Ok(buf.len())
+    }
+
+    // Held back data stays buffered, only finish can tell it is the end
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Decrypts everything read from the wrapped reader.
+///
+/// The header is read when the reader is built. The last block is unpadded once the wrapped
+/// reader ends. In GCM, CCM, OCB, EAX, SIV and GCM-SIV modes nothing is returned before
+/// the whole message is read and its tag verifies, GCM-STREAM returns each segment once it
+/// verifies. Authentication and padding errors come wrapped in `io::Error`, converting back
+/// to `SymciphError` recovers them.
+pub struct DecryptingReader<R: Read> {
+    inner: R,
+    transform: Transform,
+    output: Vec<u8>,
+    position: usize,
+    finished: bool,
+}
+
+impl<R: Read> DecryptingReader<R> {
+    /// Reads a container with the given key, see `Decryptor::reader` for the other options.
+    pub fn new(inner: R, key: &[u8]) -> Result<Self> {
+        Decryptor::new().key(key).reader(inner)
+    }
+
+    pub(crate) fn from_transform(inner: R, transform: Transform) -> Self {
+        Self {
+            inner,
+            transform,
+            output: Vec::new(),
+            position: 0,
+            finished: false,
+        }
+    }
+
+    /// Returns the wrapped reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    // Runs input through the transform until it gives some output or the input ends
+    fn fill(&mut self) -> Result<()> {
+        let mut buffer = vec![0u8; STREAM_READ_LEN];
+
+        while self.output.is_empty() && !self.finished {
+            let bytes_read = self.inner.read(&mut buffer)?;
+
+            self.output = match bytes_read {
+                // Only marked finished once the last unit checks out, a retry must not look like EOF
+                0 => {
+                    let output = self.transform.finish()?;
+                    self.finished = true;
+
+                    output
+                }
+                _ => self.transform.push(&buffer[..bytes_read])?,
+            };
+        }
+
+        Ok(())
+    }
+}
+
+impl<R: Read> Read for DecryptingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.position == self.output.len() {
+            self.output.clear();
+            self.position = 0;
+            self.fill()?;
+        }
+
+        let bytes_read = buf.len().min(self.output.len() - self.position);
+        buf[..bytes_read].copy_from_slice(&self.output[self.position..self.position + bytes_read]);
+        self.position += bytes_read;
+
+        Ok(bytes_read)
+    }
+}
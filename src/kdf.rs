@@ -0,0 +1,400 @@
+mod argon2;
+mod pbkdf2;
+mod scrypt;
+
+use crate::error::{Result, SymciphError};
+
+use rand::Rng;
+
+use super::{
+    header::{KDF_ARGON2ID, KDF_NONE, KDF_PBKDF2_SHA256, KDF_SCRYPT},
+    pipeline::{
+        check_iterations, check_memory_cost, check_parallelism, check_time_cost, RangeCheck,
+    },
+    KdfSchemes,
+};
+
+use argon2::argon2id;
+use pbkdf2::pbkdf2_sha256;
+use scrypt::scrypt;
+
+const SALT_LEN: usize = 16;
+
+// scrypt lanes run one after another, so all of them together may take as long as 16 lanes of the
+// largest memory cost, 64 GiB
+const MAX_MEMORY_BYTES: u128 = 0x400_0000 * 1024;
+
+// Derives keys from passwords, the parameters are stored in the container header
+pub enum Kdf {
+    Pbkdf2Sha256 {
+        iterations: u32,
+        salt: Vec<u8>,
+    },
+    Scrypt {
+        log_n: u8,
+        block_size: u32,
+        parallelism: u32,
+        salt: Vec<u8>,
+    },
+    Argon2id {
+        memory_kib: u32,
+        passes: u32,
+        lanes: u32,
+        salt: Vec<u8>,
+    },
+}
+
+// Memory-hard cost settings shared by scrypt and Argon2id
+pub struct KdfCost {
+    pub iterations: u32,
+    pub memory_kib: u32,
+    pub time_cost: u32,
+    pub parallelism: u32,
+}
+
+pub const SCRYPT_BLOCK_SIZE: u32 = 8;
+
+impl KdfSchemes {
+    pub fn kdf(&self, cost: &KdfCost) -> Kdf {
+        let salt = generate_salt();
+
+        match self {
+            Self::Pbkdf2 => Kdf::Pbkdf2Sha256 {
+                iterations: cost.iterations,
+                salt,
+            },
+            // N is the largest power of two whose 128 * r * N bytes fit in the memory cost
+            Self::Scrypt => Kdf::Scrypt {
+                log_n: (cost.memory_kib as u64 * 1024 / (128 * SCRYPT_BLOCK_SIZE as u64)).ilog2()
+                    as u8,
+                block_size: SCRYPT_BLOCK_SIZE,
+                parallelism: cost.parallelism,
+                salt,
+            },
+            Self::Argon2id => Kdf::Argon2id {
+                memory_kib: cost.memory_kib,
+                passes: cost.time_cost,
+                lanes: cost.parallelism,
+                salt,
+            },
+        }
+    }
+}
+
+impl Kdf {
+    pub fn from_header(kdf_id: u8, kdf_params: &[u8]) -> Result<Option<Self>> {
+        let unsupported = || {
+            SymciphError::Format(
+                "📦🙀: Container key derivation function is not supported.".to_string(),
+            )
+        };
+
+        if kdf_id == KDF_NONE {
+            return Ok(None);
+        }
+
+        let (cost, salt) = match kdf_id {
+            KDF_PBKDF2_SHA256 => kdf_params.split_at_checked(4),
+            KDF_SCRYPT => kdf_params.split_at_checked(9),
+            KDF_ARGON2ID => kdf_params.split_at_checked(12),
+            _ => None,
+        }
+        .filter(|(_, salt)| salt.len() == SALT_LEN)
+        .ok_or_else(unsupported)?;
+
+        let word =
+            |position: usize| u32::from_be_bytes(cost[position..position + 4].try_into().unwrap());
+        let salt = salt.to_vec();
+
+        let kdf = match kdf_id {
+            KDF_PBKDF2_SHA256 => Self::Pbkdf2Sha256 {
+                iterations: word(0),
+                salt,
+            },
+            KDF_SCRYPT => Self::Scrypt {
+                log_n: cost[0],
+                block_size: word(1),
+                parallelism: word(5),
+                salt,
+            },
+            _ => Self::Argon2id {
+                memory_kib: word(0),
+                passes: word(4),
+                lanes: word(8),
+                salt,
+            },
+        };
+
+        // Parameters come from the file, so they are checked before they can trip an assertion
+        if !kdf.is_valid() {
+            return Err(unsupported());
+        }
+
+        // or make the derivation allocate whatever a crafted file asks for
+        kdf.check_limits()?;
+
+        Ok(Some(kdf))
+    }
+
+    // Holds file parameters to the limits the CLI and the builders put on the cost options
+    fn check_limits(&self) -> Result<()> {
+        let check = |name: &str, check: RangeCheck| {
+            check.map_err(|reason| {
+                SymciphError::Format(format!("📦🙀: Container {} {}.", name, reason))
+            })
+        };
+
+        match self {
+            Self::Pbkdf2Sha256 { iterations, .. } => {
+                check("iterations", check_iterations(*iterations))
+            }
+            // Each lane takes 128 * r * N bytes, is_valid has kept that within 64 GiB
+            Self::Scrypt {
+                log_n,
+                block_size,
+                parallelism,
+                ..
+            } => {
+                let memory_kib = 128 * u64::from(*block_size) * (1 << log_n) / 1024;
+
+                check(
+                    "memory cost",
+                    check_memory_cost(u32::try_from(memory_kib).unwrap_or(u32::MAX)),
+                )?;
+                check("parallelism", check_parallelism(*parallelism))
+            }
+            Self::Argon2id {
+                memory_kib,
+                passes,
+                lanes,
+                ..
+            } => {
+                check("memory cost", check_memory_cost(*memory_kib))?;
+                check("time cost", check_time_cost(*passes))?;
+                check("parallelism", check_parallelism(*lanes))
+            }
+        }
+    }
+
+    pub fn id(&self) -> u8 {
+        match self {
+            Self::Pbkdf2Sha256 { .. } => KDF_PBKDF2_SHA256,
+            Self::Scrypt { .. } => KDF_SCRYPT,
+            Self::Argon2id { .. } => KDF_ARGON2ID,
+        }
+    }
+
+    // PBKDF2: iterations(4) salt
+    // scrypt: log2 N(1) r(4) p(4) salt
+    // Argon2id: memory KiB(4) passes(4) lanes(4) salt
+    pub fn params(&self) -> Vec<u8> {
+        match self {
+            Self::Pbkdf2Sha256 { iterations, salt } => {
+                [iterations.to_be_bytes().as_slice(), salt].concat()
+            }
+            Self::Scrypt {
+                log_n,
+                block_size,
+                parallelism,
+                salt,
+            } => [
+                [*log_n].as_slice(),
+                &block_size.to_be_bytes(),
+                &parallelism.to_be_bytes(),
+                salt,
+            ]
+            .concat(),
+            Self::Argon2id {
+                memory_kib,
+                passes,
+                lanes,
+                salt,
+            } => [
+                memory_kib.to_be_bytes().as_slice(),
+                &passes.to_be_bytes(),
+                &lanes.to_be_bytes(),
+                salt,
+            ]
+            .concat(),
+        }
+    }
+
+    pub fn derive_key(&self, password: &[u8], key_len: usize) -> Vec<u8> {
+        match self {
+            Self::Pbkdf2Sha256 { iterations, salt } => {
+                pbkdf2_sha256(password, salt, *iterations, key_len)
+            }
+            Self::Scrypt {
+                log_n,
+                block_size,
+                parallelism,
+                salt,
+            } => scrypt(password, salt, *log_n, *block_size, *parallelism, key_len),
+            Self::Argon2id {
+                memory_kib,
+                passes,
+                lanes,
+                salt,
+            } => argon2id(
+                password,
+                salt,
+                &[],
+                &[],
+                *memory_kib,
+                *passes,
+                *lanes,
+                key_len,
+            ),
+        }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        match self {
+            Self::Pbkdf2Sha256 { iterations, .. } => *iterations > 0,
+            Self::Scrypt {
+                log_n,
+                block_size,
+                parallelism,
+                ..
+            } => {
+                *block_size > 0
+                    && *parallelism > 0
+                    && *log_n > 0
+                    && (*log_n as u64) < 16 * *block_size as u64
+                    && *log_n < u64::BITS as u8
+                    && 128 * *block_size as u128 * (1 << *log_n) * *parallelism as u128
+                        <= MAX_MEMORY_BYTES
+            }
+            Self::Argon2id {
+                memory_kib,
+                passes,
+                lanes,
+                ..
+            } => {
+                *passes > 0
+                    && (1..0x100_0000).contains(lanes)
+                    && *memory_kib as u64 >= 8 * *lanes as u64
+            }
+        }
+    }
+}
+
+fn generate_salt() -> Vec<u8> {
+    let mut rng = rand::thread_rng();
+
+    (0..SALT_LEN).map(|_| rng.gen()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::ValueEnum;
+
+    use crate::pipeline::{MAX_ITERATIONS, MAX_TIME_COST};
+
+    use super::*;
+
+    fn helper_get_cost() -> KdfCost {
+        KdfCost {
+            iterations: 1000,
+            memory_kib: 64,
+            time_cost: 2,
+            parallelism: 2,
+        }
+    }
+
+    #[test]
+    fn params_round_trip() {
+        for kdf_scheme in KdfSchemes::value_variants() {
+            let kdf = kdf_scheme.kdf(&helper_get_cost());
+
+            let left = Kdf::from_header(kdf.id(), &kdf.params())
+                .unwrap()
+                .unwrap()
+                .derive_key(b"password", 16);
+            let right = kdf.derive_key(b"password", 16);
+
+            assert_eq!(left, right);
+        }
+    }
+
+    #[test]
+    fn random_salt() {
+        let left = KdfSchemes::Argon2id.kdf(&helper_get_cost()).params();
+        let right = KdfSchemes::Argon2id.kdf(&helper_get_cost()).params();
+
+        assert_ne!(left, right);
+    }
+
+    #[test]
+    fn scrypt_cost_fits_memory() {
+        let kdf = KdfSchemes::Scrypt.kdf(&KdfCost {
+            memory_kib: 3000,
+            ..helper_get_cost()
+        });
+
+        // 128 * 8 * 2^11 bytes is 2 MiB, 2^12 would take 4 MiB
+        assert!(matches!(kdf, Kdf::Scrypt { log_n: 11, .. }));
+    }
+
+    #[test]
+    fn invalid_params() {
+        let mut params = KdfSchemes::Argon2id.kdf(&helper_get_cost()).params();
+        params[..4].copy_from_slice(&[0x00; 4]);
+
+        assert!(Kdf::from_header(KDF_ARGON2ID, &params).is_err());
+
+        let mut params = KdfSchemes::Scrypt.kdf(&helper_get_cost()).params();
+        params[0] = 0x00;
+
+        assert!(Kdf::from_header(KDF_SCRYPT, &params).is_err());
+    }
+
+    #[test]
+    fn params_over_limits() {
+        for iterations in [999, MAX_ITERATIONS + 1] {
+            let mut params = KdfSchemes::Pbkdf2.kdf(&helper_get_cost()).params();
+            params[..4].copy_from_slice(&iterations.to_be_bytes());
+
+            assert!(matches!(
+                Kdf::from_header(KDF_PBKDF2_SHA256, &params),
+                Err(SymciphError::Format(_))
+            ));
+        }
+
+        // 4 GiB and one KiB of memory, then 33 passes
+        for (position, cost) in [(0, 0x40_0001), (4, MAX_TIME_COST + 1)] {
+            let mut params = KdfSchemes::Argon2id.kdf(&helper_get_cost()).params();
+            params[position..position + 4].copy_from_slice(&u32::to_be_bytes(cost));
+
+            assert!(matches!(
+                Kdf::from_header(KDF_ARGON2ID, &params),
+                Err(SymciphError::Format(_))
+            ));
+        }
+
+        // 128 * 8 * 2^23 bytes is 8 GiB per lane, within the total but not the memory cost
+        let mut params = KdfSchemes::Scrypt.kdf(&helper_get_cost()).params();
+        params[0] = 23;
+
+        assert!(matches!(
+            Kdf::from_header(KDF_SCRYPT, &params),
+            Err(SymciphError::Format(_))
+        ));
+
+        // 128 * 8 * 2^26 * 2 bytes is 128 GiB
+        let mut params = KdfSchemes::Scrypt.kdf(&helper_get_cost()).params();
+        params[0] = 26;
+
+        assert!(matches!(
+            Kdf::from_header(KDF_SCRYPT, &params),
+            Err(SymciphError::Format(_))
+        ));
+    }
+
+    #[test]
+    fn unknown_kdf() {
+        assert!(Kdf::from_header(0xFF, &[]).is_err());
+        assert!(Kdf::from_header(KDF_PBKDF2_SHA256, &[0x00; 4]).is_err());
+        assert!(Kdf::from_header(KDF_NONE, &[]).unwrap().is_none());
+    }
+}
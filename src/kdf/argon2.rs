@@ -0,0 +1,356 @@
+use blake2b::{Blake2b, BLAKE2B_MAX_DIGEST_LEN};
+
+const BLOCK_WORDS: usize = 128;
+const BLOCK_LEN: usize = BLOCK_WORDS * 8;
+const SYNC_POINTS: usize = 4;
+const ARGON2_VERSION: u32 = 0x13;
+const ARGON2ID_TYPE: u32 = 2;
+
+type Block = [u64; BLOCK_WORDS];
+
+// Argon2id (RFC 9106) version 1.3, memory is in KiB, each lane is filled in turn
+#[allow(clippy::too_many_arguments)]
+pub(super) fn argon2id(
+    password: &[u8],
+    salt: &[u8],
+    secret: &[u8],
+    associated_data: &[u8],
+    memory_kib: u32,
+    passes: u32,
+    lanes: u32,
+    key_len: usize,
+) -> Vec<u8> {
+    assert!(
+        (1..0x100_0000).contains(&lanes),
+        "Argon2: Parallelism must be between 1 and 2^24 - 1"
+    );
+    assert!(
+        memory_kib >= 8 * lanes,
+        "Argon2: Memory must be at least 8 KiB per lane"
+    );
+    assert!(passes > 0, "Argon2: Pass count cannot be 0");
+    assert!(key_len >= 4, "Argon2: Key must be at least 4 bytes long");
+
+    let mut initial_hash = Blake2b::new(BLAKE2B_MAX_DIGEST_LEN);
+
+    for parameter in [
+        lanes,
+        key_len as u32,
+        memory_kib,
+        passes,
+        ARGON2_VERSION,
+        ARGON2ID_TYPE,
+    ] {
+        initial_hash.update(&parameter.to_le_bytes());
+    }
+
+    for input in [password, salt, secret, associated_data] {
+        initial_hash.update(&(input.len() as u32).to_le_bytes());
+        initial_hash.update(input);
+    }
+
+    let initial_hash = initial_hash.finalize();
+
+    let lanes = lanes as usize;
+    // Memory is rounded down to a whole number of segments in every lane
+    let lane_len = memory_kib as usize / (SYNC_POINTS * lanes) * SYNC_POINTS;
+    let mut memory = Memory {
+        blocks: vec![[0u64; BLOCK_WORDS]; lanes * lane_len],
+        lanes,
+        lane_len,
+        segment_len: lane_len / SYNC_POINTS,
+        passes: passes as usize,
+    };
+
+    for lane in 0..lanes {
+        for column in 0..2 {
+            memory.blocks[lane * lane_len + column] = bytes_to_block(&hash_long(
+                BLOCK_LEN,
+                &[
+                    &initial_hash,
+                    &(column as u32).to_le_bytes(),
+                    &(lane as u32).to_le_bytes(),
+                ],
+            ));
+        }
+    }
+
+    for pass in 0..memory.passes {
+        for slice in 0..SYNC_POINTS {
+            for lane in 0..lanes {
+                memory.fill_segment(pass, slice, lane);
+            }
+        }
+    }
+
+    let mut final_block = memory.blocks[lane_len - 1];
+
+    for lane in 1..lanes {
+        xor_block(
+            &mut final_block,
+            &memory.blocks[lane * lane_len + lane_len - 1],
+        );
+    }
+
+    hash_long(key_len, &[&block_to_bytes(&final_block)])
+}
+
+struct Memory {
+    blocks: Vec<Block>,
+    lanes: usize,
+    lane_len: usize,
+    segment_len: usize,
+    passes: usize,
+}
+
+impl Memory {
+    fn fill_segment(&mut self, pass: usize, slice: usize, lane: usize) {
+        // Argon2id resists side channels in the first half of the first pass, then tradeoff attacks
+        let data_independent = pass == 0 && slice < SYNC_POINTS / 2;
+
+        let mut input_block = [0u64; BLOCK_WORDS];
+        let mut address_block = [0u64; BLOCK_WORDS];
+
+        input_block[..6].copy_from_slice(&[
+            pass as u64,
+            lane as u64,
+            slice as u64,
+            self.blocks.len() as u64,
+            self.passes as u64,
+            ARGON2ID_TYPE as u64,
+        ]);
+
+        // The first two blocks of every lane come straight from the initial hash
+        let start_index = match pass == 0 && slice == 0 {
+            true => {
+                if data_independent {
+                    next_addresses(&mut address_block, &mut input_block);
+                }
+
+                2
+            }
+            false => 0,
+        };
+
+        for index in start_index..self.segment_len {
+            let column = slice * self.segment_len + index;
+            let previous = lane * self.lane_len + (column + self.lane_len - 1) % self.lane_len;
+
+            let pseudo_random = match data_independent {
+                true => {
+                    if index % BLOCK_WORDS == 0 {
+                        next_addresses(&mut address_block, &mut input_block);
+                    }
+
+                    address_block[index % BLOCK_WORDS]
+                }
+                false => self.blocks[previous][0],
+            };
+
+            let reference_lane = match pass == 0 && slice == 0 {
+                true => lane,
+                false => (pseudo_random >> u32::BITS) as usize % self.lanes,
+            };
+            let reference = reference_lane * self.lane_len
+                + self.reference_column(
+                    pass,
+                    slice,
+                    index,
+                    reference_lane == lane,
+                    pseudo_random as u32,
+                );
+
+            let block = compress(&self.blocks[previous], &self.blocks[reference]);
+            let current = lane * self.lane_len + column;
+
+            match pass {
+                0 => self.blocks[current] = block,
+                _ => xor_block(&mut self.blocks[current], &block),
+            }
+        }
+    }
+
+    // Blocks still being written in other lanes are never referenced
+    fn reference_column(
+        &self,
+        pass: usize,
+        slice: usize,
+        index: usize,
+        same_lane: bool,
+        pseudo_random: u32,
+    ) -> usize {
+        let finished_segments_len = match pass {
+            0 => slice * self.segment_len,
+            _ => self.lane_len - self.segment_len,
+        };
+        let reference_area_len = match same_lane {
+            true => finished_segments_len + index - 1,
+            false => finished_segments_len - (index == 0) as usize,
+        };
+
+        // Squaring skews the distribution towards recently written blocks
+        let x = (pseudo_random as u64 * pseudo_random as u64) >> u32::BITS;
+        let y = (reference_area_len as u64 * x) >> u32::BITS;
+        let relative_position = reference_area_len - 1 - y as usize;
+
+        let start_position = match pass == 0 || slice == SYNC_POINTS - 1 {
+            true => 0,
+            false => (slice + 1) * self.segment_len,
+        };
+
+        (start_position + relative_position) % self.lane_len
+    }
+}
+
+fn next_addresses(address_block: &mut Block, input_block: &mut Block) {
+    let zero_block = [0u64; BLOCK_WORDS];
+
+    input_block[6] += 1;
+    *address_block = compress(&zero_block, input_block);
+    *address_block = compress(&zero_block, address_block);
+}
+
+// The compression function G: the BLAKE2b round over the rows and then the columns of X xor Y
+fn compress(x: &Block, y: &Block) -> Block {
+    let mut r = *x;
+    xor_block(&mut r, y);
+
+    let mut z = r;
+
+    for row in 0..8 {
+        permute(&mut z, std::array::from_fn(|i| 16 * row + i));
+    }
+
+    for column in 0..8 {
+        permute(
+            &mut z,
+            std::array::from_fn(|i| 2 * column + 16 * (i / 2) + i % 2),
+        );
+    }
+
+    xor_block(&mut z, &r);
+
+    z
+}
+
+fn permute(block: &mut Block, words: [usize; 16]) {
+    for [a, b, c, d] in [
+        [0, 4, 8, 12],
+        [1, 5, 9, 13],
+        [2, 6, 10, 14],
+        [3, 7, 11, 15],
+        [0, 5, 10, 15],
+        [1, 6, 11, 12],
+        [2, 7, 8, 13],
+        [3, 4, 9, 14],
+    ] {
+        mix(block, [words[a], words[b], words[c], words[d]]);
+    }
+}
+
+// BLAKE2b mixing with a multiplication added to each addition
+fn mix(v: &mut Block, [a, b, c, d]: [usize; 4]) {
+    v[a] = multiply_add(v[a], v[b]);
+    v[d] = (v[d] ^ v[a]).rotate_right(32);
+    v[c] = multiply_add(v[c], v[d]);
+    v[b] = (v[b] ^ v[c]).rotate_right(24);
+    v[a] = multiply_add(v[a], v[b]);
+    v[d] = (v[d] ^ v[a]).rotate_right(16);
+    v[c] = multiply_add(v[c], v[d]);
+    v[b] = (v[b] ^ v[c]).rotate_right(63);
+}
+
+fn multiply_add(x: u64, y: u64) -> u64 {
+    let product = (x as u32 as u64) * (y as u32 as u64);
+
+    x.wrapping_add(y).wrapping_add(product.wrapping_mul(2))
+}
+
+// H', chains BLAKE2b digests for outputs longer than 64 bytes
+fn hash_long(digest_len: usize, inputs: &[&[u8]]) -> Vec<u8> {
+    let mut hash = Blake2b::new(digest_len.min(BLAKE2B_MAX_DIGEST_LEN));
+    hash.update(&(digest_len as u32).to_le_bytes());
+
+    for input in inputs {
+        hash.update(input);
+    }
+
+    let mut digest = hash.finalize();
+
+    if digest_len <= BLAKE2B_MAX_DIGEST_LEN {
+        return digest;
+    }
+
+    let half_digest_len = BLAKE2B_MAX_DIGEST_LEN / 2;
+    let mut output = Vec::with_capacity(digest_len);
+
+    while digest_len - output.len() > BLAKE2B_MAX_DIGEST_LEN {
+        output.extend_from_slice(&digest[..half_digest_len]);
+        digest = Blake2b::digest(
+            (digest_len - output.len()).min(BLAKE2B_MAX_DIGEST_LEN),
+            &digest,
+        );
+    }
+
+    output.extend_from_slice(&digest);
+
+    output
+}
+
+fn xor_block(block: &mut Block, other: &Block) {
+    block
+        .iter_mut()
+        .zip(other)
+        .for_each(|(word, other_word)| *word ^= other_word);
+}
+
+fn bytes_to_block(bytes: &[u8]) -> Block {
+    std::array::from_fn(|i| u64::from_le_bytes(bytes[8 * i..8 * i + 8].try_into().unwrap()))
+}
+
+fn block_to_bytes(block: &Block) -> Vec<u8> {
+    block.iter().flat_map(|word| word.to_le_bytes()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // RFC 9106, section 5.3
+    #[test]
+    fn argon2id_with_secret_and_associated_data() {
+        let left = argon2id(
+            &[0x01; 32],
+            &[0x02; 16],
+            &[0x03; 8],
+            &[0x04; 12],
+            32,
+            3,
+            4,
+            32,
+        );
+        let right = [
+            0x0D, 0x64, 0x0D, 0xF5, 0x8D, 0x78, 0x76, 0x6C, 0x08, 0xC0, 0x37, 0xA3, 0x4A, 0x8B,
+            0x53, 0xC9, 0xD0, 0x1E, 0xF0, 0x45, 0x2D, 0x75, 0xB6, 0x5E, 0xB5, 0x25, 0x20, 0xE9,
+            0x6B, 0x01, 0xE6, 0x59,
+        ];
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn hash_long_output_length() {
+        for digest_len in [4, 64, 65, 96, 97, 1024] {
+            let left = hash_long(digest_len, &[b"input"]).len();
+            let right = digest_len;
+
+            assert_eq!(left, right);
+        }
+    }
+
+    #[should_panic]
+    #[test]
+    fn too_little_memory() {
+        argon2id(b"password", b"somesalt", &[], &[], 15, 1, 2, 32);
+    }
+}
@@ -0,0 +1,90 @@
+use sha256::{hmac::HmacSha256, SHA256_DIGEST_LEN};
+
+// PBKDF2 (RFC 8018) with HMAC-SHA256 as the pseudorandom function
+pub(super) fn pbkdf2_sha256(
+    password: &[u8],
+    salt: &[u8],
+    iterations: u32,
+    key_len: usize,
+) -> Vec<u8> {
+    assert!(iterations > 0, "PBKDF2: Iteration count cannot be 0");
+
+    // The keyed HMAC state is computed once and cloned for every iteration
+    let keyed_hmac = HmacSha256::new(password);
+    let mut key = Vec::with_capacity(key_len.next_multiple_of(SHA256_DIGEST_LEN));
+
+    for block_index in 1..=key_len.div_ceil(SHA256_DIGEST_LEN) as u32 {
+        let mut hmac = keyed_hmac.clone();
+        hmac.update(salt);
+        hmac.update(&block_index.to_be_bytes());
+
+        let mut u = hmac.finalize();
+        let mut block = u;
+
+        for _ in 1..iterations {
+            let mut hmac = keyed_hmac.clone();
+            hmac.update(&u);
+            u = hmac.finalize();
+
+            block
+                .iter_mut()
+                .zip(u)
+                .for_each(|(byte, u_byte)| *byte ^= u_byte);
+        }
+
+        key.extend_from_slice(&block);
+    }
+
+    key.truncate(key_len);
+
+    key
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // RFC 7914, section 11
+    #[test]
+    fn pbkdf2_single_iteration() {
+        let left = pbkdf2_sha256(b"passwd", b"salt", 1, 64);
+        let right = [
+            0x55, 0xAC, 0x04, 0x6E, 0x56, 0xE3, 0x08, 0x9F, 0xEC, 0x16, 0x91, 0xC2, 0x25, 0x44,
+            0xB6, 0x05, 0xF9, 0x41, 0x85, 0x21, 0x6D, 0xDE, 0x04, 0x65, 0xE6, 0x8B, 0x9D, 0x57,
+            0xC2, 0x0D, 0xAC, 0xBC, 0x49, 0xCA, 0x9C, 0xCC, 0xF1, 0x79, 0xB6, 0x45, 0x99, 0x16,
+            0x64, 0xB3, 0x9D, 0x77, 0xEF, 0x31, 0x7C, 0x71, 0xB8, 0x45, 0xB1, 0xE3, 0x0B, 0xD5,
+            0x09, 0x11, 0x20, 0x41, 0xD3, 0xA1, 0x97, 0x83,
+        ];
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn pbkdf2_many_iterations() {
+        let left = pbkdf2_sha256(b"password", b"salt", 4096, 32);
+        let right = [
+            0xC5, 0xE4, 0x78, 0xD5, 0x92, 0x88, 0xC8, 0x41, 0xAA, 0x53, 0x0D, 0xB6, 0x84, 0x5C,
+            0x4C, 0x8D, 0x96, 0x28, 0x93, 0xA0, 0x01, 0xCE, 0x4E, 0x11, 0xA4, 0x96, 0x38, 0x73,
+            0xAA, 0x98, 0x13, 0x4A,
+        ];
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn pbkdf2_partial_last_block() {
+        let left = pbkdf2_sha256(
+            b"passwordPASSWORDpassword",
+            b"saltSALTsaltSALTsaltSALTsaltSALTsalt",
+            4096,
+            40,
+        );
+        let right = [
+            0x34, 0x8C, 0x89, 0xDB, 0xCB, 0xD3, 0x2B, 0x2F, 0x32, 0xD8, 0x14, 0xB8, 0x11, 0x6E,
+            0x84, 0xCF, 0x2B, 0x17, 0x34, 0x7E, 0xBC, 0x18, 0x00, 0x18, 0x1C, 0x4E, 0x2A, 0x1F,
+            0xB8, 0xDD, 0x53, 0xE1, 0xC6, 0x35, 0x51, 0x8C, 0x7D, 0xAC, 0x47, 0xE9,
+        ];
+
+        assert_eq!(left, right);
+    }
+}
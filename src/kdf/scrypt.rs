@@ -0,0 +1,168 @@
+use super::pbkdf2::pbkdf2_sha256;
+
+const SALSA_BLOCK_WORDS: usize = 16;
+
+// scrypt (RFC 7914), memory use is 128 * r * N bytes per lane of parallelism
+pub(super) fn scrypt(
+    password: &[u8],
+    salt: &[u8],
+    log_n: u8,
+    block_size: u32,
+    parallelism: u32,
+    key_len: usize,
+) -> Vec<u8> {
+    assert!(
+        log_n > 0 && (log_n as u32) < 16 * block_size && log_n < u64::BITS as u8,
+        "scrypt: Cost must satisfy 0 < log2(N) < 16 * r"
+    );
+    assert!(
+        block_size > 0 && parallelism > 0,
+        "scrypt: Block size and parallelism cannot be 0"
+    );
+
+    let block_words = 32 * block_size as usize;
+    let bytes = pbkdf2_sha256(password, salt, 1, 4 * block_words * parallelism as usize);
+
+    let mut mixed = Vec::with_capacity(bytes.len());
+
+    for block in bytes.chunks_exact(4 * block_words) {
+        let mut words: Vec<u32> = block
+            .chunks_exact(4)
+            .map(|word| u32::from_le_bytes(word.try_into().unwrap()))
+            .collect();
+
+        ro_mix(&mut words, 1 << log_n);
+
+        mixed.extend(words.iter().flat_map(|word| word.to_le_bytes()));
+    }
+
+    pbkdf2_sha256(password, &mixed, 1, key_len)
+}
+
+// Sequential memory-hard mixing, every one of the N earlier states may be read back
+fn ro_mix(block: &mut [u32], cost: usize) {
+    let block_len = block.len();
+    let mut states = Vec::with_capacity(cost * block_len);
+
+    for _ in 0..cost {
+        states.extend_from_slice(block);
+        block_mix(block);
+    }
+
+    for _ in 0..cost {
+        let j = integerify(block) as usize & (cost - 1);
+
+        for (word, state_word) in block.iter_mut().zip(&states[j * block_len..]) {
+            *word ^= state_word;
+        }
+
+        block_mix(block);
+    }
+}
+
+fn integerify(block: &[u32]) -> u64 {
+    let last_block = &block[block.len() - SALSA_BLOCK_WORDS..];
+
+    last_block[0] as u64 | (last_block[1] as u64) << u32::BITS
+}
+
+// Even output blocks go to the first half and odd ones to the second
+fn block_mix(block: &mut [u32]) {
+    let num_blocks = block.len() / SALSA_BLOCK_WORDS;
+    let mut x: [u32; SALSA_BLOCK_WORDS] =
+        block[block.len() - SALSA_BLOCK_WORDS..].try_into().unwrap();
+    let mut output = vec![0u32; block.len()];
+
+    for (i, salsa_block) in block.chunks_exact(SALSA_BLOCK_WORDS).enumerate() {
+        x.iter_mut()
+            .zip(salsa_block)
+            .for_each(|(word, block_word)| *word ^= block_word);
+        salsa20_8(&mut x);
+
+        let position = (i / 2 + (i % 2) * num_blocks / 2) * SALSA_BLOCK_WORDS;
+        output[position..position + SALSA_BLOCK_WORDS].copy_from_slice(&x);
+    }
+
+    block.copy_from_slice(&output);
+}
+
+fn salsa20_8(block: &mut [u32; SALSA_BLOCK_WORDS]) {
+    let mut x = *block;
+
+    for _ in 0..4 {
+        // Columns
+        quarter_round(&mut x, [0, 4, 8, 12]);
+        quarter_round(&mut x, [5, 9, 13, 1]);
+        quarter_round(&mut x, [10, 14, 2, 6]);
+        quarter_round(&mut x, [15, 3, 7, 11]);
+        // Rows
+        quarter_round(&mut x, [0, 1, 2, 3]);
+        quarter_round(&mut x, [5, 6, 7, 4]);
+        quarter_round(&mut x, [10, 11, 8, 9]);
+        quarter_round(&mut x, [15, 12, 13, 14]);
+    }
+
+    for (word, mixed) in block.iter_mut().zip(x) {
+        *word = word.wrapping_add(mixed);
+    }
+}
+
+fn quarter_round(x: &mut [u32; SALSA_BLOCK_WORDS], [a, b, c, d]: [usize; 4]) {
+    x[b] ^= x[a].wrapping_add(x[d]).rotate_left(7);
+    x[c] ^= x[b].wrapping_add(x[a]).rotate_left(9);
+    x[d] ^= x[c].wrapping_add(x[b]).rotate_left(13);
+    x[a] ^= x[d].wrapping_add(x[c]).rotate_left(18);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // RFC 7914, section 12
+    #[test]
+    fn scrypt_empty() {
+        let left = scrypt(b"", b"", 4, 1, 1, 64);
+        let right = [
+            0x77, 0xD6, 0x57, 0x62, 0x38, 0x65, 0x7B, 0x20, 0x3B, 0x19, 0xCA, 0x42, 0xC1, 0x8A,
+            0x04, 0x97, 0xF1, 0x6B, 0x48, 0x44, 0xE3, 0x07, 0x4A, 0xE8, 0xDF, 0xDF, 0xFA, 0x3F,
+            0xED, 0xE2, 0x14, 0x42, 0xFC, 0xD0, 0x06, 0x9D, 0xED, 0x09, 0x48, 0xF8, 0x32, 0x6A,
+            0x75, 0x3A, 0x0F, 0xC8, 0x1F, 0x17, 0xE8, 0xD3, 0xE0, 0xFB, 0x2E, 0x0D, 0x36, 0x28,
+            0xCF, 0x35, 0xE2, 0x0C, 0x38, 0xD1, 0x89, 0x06,
+        ];
+
+        assert_eq!(left, right);
+    }
+
+    // RFC 7914, section 12
+    #[test]
+    fn scrypt_parallel() {
+        let left = scrypt(b"password", b"NaCl", 10, 8, 16, 64);
+        let right = [
+            0xFD, 0xBA, 0xBE, 0x1C, 0x9D, 0x34, 0x72, 0x00, 0x78, 0x56, 0xE7, 0x19, 0x0D, 0x01,
+            0xE9, 0xFE, 0x7C, 0x6A, 0xD7, 0xCB, 0xC8, 0x23, 0x78, 0x30, 0xE7, 0x73, 0x76, 0x63,
+            0x4B, 0x37, 0x31, 0x62, 0x2E, 0xAF, 0x30, 0xD9, 0x2E, 0x22, 0xA3, 0x88, 0x6F, 0xF1,
+            0x09, 0x27, 0x9D, 0x98, 0x30, 0xDA, 0xC7, 0x27, 0xAF, 0xB9, 0x4A, 0x83, 0xEE, 0x6D,
+            0x83, 0x60, 0xCB, 0xDF, 0xA2, 0xCC, 0x06, 0x40,
+        ];
+
+        assert_eq!(left, right);
+    }
+
+    // RFC 7914, section 8, the words are the little-endian reading of the example bytes
+    #[test]
+    fn salsa20_8_core() {
+        let mut left = [
+            0x219A877E, 0x86C93E4F, 0xE640A97C, 0x268F7141, 0x5B55EEBA, 0xB5C1618C, 0x1146F80D,
+            0x1D3BCD6D, 0x19F324EE, 0x853D9BDF, 0x4B1E1214, 0x32AAC55A, 0x291D0276, 0x2948C709,
+            0x8DC6EBED, 0x5EC2B8B8,
+        ];
+        salsa20_8(&mut left);
+        let right = [
+            0x9C851FA4, 0x99CC0866, 0xCBCA813B, 0x05EF0C02, 0x81214B04, 0x7D33FDA2, 0x631C7BFD,
+            0x292F6896, 0x683139B4, 0xBCE6C9E3, 0xB7C56BFE, 0xBA966DA0, 0x10CC24E4, 0x5C74912C,
+            0x3D67AD24, 0x818F61C7,
+        ];
+
+        assert_eq!(left, right);
+    }
+}
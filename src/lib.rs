@@ -1,11 +1,33 @@
+mod armor;
 mod command;
+mod decryptor;
+mod drbg;
+mod encryptor;
+mod error;
+mod header;
 pub mod io;
+mod kdf;
+mod mode;
+mod padding;
+mod pipeline;
 mod strategy;
 
 use clap::{Args, Parser, Subcommand, ValueEnum};
-use std::io::Result;
 
-type DataChunk = [u8; BLOCK_CHUNK_SIZE];
+use pipeline::{
+    check_counter_bits, check_iterations, check_memory_cost, check_sector_size, check_segment_size,
+    check_time_cost, RangeCheck, DEFAULT_COUNTER_BITS, DEFAULT_ITERATIONS, DEFAULT_MEMORY_COST,
+    DEFAULT_NONCE_LEN, DEFAULT_PARALLELISM, DEFAULT_SECTOR_SIZE, DEFAULT_SEGMENT_SIZE,
+    DEFAULT_TIME_COST,
+};
+
+pub use armor::{ArmoringWriter, DearmoringReader};
+pub use decryptor::{decrypt_file, Decryptor};
+pub use encryptor::{encrypt_file, Encryptor};
+pub use error::{Result, SymciphError};
+pub use io::{DecryptingReader, EncryptingWriter};
+
+pub type DataChunk = [u8; BLOCK_CHUNK_SIZE];
 
 const BLOCK_CHUNK_SIZE: usize = 8;
 
@@ -19,13 +41,92 @@ pub struct App {
     command: Commands,
 }
 
-#[derive(ValueEnum, Clone)]
-enum EncryptionSchemes {
+#[derive(ValueEnum, Clone, PartialEq)]
+pub enum EncryptionSchemes {
     Des,
+    /// Triple DES (EDE), keying option 1, 2 or 3 with a 24, 16 or 8 byte key
     Tdes,
     Aes128,
     Aes192,
     Aes256,
+    /// DES as implemented before it followed FIPS 46-3, only to decrypt files written with it
+    DesLegacy,
+    /// Triple DES as three legacy DES encryptions (EEE), only to decrypt files written before EDE
+    TdesLegacyEee,
+}
+
+#[derive(ValueEnum, Clone)]
+pub enum EncryptionModes {
+    /// Electronic codebook, every block encrypted independently
+    Ecb,
+    /// Cipher block chaining with a random IV prepended to the ciphertext
+    Cbc,
+    /// CBC with ciphertext stealing, the penultimate block is truncated in place
+    CbcCs1,
+    /// CBC with ciphertext stealing, the last two blocks are swapped unless the input is block aligned
+    CbcCs2,
+    /// CBC with ciphertext stealing, the last two blocks are always swapped as in Kerberos
+    CbcCs3,
+    /// Propagating cipher block chaining
+    Pcbc,
+    /// Full-block cipher feedback, no padding required
+    Cfb,
+    /// 8-bit cipher feedback, one block encryption per byte
+    Cfb8,
+    /// Output feedback, no padding required
+    Ofb,
+    /// Counter mode, turns the block cipher into a stream cipher without padding
+    Ctr,
+    /// Galois/counter mode, authenticated encryption with a tag appended to the ciphertext (AES only)
+    Gcm,
+    /// Online GCM (STREAM) over fixed-size segments, each verified before it is written (AES only)
+    GcmStream,
+    /// Counter with CBC-MAC, authenticated encryption with configurable nonce and tag lengths (AES only)
+    Ccm,
+    /// Offset codebook (RFC 7253), one-pass authenticated encryption with 1 to 15 byte nonces (AES only)
+    Ocb,
+    /// Two-pass CTR and CMAC authenticated encryption, tags up to the cipher block size (AES and 3DES)
+    Eax,
+    /// Synthetic IV (RFC 5297), deterministic without a nonce, takes a key twice the cipher's size (AES only)
+    Siv,
+    /// Nonce misuse-resistant GCM (RFC 8452), deterministic without a nonce (AES-128 and AES-256 only)
+    GcmSiv,
+    /// XEX tweakable mode with ciphertext stealing (IEEE 1619) for disk images, takes a key twice the cipher's size (AES-128 and AES-256 only)
+    Xts,
+}
+
+#[derive(ValueEnum, Clone)]
+pub enum PaddingSchemes {
+    /// Every padding byte holds the padding length (PKCS#7)
+    Pkcs7,
+    /// Zeros followed by the padding length (ANSI X9.23)
+    AnsiX923,
+    /// A 0x80 byte followed by zeros (ISO/IEC 7816-4)
+    Iso7816,
+    /// Random bytes followed by the padding length (ISO 10126)
+    Iso10126,
+    /// No padding, the input must be a multiple of the block size
+    None,
+}
+
+#[derive(ValueEnum, Clone)]
+pub enum KdfSchemes {
+    /// Memory-hard Argon2id (RFC 9106), tuned with --memory-cost, --time-cost and --parallelism
+    Argon2id,
+    /// Memory-hard scrypt (RFC 7914) with r = 8, tuned with --memory-cost and --parallelism
+    Scrypt,
+    /// PBKDF2-HMAC-SHA256 (RFC 8018), tuned with --iterations, not memory-hard
+    Pbkdf2,
+}
+
+#[derive(ValueEnum, Clone, PartialEq, Debug)]
+pub enum ArmorSchemes {
+    /// Lowercase hex digits, 64 to a line
+    Hex,
+    /// Base64 (RFC 4648), 64 characters to a line
+    Base64,
+    /// Base64 between BEGIN and END lines, with header fields and a CRC24 checksum
+    Pem,
 }
 
 #[derive(Subcommand)]
@@ -35,42 +136,234 @@ enum Commands {
     Encrypt(Encrypt),
     /// Decrypt a ciphertext file
     #[command(short_flag = 'd')]
+    #[command(
+        override_usage = "symciph decrypt [OPTIONS] [CIPHER] <INPUT_PATH> <OUTPUT_PATH> <KEY>\n       symciph decrypt --password [OPTIONS] [CIPHER] <INPUT_PATH> <OUTPUT_PATH>"
+    )]
     Decrypt(Decrypt),
     /// Generate a pseudorandom symmetric key
     #[command(short_flag = 'g')]
     GenerateKey(GenerateKey),
+    /// Audit a key file for length, parity bits and weak or degenerate DES keys
+    CheckKey(CheckKey),
+    /// Calibrate password key derivation parameters to a target time on this machine
+    KdfBench(KdfBench),
 }
 
 #[derive(Args)]
 struct Encrypt {
     /// Encryption algorithm
     cipher: EncryptionSchemes,
-    /// Plaintext file path
+    /// Plaintext file path, - for stdin
     input_path: String,
-    /// Ciphertext file path
+    /// Ciphertext file path, - for stdout
     output_path: String,
-    /// Encryption key file path
-    key: String,
+    /// Encryption key file path, - for stdin, left out when the key is derived from a password
+    /// or read with --key-fd
+    #[arg(
+        required_unless_present_any = ["password", "password_fd", "key_fd"],
+        conflicts_with_all = ["password", "password_fd", "key_fd"]
+    )]
+    key: Option<String>,
+    /// Read the key from an inherited file descriptor instead of a key file
+    #[arg(long, value_name = "FD", conflicts_with_all = ["password", "password_fd"])]
+    key_fd: Option<u32>,
+    /// Write a headerless file with only the IV or nonce in front of the ciphertext
+    #[arg(long)]
+    raw: bool,
+    /// Derive the key from a password prompted for without echo, instead of reading a key file
+    #[arg(long)]
+    password: bool,
+    /// Read the password from the first line of an inherited file descriptor instead of prompting
+    #[arg(long, value_name = "FD")]
+    password_fd: Option<u32>,
+    /// Key derivation function when deriving the key from a password
+    #[arg(long, value_enum, default_value_t = KdfSchemes::Argon2id)]
+    kdf: KdfSchemes,
+    /// PBKDF2 iteration count
+    #[arg(long, default_value_t = DEFAULT_ITERATIONS, value_parser = parse_iterations)]
+    iterations: u32,
+    /// Memory in KiB used by Argon2id and scrypt
+    #[arg(long, default_value_t = DEFAULT_MEMORY_COST, value_parser = parse_memory_cost)]
+    memory_cost: u32,
+    /// Number of passes over memory in Argon2id
+    #[arg(long, default_value_t = DEFAULT_TIME_COST, value_parser = parse_time_cost)]
+    time_cost: u32,
+    /// Number of lanes in Argon2id and of independent mixes in scrypt
+    #[arg(long, default_value_t = DEFAULT_PARALLELISM, value_parser = clap::value_parser!(u32).range(1..=255))]
+    parallelism: u32,
+    /// Block cipher mode of operation
+    #[arg(short, long, value_enum, default_value_t = EncryptionModes::Ecb)]
+    mode: EncryptionModes,
+    /// Padding of the final block in ECB, CBC and PCBC modes
+    #[arg(short, long, value_enum, default_value_t = PaddingSchemes::Pkcs7)]
+    padding: PaddingSchemes,
+    /// Counter size in bits in CTR mode, the rest of the counter block is the nonce
+    #[arg(long, default_value_t = DEFAULT_COUNTER_BITS, value_parser = parse_counter_bits)]
+    counter_bits: usize,
+    /// Additional authenticated data in AEAD modes, bound to the ciphertext but not encrypted
+    #[arg(long, default_value = "")]
+    aad: String,
+    /// Nonce length in bytes in AEAD modes, GCM requires 12, 0 makes SIV and GCM-SIV deterministic
+    #[arg(long, default_value_t = DEFAULT_NONCE_LEN)]
+    nonce_len: usize,
+    /// Authentication tag length in bytes in AEAD modes, CCM, OCB and EAX accept shorter tags
+    /// [default: 16, or 8 for EAX with DES and 3DES]
+    #[arg(long)]
+    tag_len: Option<usize>,
+    /// Data unit size in bytes in XTS mode, every sector is encrypted independently
+    #[arg(long, default_value_t = DEFAULT_SECTOR_SIZE, value_parser = parse_sector_size)]
+    sector_size: usize,
+    /// Number of the first sector in XTS mode, used as the tweak of the first data unit
+    #[arg(long, default_value_t = 0)]
+    start_sector: u64,
+    /// Plaintext segment size in bytes in GCM-STREAM mode, each segment carries its own tag
+    #[arg(long, default_value_t = DEFAULT_SEGMENT_SIZE, value_parser = parse_segment_size)]
+    segment_size: usize,
+    /// Write the ciphertext as text, decryption recognizes the armor on its own
+    #[arg(long, value_enum)]
+    armor: Option<ArmorSchemes>,
 }
 
 #[derive(Args)]
 struct Decrypt {
-    /// Encryption algorithm
-    cipher: EncryptionSchemes,
-    /// Ciphertext file path
-    input_path: String,
-    /// Plaintext file path
-    output_path: String,
-    /// Decryption key file path
-    key: String,
+    /// Encryption algorithm (optional unless --raw, the container header names it),
+    /// ciphertext file path (- for stdin), plaintext file path (- for stdout) and decryption key
+    /// file path (- for stdin, left out with --password or --key-fd)
+    #[arg(
+        num_args = 2..=4,
+        required = true,
+        value_names = ["CIPHER", "INPUT_PATH", "OUTPUT_PATH", "KEY"]
+    )]
+    positionals: Vec<String>,
+    /// Read a headerless file, the cipher and mode options must match those used to encrypt it
+    #[arg(long)]
+    raw: bool,
+    /// Derive the key from a password prompted for without echo, instead of reading a key file
+    #[arg(long)]
+    password: bool,
+    /// Read the password from the first line of an inherited file descriptor instead of prompting
+    #[arg(long, value_name = "FD")]
+    password_fd: Option<u32>,
+    /// Read the key from an inherited file descriptor instead of a key file
+    #[arg(long, value_name = "FD", conflicts_with_all = ["password", "password_fd"])]
+    key_fd: Option<u32>,
+    /// Block cipher mode of operation
+    #[arg(short, long, value_enum, default_value_t = EncryptionModes::Ecb)]
+    mode: EncryptionModes,
+    /// Padding of the final block in ECB, CBC and PCBC modes
+    #[arg(short, long, value_enum, default_value_t = PaddingSchemes::Pkcs7)]
+    padding: PaddingSchemes,
+    /// Counter size in bits in CTR mode, the rest of the counter block is the nonce
+    #[arg(long, default_value_t = DEFAULT_COUNTER_BITS, value_parser = parse_counter_bits)]
+    counter_bits: usize,
+    /// Additional authenticated data in AEAD modes, bound to the ciphertext but not encrypted
+    #[arg(long, default_value = "")]
+    aad: String,
+    /// Nonce length in bytes in AEAD modes, GCM requires 12, 0 makes SIV and GCM-SIV deterministic
+    #[arg(long, default_value_t = DEFAULT_NONCE_LEN)]
+    nonce_len: usize,
+    /// Authentication tag length in bytes in AEAD modes, CCM, OCB and EAX accept shorter tags
+    /// [default: 16, or 8 for EAX with DES and 3DES]
+    #[arg(long)]
+    tag_len: Option<usize>,
+    /// Data unit size in bytes in XTS mode, every sector is encrypted independently
+    #[arg(long, default_value_t = DEFAULT_SECTOR_SIZE, value_parser = parse_sector_size)]
+    sector_size: usize,
+    /// Number of the first sector in XTS mode, used as the tweak of the first data unit
+    #[arg(long, default_value_t = 0)]
+    start_sector: u64,
+    /// Plaintext segment size in bytes in GCM-STREAM mode, each segment carries its own tag
+    #[arg(long, default_value_t = DEFAULT_SEGMENT_SIZE, value_parser = parse_segment_size)]
+    segment_size: usize,
+    /// First plaintext byte to decrypt in CTR, XTS and GCM-STREAM modes, the input is seeked past the rest
+    #[arg(long, default_value_t = 0)]
+    offset: u64,
+    /// Number of plaintext bytes to decrypt from the offset, up to the end of the plaintext by default
+    #[arg(long)]
+    length: Option<u64>,
 }
 
 #[derive(Args)]
 struct GenerateKey {
     /// Encryption algorithm
     cipher: EncryptionSchemes,
+    /// Block cipher mode of operation the key is for
+    #[arg(short, long, value_enum, default_value_t = EncryptionModes::Ecb)]
+    mode: EncryptionModes,
     /// Generated key file path
     output_path: String,
+    /// 3DES keying option, 1 for three independent keys, 2 for two and 3 for a single DES key
+    /// [default: 1]
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=3))]
+    keying_option: Option<u8>,
+    /// Reseed the random bit generator from the operating system right before the key is drawn
+    #[arg(long)]
+    prediction_resistance: bool,
+    /// Write the key as text, key files are read in any armor as well as raw
+    #[arg(long, value_enum)]
+    armor: Option<ArmorSchemes>,
+}
+
+fn parse_counter_bits(arg: &str) -> std::result::Result<usize, String> {
+    parse_checked(arg, check_counter_bits)
+}
+
+fn parse_sector_size(arg: &str) -> std::result::Result<usize, String> {
+    parse_checked(arg, check_sector_size)
+}
+
+#[derive(Args)]
+struct CheckKey {
+    /// Encryption algorithm
+    cipher: EncryptionSchemes,
+    /// Block cipher mode of operation the key is for
+    #[arg(short, long, value_enum, default_value_t = EncryptionModes::Ecb)]
+    mode: EncryptionModes,
+    /// Key file path
+    key: String,
+}
+
+#[derive(Args)]
+struct KdfBench {
+    /// Key derivation function to calibrate
+    #[arg(long, value_enum, default_value_t = KdfSchemes::Argon2id)]
+    kdf: KdfSchemes,
+    /// Derivation time to aim for in milliseconds
+    #[arg(long, default_value_t = 1000)]
+    target_ms: u64,
+    /// Most memory in KiB Argon2id and scrypt may use, halved until one pass fits in the target
+    #[arg(long, default_value_t = DEFAULT_MEMORY_COST, value_parser = parse_memory_cost)]
+    memory_cost: u32,
+    /// Number of lanes in Argon2id and of independent mixes in scrypt
+    #[arg(long, default_value_t = DEFAULT_PARALLELISM, value_parser = clap::value_parser!(u32).range(1..=255))]
+    parallelism: u32,
+}
+
+fn parse_iterations(arg: &str) -> std::result::Result<u32, String> {
+    parse_checked(arg, check_iterations)
+}
+
+fn parse_memory_cost(arg: &str) -> std::result::Result<u32, String> {
+    parse_checked(arg, check_memory_cost)
+}
+
+fn parse_time_cost(arg: &str) -> std::result::Result<u32, String> {
+    parse_checked(arg, check_time_cost)
+}
+
+fn parse_segment_size(arg: &str) -> std::result::Result<usize, String> {
+    parse_checked(arg, check_segment_size)
+}
+
+fn parse_checked<T: std::str::FromStr + Copy>(
+    arg: &str,
+    check: fn(T) -> RangeCheck,
+) -> std::result::Result<T, String> {
+    let value: T = arg
+        .parse()
+        .map_err(|_| format!("{} is not a number", arg))?;
+
+    check(value).map(|_| value).map_err(String::from)
 }
 
 impl App {
@@ -1,7 +1,10 @@
+use std::process;
+
 use symciph::App;
 
 fn main() {
     if let Err(err) = App::new().run() {
-        eprint!("Error: {}", err);
+        eprintln!("Error: {}", err);
+        process::exit(err.exit_code());
     }
 }
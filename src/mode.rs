@@ -0,0 +1,759 @@
+mod cbc;
+mod cbc_cs;
+mod ccm;
+mod cfb;
+mod cfb8;
+mod cmac;
+mod ctr;
+mod eax;
+mod ecb;
+mod gcm;
+mod gcm_siv;
+mod ghash;
+mod ocb;
+mod ofb;
+mod pcbc;
+mod siv;
+mod stream;
+mod xts;
+
+use rand::Rng;
+
+use self::{
+    cbc::CbcMode,
+    cbc_cs::{CbcCsMode, Stealing},
+    ccm::{CCM_MAX_NONCE_LEN, CCM_MAX_TAG_LEN, CCM_MIN_NONCE_LEN, CCM_MIN_TAG_LEN},
+    cfb::CfbMode,
+    cfb8::Cfb8Mode,
+    eax::EAX_MIN_TAG_LEN,
+    ecb::EcbMode,
+    gcm::{GCM_NONCE_LEN, GCM_TAG_LEN},
+    gcm_siv::{GCM_SIV_NONCE_LEN, GCM_SIV_TAG_LEN},
+    ocb::{OCB_MAX_NONCE_LEN, OCB_MAX_TAG_LEN, OCB_MIN_NONCE_LEN, OCB_MIN_TAG_LEN},
+    ofb::OfbMode,
+    pcbc::PcbcMode,
+    siv::SIV_TAG_LEN,
+};
+
+use super::{
+    error::{Result, SymciphError},
+    strategy::EncryptionStrategy,
+    DataChunk, EncryptionModes, EncryptionSchemes, BLOCK_CHUNK_SIZE,
+};
+
+pub(crate) use self::{
+    ccm::{ccm_max_payload_len, CcmMode},
+    ctr::{counter_exhausted_error, CtrMode},
+    eax::EaxMode,
+    gcm::{GcmMode, GCM_MAX_PAYLOAD_LEN},
+    gcm_siv::{GcmSivMode, GCM_SIV_MAX_PLAINTEXT_LEN},
+    ocb::OcbMode,
+    siv::SivMode,
+    stream::{segment_counter_error, StreamMode, STREAM_TAG_LEN},
+    xts::{XtsMode, XTS_BLOCK_LEN},
+};
+
+pub(crate) trait EncryptionMode {
+    fn encrypt(&mut self, plaintext: &[DataChunk]) -> Result<Vec<DataChunk>>;
+    fn decrypt(&mut self, ciphertext: &[DataChunk]) -> Result<Vec<DataChunk>>;
+}
+
+pub(crate) trait AuthenticatedMode {
+    fn seal(&mut self, aad: &[u8], plaintext: &[u8]) -> Vec<u8>;
+    // Returns None without exposing any plaintext if the tag does not verify
+    fn open(&mut self, aad: &[u8], ciphertext: &[u8]) -> Option<Vec<u8>>;
+}
+
+impl EncryptionModes {
+    pub(crate) fn mode(
+        &self,
+        strategy: Box<dyn EncryptionStrategy>,
+        iv: &[DataChunk],
+        counter_bits: usize,
+    ) -> Result<Box<dyn EncryptionMode>> {
+        Ok(match self {
+            Self::Ecb => Box::new(EcbMode::new(strategy)),
+            Self::Cbc => Box::new(CbcMode::new(strategy, iv)?),
+            Self::Pcbc => Box::new(PcbcMode::new(strategy, iv)?),
+            Self::Cfb => Box::new(CfbMode::new(strategy, iv)?),
+            Self::Cfb8 => Box::new(Cfb8Mode::new(strategy, iv)?),
+            Self::Ofb => Box::new(OfbMode::new(strategy, iv)?),
+            Self::Ctr => Box::new(CtrMode::new(strategy, iv, counter_bits)?),
+            Self::Gcm | Self::Ccm | Self::Ocb | Self::Eax | Self::Siv | Self::GcmSiv => unreachable!(
+                "🙀🧨 Mode: Authenticated modes process whole messages. This was not supposed to happen."
+            ),
+            Self::Xts => unreachable!(
+                "🙀🧨 Mode: XTS processes whole sectors. This was not supposed to happen."
+            ),
+            Self::GcmStream => unreachable!(
+                "🙀🧨 Mode: STREAM processes whole segments. This was not supposed to happen."
+            ),
+            Self::CbcCs1 | Self::CbcCs2 | Self::CbcCs3 => unreachable!(
+                "🙀🧨 Mode: Ciphertext stealing processes whole messages. This was not supposed to happen."
+            ),
+        })
+    }
+
+    pub(crate) fn segmented_mode(
+        &self,
+        cipher: &EncryptionSchemes,
+        key: &[DataChunk],
+        nonce: &[u8],
+    ) -> Result<StreamMode> {
+        assert!(
+            matches!(self, Self::GcmStream),
+            "🙀🧨 Mode: Not a segmented mode. This was not supposed to happen."
+        );
+
+        Ok(StreamMode::new(cipher.strategy(key)?, nonce))
+    }
+
+    pub(crate) fn stealing_mode(
+        &self,
+        strategy: Box<dyn EncryptionStrategy>,
+        iv: &[DataChunk],
+    ) -> Result<CbcCsMode> {
+        let stealing = match self {
+            Self::CbcCs1 => Stealing::Cs1,
+            Self::CbcCs2 => Stealing::Cs2,
+            Self::CbcCs3 => Stealing::Cs3,
+            _ => unreachable!(
+                "🙀🧨 Mode: Not a ciphertext stealing mode. This was not supposed to happen."
+            ),
+        };
+
+        CbcCsMode::new(strategy, iv, stealing)
+    }
+
+    // XTS keys are a data key followed by a tweak key of the cipher's size
+    pub(crate) fn sector_mode(
+        &self,
+        cipher: &EncryptionSchemes,
+        key: &[DataChunk],
+    ) -> Result<XtsMode> {
+        assert!(
+            matches!(self, Self::Xts),
+            "🙀🧨 Mode: Not a sector mode. This was not supposed to happen."
+        );
+
+        let (data_key, tweak_key) = key.split_at(key.len() / 2);
+
+        Ok(XtsMode::new(
+            cipher.strategy(data_key)?,
+            cipher.strategy(tweak_key)?,
+        ))
+    }
+
+    pub(crate) fn authenticated_mode(
+        &self,
+        cipher: &EncryptionSchemes,
+        key: &[DataChunk],
+        nonce: &[u8],
+        tag_len: usize,
+    ) -> Result<Box<dyn AuthenticatedMode>> {
+        Ok(match self {
+            Self::Gcm => Box::new(GcmMode::new(cipher.strategy(key)?, nonce)),
+            Self::Ccm => Box::new(CcmMode::new(cipher.strategy(key)?, nonce, tag_len)),
+            Self::Ocb => Box::new(OcbMode::new(cipher.strategy(key)?, nonce, tag_len)),
+            Self::Eax => Box::new(EaxMode::new(cipher.strategy(key)?, nonce, tag_len)),
+            Self::Siv => {
+                let (mac_key, ctr_key) = key.split_at(key.len() / 2);
+
+                Box::new(SivMode::new(
+                    cipher.strategy(mac_key)?,
+                    cipher.strategy(ctr_key)?,
+                    nonce,
+                ))
+            }
+            Self::GcmSiv => Box::new(GcmSivMode::new(key, nonce)?),
+            _ => unreachable!(
+                "🙀🧨 Mode: Not an authenticated mode. This was not supposed to happen."
+            ),
+        })
+    }
+
+    pub(crate) const fn is_authenticated(&self) -> bool {
+        matches!(
+            self,
+            Self::Gcm | Self::Ccm | Self::Ocb | Self::Eax | Self::Siv | Self::GcmSiv
+        )
+    }
+
+    pub(crate) const fn is_ciphertext_stealing(&self) -> bool {
+        matches!(self, Self::CbcCs1 | Self::CbcCs2 | Self::CbcCs3)
+    }
+
+    pub(crate) const fn is_segmented(&self) -> bool {
+        matches!(self, Self::GcmStream)
+    }
+
+    // Byte ranges can be decrypted without touching anything in front of them
+    pub(crate) const fn is_seekable(&self) -> bool {
+        matches!(self, Self::Ctr | Self::Xts | Self::GcmStream)
+    }
+
+    pub(crate) const fn is_sector_based(&self) -> bool {
+        matches!(self, Self::Xts)
+    }
+
+    pub(crate) const fn supports_cipher(&self, cipher: &EncryptionSchemes) -> bool {
+        let is_aes = matches!(
+            cipher,
+            EncryptionSchemes::Aes128 | EncryptionSchemes::Aes192 | EncryptionSchemes::Aes256
+        );
+
+        match self {
+            Self::Gcm | Self::GcmStream | Self::Ccm | Self::Ocb | Self::Siv => is_aes,
+            Self::GcmSiv | Self::Xts => matches!(
+                cipher,
+                EncryptionSchemes::Aes128 | EncryptionSchemes::Aes256
+            ),
+            _ => true,
+        }
+    }
+
+    // SIV keys are a MAC key followed by an encryption key of the cipher's size, XTS keys alike
+    pub(crate) const fn key_size(&self, cipher: &EncryptionSchemes) -> usize {
+        match self {
+            Self::Siv | Self::Xts => 2 * cipher.key_size(),
+            _ => cipher.key_size(),
+        }
+    }
+
+    pub(crate) const fn supports_key_size(
+        &self,
+        cipher: &EncryptionSchemes,
+        key_chunks: usize,
+    ) -> bool {
+        match self {
+            Self::Siv | Self::Xts => {
+                key_chunks.is_multiple_of(2) && cipher.supports_key_size(key_chunks / 2)
+            }
+            _ => cipher.supports_key_size(key_chunks),
+        }
+    }
+
+    pub(crate) const fn supports_nonce_and_tag(
+        &self,
+        cipher: &EncryptionSchemes,
+        nonce_len: usize,
+        tag_len: usize,
+    ) -> bool {
+        match self {
+            Self::Gcm | Self::GcmStream => nonce_len == GCM_NONCE_LEN && tag_len == GCM_TAG_LEN,
+            Self::Ccm => {
+                nonce_len >= CCM_MIN_NONCE_LEN
+                    && nonce_len <= CCM_MAX_NONCE_LEN
+                    && tag_len >= CCM_MIN_TAG_LEN
+                    && tag_len <= CCM_MAX_TAG_LEN
+                    && tag_len.is_multiple_of(2)
+            }
+            Self::Ocb => {
+                nonce_len >= OCB_MIN_NONCE_LEN
+                    && nonce_len <= OCB_MAX_NONCE_LEN
+                    && tag_len >= OCB_MIN_TAG_LEN
+                    && tag_len <= OCB_MAX_TAG_LEN
+            }
+            // EAX takes nonces of any length, but tags no longer than a cipher block
+            Self::Eax => {
+                nonce_len > 0
+                    && tag_len >= EAX_MIN_TAG_LEN
+                    && tag_len <= cipher.block_size() * BLOCK_CHUNK_SIZE
+            }
+            // An empty nonce selects deterministic encryption
+            Self::Siv => tag_len == SIV_TAG_LEN,
+            Self::GcmSiv => {
+                (nonce_len == 0 || nonce_len == GCM_SIV_NONCE_LEN) && tag_len == GCM_SIV_TAG_LEN
+            }
+            _ => true,
+        }
+    }
+
+    // The counter is the low end of the counter block, so it can be no longer than a block
+    pub(crate) const fn supports_counter_bits(
+        &self,
+        cipher: &EncryptionSchemes,
+        counter_bits: usize,
+    ) -> bool {
+        match self {
+            Self::Ctr => counter_bits <= cipher.block_size() * BLOCK_CHUNK_SIZE * u8::BITS as usize,
+            _ => true,
+        }
+    }
+
+    pub(crate) const fn iv_size(&self, block_size: usize) -> usize {
+        match self {
+            Self::Ecb | Self::Xts => 0,
+            _ => block_size,
+        }
+    }
+
+    // Stream modes produce exactly as many bytes as they consume
+    pub(crate) const fn preserves_length(&self) -> bool {
+        matches!(
+            self,
+            Self::Cfb
+                | Self::Cfb8
+                | Self::Ofb
+                | Self::Ctr
+                | Self::Xts
+                | Self::CbcCs1
+                | Self::CbcCs2
+                | Self::CbcCs3
+        )
+    }
+
+    pub(crate) fn generate_iv(&self, block_size: usize) -> Vec<DataChunk> {
+        let mut rng = rand::thread_rng();
+
+        (0..self.iv_size(block_size)).map(|_| rng.gen()).collect()
+    }
+
+    pub(crate) fn generate_nonce(&self, nonce_len: usize) -> Vec<u8> {
+        let mut rng = rand::thread_rng();
+
+        (0..nonce_len).map(|_| rng.gen()).collect()
+    }
+}
+
+fn check_iv_len(strategy: &dyn EncryptionStrategy, iv: &[DataChunk]) -> Result<()> {
+    match iv.len() == strategy.block_size() {
+        true => Ok(()),
+        false => Err(SymciphError::InvalidInput(
+            "🧩🙀: IV length does not match the cipher block size.".to_string(),
+        )),
+    }
+}
+
+// Modes only pass whole blocks they built themselves through these
+fn encrypt_block(strategy: &dyn EncryptionStrategy, block: &[u8]) -> Vec<u8> {
+    strategy
+        .encrypt(&to_chunks(block))
+        .expect("🙀🧨 Mode: Partial cipher block. This was not supposed to happen.")
+        .concat()
+}
+
+fn decrypt_block(strategy: &dyn EncryptionStrategy, block: &[u8]) -> Vec<u8> {
+    strategy
+        .decrypt(&to_chunks(block))
+        .expect("🙀🧨 Mode: Partial cipher block. This was not supposed to happen.")
+        .concat()
+}
+
+fn process_bytes(chunks: &[DataChunk], process: impl FnOnce(&mut [u8])) -> Vec<DataChunk> {
+    let mut bytes = chunks.concat();
+
+    process(&mut bytes);
+
+    to_chunks(&bytes)
+}
+
+pub(crate) fn to_chunks(bytes: &[u8]) -> Vec<DataChunk> {
+    bytes
+        .chunks(BLOCK_CHUNK_SIZE)
+        .map(|bytes_chunk| {
+            let mut chunk = [0u8; BLOCK_CHUNK_SIZE];
+            chunk[..bytes_chunk.len()].copy_from_slice(bytes_chunk);
+
+            chunk
+        })
+        .collect()
+}
+
+// Compares every byte regardless of where the first difference is
+fn constant_time_eq(left: &[u8], right: &[u8]) -> bool {
+    left.len() == right.len()
+        && left
+            .iter()
+            .zip(right)
+            .fold(0, |difference, (left_byte, right_byte)| {
+                difference | (left_byte ^ right_byte)
+            })
+            == 0
+}
+
+fn xor_bytes(left: &[u8], right: &[u8]) -> Vec<u8> {
+    left.iter()
+        .zip(right)
+        .map(|(left_byte, right_byte)| left_byte ^ right_byte)
+        .collect()
+}
+
+fn xor_chunks(left: &[DataChunk], right: &[DataChunk]) -> Vec<DataChunk> {
+    left.iter()
+        .zip(right)
+        .map(|(left_chunk, right_chunk)| {
+            let mut chunk = *left_chunk;
+
+            for (byte, right_byte) in chunk.iter_mut().zip(right_chunk) {
+                *byte ^= right_byte;
+            }
+
+            chunk
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // NIST SP 800-38A, Appendix F
+    pub const NIST_KEY_128_BITS: [DataChunk; 2] = [
+        [0x2B, 0x7E, 0x15, 0x16, 0x28, 0xAE, 0xD2, 0xA6],
+        [0xAB, 0xF7, 0x15, 0x88, 0x09, 0xCF, 0x4F, 0x3C],
+    ];
+
+    pub const NIST_IV: [DataChunk; 2] = [
+        [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07],
+        [0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E, 0x0F],
+    ];
+
+    pub const NIST_PLAINTEXT: [DataChunk; 8] = [
+        [0x6B, 0xC1, 0xBE, 0xE2, 0x2E, 0x40, 0x9F, 0x96],
+        [0xE9, 0x3D, 0x7E, 0x11, 0x73, 0x93, 0x17, 0x2A],
+        [0xAE, 0x2D, 0x8A, 0x57, 0x1E, 0x03, 0xAC, 0x9C],
+        [0x9E, 0xB7, 0x6F, 0xAC, 0x45, 0xAF, 0x8E, 0x51],
+        [0x30, 0xC8, 0x1C, 0x46, 0xA3, 0x5C, 0xE4, 0x11],
+        [0xE5, 0xFB, 0xC1, 0x19, 0x1A, 0x0A, 0x52, 0xEF],
+        [0xF6, 0x9F, 0x24, 0x45, 0xDF, 0x4F, 0x9B, 0x17],
+        [0xAD, 0x2B, 0x41, 0x7B, 0xE6, 0x6C, 0x37, 0x10],
+    ];
+
+    pub const KEY_64_BITS: [DataChunk; 1] = [[0x13, 0x34, 0x57, 0x79, 0x9B, 0xBC, 0xDF, 0xF1]];
+
+    pub const IV_64_BITS: [DataChunk; 1] = [[0xF0, 0xE1, 0xD2, 0xC3, 0xB4, 0xA5, 0x96, 0x87]];
+
+    const KEY_192_BITS: [DataChunk; 3] = [
+        [0x6F, 0x2B, 0x91, 0xE7, 0x4F, 0xD8, 0xA9, 0x5C],
+        [0x1A, 0x3B, 0x4C, 0x5D, 0x6E, 0x7F, 0x80, 0x91],
+        [0xA2, 0xB3, 0xC4, 0xD5, 0xE6, 0xF7, 0x08, 0x19],
+    ];
+
+    const NONCE_96_BITS: [u8; 12] = [
+        0xF0, 0xE1, 0xD2, 0xC3, 0xB4, 0xA5, 0x96, 0x87, 0x78, 0x69, 0x5A, 0x4B,
+    ];
+
+    fn helper_get_ocb_and_eax_modes() -> Vec<Box<dyn AuthenticatedMode>> {
+        [
+            (EncryptionModes::Ocb, EncryptionSchemes::Aes192, 16),
+            (EncryptionModes::Eax, EncryptionSchemes::Aes192, 16),
+            (EncryptionModes::Eax, EncryptionSchemes::Tdes, 8),
+        ]
+        .iter()
+        .map(|(mode, cipher, tag_len)| {
+            mode.authenticated_mode(cipher, &KEY_192_BITS, &NONCE_96_BITS, *tag_len)
+                .unwrap()
+        })
+        .collect()
+    }
+
+    #[test]
+    fn xor_bytes_stops_at_shorter() {
+        let left = xor_bytes(&[0x0F, 0xF0, 0xFF], &[0xFF, 0xFF]);
+        let right = [0xF0, 0x0F];
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn xor_chunks_with_itself() {
+        let left = xor_chunks(&NIST_PLAINTEXT, &NIST_PLAINTEXT);
+        let right = [[0u8; 8]; 8];
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn xor_chunks_shorter_right() {
+        let left = xor_chunks(&NIST_PLAINTEXT, &NIST_IV);
+        let right = [
+            [0x6B, 0xC0, 0xBC, 0xE1, 0x2A, 0x45, 0x99, 0x91],
+            [0xE1, 0x34, 0x74, 0x1A, 0x7F, 0x9E, 0x19, 0x25],
+        ];
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn to_chunks_zero_fills_last_chunk() {
+        let left = to_chunks(&[0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09]);
+        let right = [
+            [0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08],
+            [0x09, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+        ];
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn ecb_has_no_iv() {
+        let left = EncryptionModes::Ecb.generate_iv(2);
+
+        assert!(left.is_empty());
+    }
+
+    #[test]
+    fn chaining_modes_iv_matches_block_size() {
+        for mode in [
+            EncryptionModes::Cbc,
+            EncryptionModes::Pcbc,
+            EncryptionModes::Cfb,
+            EncryptionModes::Cfb8,
+            EncryptionModes::Ofb,
+            EncryptionModes::Ctr,
+        ] {
+            let left = mode.generate_iv(2).len();
+            let right = 2;
+
+            assert_eq!(left, right);
+        }
+    }
+
+    #[test]
+    fn only_stream_modes_preserve_length() {
+        assert!(!EncryptionModes::Ecb.preserves_length());
+        assert!(!EncryptionModes::Cbc.preserves_length());
+        assert!(!EncryptionModes::Pcbc.preserves_length());
+        assert!(EncryptionModes::Cfb.preserves_length());
+        assert!(EncryptionModes::Cfb8.preserves_length());
+        assert!(EncryptionModes::Ofb.preserves_length());
+        assert!(EncryptionModes::Ctr.preserves_length());
+        assert!(EncryptionModes::Xts.preserves_length());
+        assert!(EncryptionModes::CbcCs3.preserves_length());
+    }
+
+    #[test]
+    fn only_aead_modes_are_authenticated() {
+        assert!(!EncryptionModes::Ctr.is_authenticated());
+        assert!(EncryptionModes::Gcm.is_authenticated());
+        assert!(EncryptionModes::Ccm.is_authenticated());
+        assert!(EncryptionModes::Siv.is_authenticated());
+        assert!(EncryptionModes::GcmSiv.is_authenticated());
+        assert!(EncryptionModes::Ocb.is_authenticated());
+        assert!(EncryptionModes::Eax.is_authenticated());
+    }
+
+    #[test]
+    fn aead_modes_require_aes() {
+        assert!(!EncryptionModes::Gcm.supports_cipher(&EncryptionSchemes::Tdes));
+        assert!(EncryptionModes::Gcm.supports_cipher(&EncryptionSchemes::Aes192));
+        assert!(!EncryptionModes::Ccm.supports_cipher(&EncryptionSchemes::Des));
+        assert!(!EncryptionModes::Siv.supports_cipher(&EncryptionSchemes::Des));
+        assert!(EncryptionModes::Cbc.supports_cipher(&EncryptionSchemes::Des));
+    }
+
+    #[test]
+    fn tdes_keying_options_accepted() {
+        for key_chunks in 1..=3 {
+            assert!(EncryptionModes::Cbc.supports_key_size(&EncryptionSchemes::Tdes, key_chunks));
+        }
+
+        assert!(!EncryptionModes::Cbc.supports_key_size(&EncryptionSchemes::Tdes, 4));
+        assert!(!EncryptionModes::Cbc.supports_key_size(&EncryptionSchemes::TdesLegacyEee, 2));
+        assert!(!EncryptionModes::Cbc.supports_key_size(&EncryptionSchemes::Des, 3));
+        assert!(EncryptionModes::Xts.supports_key_size(&EncryptionSchemes::Aes128, 4));
+        assert!(!EncryptionModes::Xts.supports_key_size(&EncryptionSchemes::Aes128, 2));
+    }
+
+    #[test]
+    fn only_ctr_xts_and_stream_are_seekable() {
+        assert!(EncryptionModes::Ctr.is_seekable());
+        assert!(EncryptionModes::Xts.is_seekable());
+        assert!(EncryptionModes::GcmStream.is_seekable());
+        assert!(!EncryptionModes::Ofb.is_seekable());
+        assert!(!EncryptionModes::Gcm.is_seekable());
+        assert!(!EncryptionModes::CbcCs3.is_seekable());
+    }
+
+    #[test]
+    fn gcm_stream_is_segmented_aes() {
+        assert!(EncryptionModes::GcmStream.is_segmented());
+        assert!(!EncryptionModes::GcmStream.is_authenticated());
+        assert!(!EncryptionModes::Gcm.is_segmented());
+        assert!(EncryptionModes::GcmStream.supports_cipher(&EncryptionSchemes::Aes192));
+        assert!(!EncryptionModes::GcmStream.supports_cipher(&EncryptionSchemes::Tdes));
+        assert!(EncryptionModes::GcmStream.supports_nonce_and_tag(
+            &EncryptionSchemes::Aes128,
+            12,
+            16
+        ));
+        assert!(!EncryptionModes::GcmStream.supports_nonce_and_tag(
+            &EncryptionSchemes::Aes128,
+            12,
+            12
+        ));
+    }
+
+    #[test]
+    fn gcm_siv_has_no_aes192() {
+        assert!(EncryptionModes::GcmSiv.supports_cipher(&EncryptionSchemes::Aes128));
+        assert!(!EncryptionModes::GcmSiv.supports_cipher(&EncryptionSchemes::Aes192));
+        assert!(EncryptionModes::GcmSiv.supports_cipher(&EncryptionSchemes::Aes256));
+    }
+
+    #[test]
+    fn xts_is_aes128_or_aes256_with_double_key() {
+        assert!(EncryptionModes::Xts.supports_cipher(&EncryptionSchemes::Aes128));
+        assert!(!EncryptionModes::Xts.supports_cipher(&EncryptionSchemes::Aes192));
+        assert!(!EncryptionModes::Xts.supports_cipher(&EncryptionSchemes::Tdes));
+
+        let left = EncryptionModes::Xts.key_size(&EncryptionSchemes::Aes128);
+        let right = 4;
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn siv_key_is_twice_cipher_key() {
+        let left = EncryptionModes::Siv.key_size(&EncryptionSchemes::Aes256);
+        let right = 8;
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn siv_modes_allow_missing_nonce() {
+        assert!(EncryptionModes::Siv.supports_nonce_and_tag(&EncryptionSchemes::Aes128, 0, 16));
+        assert!(EncryptionModes::GcmSiv.supports_nonce_and_tag(&EncryptionSchemes::Aes128, 0, 16));
+        assert!(EncryptionModes::GcmSiv.supports_nonce_and_tag(&EncryptionSchemes::Aes128, 12, 16));
+        assert!(!EncryptionModes::GcmSiv.supports_nonce_and_tag(&EncryptionSchemes::Aes128, 8, 16));
+        assert!(!EncryptionModes::Siv.supports_nonce_and_tag(&EncryptionSchemes::Aes128, 0, 8));
+    }
+
+    #[test]
+    fn gcm_only_supports_96_bit_nonce_and_128_bit_tag() {
+        assert!(EncryptionModes::Gcm.supports_nonce_and_tag(&EncryptionSchemes::Aes128, 12, 16));
+        assert!(!EncryptionModes::Gcm.supports_nonce_and_tag(&EncryptionSchemes::Aes128, 13, 16));
+        assert!(!EncryptionModes::Gcm.supports_nonce_and_tag(&EncryptionSchemes::Aes128, 12, 8));
+    }
+
+    #[test]
+    fn ccm_nonce_and_tag_ranges() {
+        assert!(EncryptionModes::Ccm.supports_nonce_and_tag(&EncryptionSchemes::Aes128, 7, 4));
+        assert!(EncryptionModes::Ccm.supports_nonce_and_tag(&EncryptionSchemes::Aes128, 13, 16));
+        assert!(!EncryptionModes::Ccm.supports_nonce_and_tag(&EncryptionSchemes::Aes128, 6, 16));
+        assert!(!EncryptionModes::Ccm.supports_nonce_and_tag(&EncryptionSchemes::Aes128, 14, 16));
+        assert!(!EncryptionModes::Ccm.supports_nonce_and_tag(&EncryptionSchemes::Aes128, 12, 5));
+        assert!(!EncryptionModes::Ccm.supports_nonce_and_tag(&EncryptionSchemes::Aes128, 12, 2));
+    }
+
+    #[test]
+    fn ocb_requires_aes_and_eax_does_not() {
+        assert!(EncryptionModes::Ocb.supports_cipher(&EncryptionSchemes::Aes256));
+        assert!(!EncryptionModes::Ocb.supports_cipher(&EncryptionSchemes::Tdes));
+        assert!(EncryptionModes::Eax.supports_cipher(&EncryptionSchemes::Tdes));
+    }
+
+    #[test]
+    fn ocb_nonce_and_tag_ranges() {
+        let cipher = EncryptionSchemes::Aes128;
+
+        assert!(EncryptionModes::Ocb.supports_nonce_and_tag(&cipher, 1, 4));
+        assert!(EncryptionModes::Ocb.supports_nonce_and_tag(&cipher, 15, 12));
+        assert!(!EncryptionModes::Ocb.supports_nonce_and_tag(&cipher, 0, 16));
+        assert!(!EncryptionModes::Ocb.supports_nonce_and_tag(&cipher, 16, 16));
+        assert!(!EncryptionModes::Ocb.supports_nonce_and_tag(&cipher, 12, 3));
+    }
+
+    #[test]
+    fn eax_tag_bounded_by_cipher_block() {
+        assert!(EncryptionModes::Eax.supports_nonce_and_tag(&EncryptionSchemes::Aes128, 32, 16));
+        assert!(EncryptionModes::Eax.supports_nonce_and_tag(&EncryptionSchemes::Tdes, 12, 8));
+        assert!(!EncryptionModes::Eax.supports_nonce_and_tag(&EncryptionSchemes::Tdes, 12, 16));
+        assert!(!EncryptionModes::Eax.supports_nonce_and_tag(&EncryptionSchemes::Aes128, 0, 16));
+    }
+
+    #[test]
+    fn ctr_counter_bounded_by_cipher_block() {
+        assert!(EncryptionModes::Ctr.supports_counter_bits(&EncryptionSchemes::Aes128, 128));
+        assert!(EncryptionModes::Ctr.supports_counter_bits(&EncryptionSchemes::Des, 64));
+        assert!(!EncryptionModes::Ctr.supports_counter_bits(&EncryptionSchemes::Des, 96));
+        assert!(EncryptionModes::Cbc.supports_counter_bits(&EncryptionSchemes::Des, 96));
+    }
+
+    #[test]
+    fn generate_nonce_of_requested_length() {
+        let left = EncryptionModes::Ccm.generate_nonce(13).len();
+        let right = 13;
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn constant_time_eq_compares_all_bytes() {
+        assert!(constant_time_eq(&[0x01, 0x02], &[0x01, 0x02]));
+        assert!(!constant_time_eq(&[0x01, 0x02], &[0x01, 0x03]));
+        assert!(!constant_time_eq(&[0x01, 0x02], &[0x01]));
+    }
+
+    #[test]
+    fn ocb_and_eax_round_trip_uneven_message() {
+        let plaintext: Vec<u8> = (0..=255).cycle().take(1001).collect();
+
+        for mut mode in helper_get_ocb_and_eax_modes() {
+            let ciphertext = mode.seal(b"packet header", &plaintext);
+
+            let left = mode.open(b"packet header", &ciphertext);
+            let right = Some(plaintext.clone());
+
+            assert_eq!(left, right);
+        }
+    }
+
+    #[test]
+    fn ocb_and_eax_reject_every_flipped_bit() {
+        for mut mode in helper_get_ocb_and_eax_modes() {
+            let ciphertext = mode.seal(b"packet header", b"attack at dawn");
+
+            for position in 0..ciphertext.len() * 8 {
+                let mut tampered = ciphertext.clone();
+                tampered[position / 8] ^= 1 << (position % 8);
+
+                assert!(mode.open(b"packet header", &tampered).is_none());
+            }
+        }
+    }
+
+    #[test]
+    fn ocb_and_eax_reject_truncated_ciphertext() {
+        for mut mode in helper_get_ocb_and_eax_modes() {
+            let ciphertext = mode.seal(&[], b"attack at dawn");
+
+            assert!(mode
+                .open(&[], &ciphertext[..ciphertext.len() - 1])
+                .is_none());
+            assert!(mode.open(&[], &ciphertext[..3]).is_none());
+        }
+    }
+
+    // Round trip through a freshly constructed mode, used for DES which has no NIST vectors
+    pub fn helper_des_round_trip(mode: EncryptionModes) {
+        use crate::EncryptionSchemes;
+
+        let ciphertext = mode
+            .mode(
+                EncryptionSchemes::Des.strategy(&KEY_64_BITS).unwrap(),
+                &IV_64_BITS,
+                32,
+            )
+            .unwrap()
+            .encrypt(&NIST_PLAINTEXT)
+            .unwrap();
+
+        assert_ne!(ciphertext, NIST_PLAINTEXT);
+
+        let left = mode
+            .mode(
+                EncryptionSchemes::Des.strategy(&KEY_64_BITS).unwrap(),
+                &IV_64_BITS,
+                32,
+            )
+            .unwrap()
+            .decrypt(&ciphertext)
+            .unwrap();
+        let right = NIST_PLAINTEXT;
+
+        assert_eq!(left, right);
+    }
+}
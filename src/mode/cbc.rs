@@ -0,0 +1,148 @@
+use crate::{error::Result, strategy::EncryptionStrategy, DataChunk};
+
+use super::{check_iv_len, xor_chunks, EncryptionMode};
+
+pub(super) struct CbcMode {
+    strategy: Box<dyn EncryptionStrategy>,
+    feedback: Vec<DataChunk>,
+}
+
+impl CbcMode {
+    pub fn new(strategy: Box<dyn EncryptionStrategy>, iv: &[DataChunk]) -> Result<Self> {
+        check_iv_len(strategy.as_ref(), iv)?;
+
+        Ok(Self {
+            strategy,
+            feedback: iv.to_vec(),
+        })
+    }
+}
+
+impl EncryptionMode for CbcMode {
+    fn encrypt(&mut self, plaintext: &[DataChunk]) -> Result<Vec<DataChunk>> {
+        let mut ciphertext_blocks: Vec<DataChunk> = Vec::with_capacity(plaintext.len());
+
+        for plaintext_block in plaintext.chunks(self.strategy.block_size()) {
+            let ciphertext_block = self
+                .strategy
+                .encrypt(&xor_chunks(plaintext_block, &self.feedback))?;
+
+            ciphertext_blocks.extend_from_slice(&ciphertext_block);
+            self.feedback = ciphertext_block;
+        }
+
+        Ok(ciphertext_blocks)
+    }
+
+    fn decrypt(&mut self, ciphertext: &[DataChunk]) -> Result<Vec<DataChunk>> {
+        let mut plaintext_blocks: Vec<DataChunk> = Vec::with_capacity(ciphertext.len());
+
+        for ciphertext_block in ciphertext.chunks(self.strategy.block_size()) {
+            let plaintext_block =
+                xor_chunks(&self.strategy.decrypt(ciphertext_block)?, &self.feedback);
+
+            plaintext_blocks.extend_from_slice(&plaintext_block);
+            self.feedback = ciphertext_block.to_vec();
+        }
+
+        Ok(plaintext_blocks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{mode::tests::*, EncryptionModes, EncryptionSchemes, SymciphError};
+
+    use super::*;
+
+    const NIST_CBC_AES128_CIPHERTEXT: [DataChunk; 8] = [
+        [0x76, 0x49, 0xAB, 0xAC, 0x81, 0x19, 0xB2, 0x46],
+        [0xCE, 0xE9, 0x8E, 0x9B, 0x12, 0xE9, 0x19, 0x7D],
+        [0x50, 0x86, 0xCB, 0x9B, 0x50, 0x72, 0x19, 0xEE],
+        [0x95, 0xDB, 0x11, 0x3A, 0x91, 0x76, 0x78, 0xB2],
+        [0x73, 0xBE, 0xD6, 0xB8, 0xE3, 0xC1, 0x74, 0x3B],
+        [0x71, 0x16, 0xE6, 0x9E, 0x22, 0x22, 0x95, 0x16],
+        [0x3F, 0xF1, 0xCA, 0xA1, 0x68, 0x1F, 0xAC, 0x09],
+        [0x12, 0x0E, 0xCA, 0x30, 0x75, 0x86, 0xE1, 0xA7],
+    ];
+
+    fn helper_get_mode() -> CbcMode {
+        CbcMode::new(
+            EncryptionSchemes::Aes128
+                .strategy(&NIST_KEY_128_BITS)
+                .unwrap(),
+            &NIST_IV,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn encrypt() {
+        let left = helper_get_mode().encrypt(&NIST_PLAINTEXT).unwrap();
+        let right = NIST_CBC_AES128_CIPHERTEXT;
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn decrypt() {
+        let left = helper_get_mode()
+            .decrypt(&NIST_CBC_AES128_CIPHERTEXT)
+            .unwrap();
+        let right = NIST_PLAINTEXT;
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn encrypt_chains_across_calls() {
+        let mut mode = helper_get_mode();
+
+        let mut left = mode.encrypt(&NIST_PLAINTEXT[..2]).unwrap();
+        left.extend(mode.encrypt(&NIST_PLAINTEXT[2..]).unwrap());
+        let right = NIST_CBC_AES128_CIPHERTEXT;
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn decrypt_chains_across_calls() {
+        let mut mode = helper_get_mode();
+
+        let mut left = mode.decrypt(&NIST_CBC_AES128_CIPHERTEXT[..6]).unwrap();
+        left.extend(mode.decrypt(&NIST_CBC_AES128_CIPHERTEXT[6..]).unwrap());
+        let right = NIST_PLAINTEXT;
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn des_identical_blocks_differ() {
+        let mut mode = CbcMode::new(
+            EncryptionSchemes::Des.strategy(&KEY_64_BITS).unwrap(),
+            &IV_64_BITS,
+        )
+        .unwrap();
+
+        let ciphertext = mode.encrypt(&[NIST_PLAINTEXT[0]; 2]).unwrap();
+
+        assert_ne!(ciphertext[0], ciphertext[1]);
+    }
+
+    #[test]
+    fn des_encrypt_and_decrypt() {
+        helper_des_round_trip(EncryptionModes::Cbc);
+    }
+
+    #[test]
+    fn iv_length_mismatch() {
+        let left = CbcMode::new(
+            EncryptionSchemes::Aes128
+                .strategy(&NIST_KEY_128_BITS)
+                .unwrap(),
+            &NIST_IV[..1],
+        );
+
+        assert!(matches!(left, Err(SymciphError::InvalidInput(_))));
+    }
+}
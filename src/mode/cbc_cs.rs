@@ -0,0 +1,277 @@
+use crate::{error::Result, strategy::EncryptionStrategy, DataChunk, BLOCK_CHUNK_SIZE};
+
+use super::{check_iv_len, decrypt_block, encrypt_block, xor_bytes};
+
+// NIST SP 800-38A Addendum, the variants only differ in the order of the last two blocks
+#[derive(Clone, Copy)]
+pub(super) enum Stealing {
+    Cs1,
+    Cs2,
+    Cs3,
+}
+
+pub(crate) struct CbcCsMode {
+    strategy: Box<dyn EncryptionStrategy>,
+    iv: Vec<u8>,
+    stealing: Stealing,
+}
+
+impl CbcCsMode {
+    pub(super) fn new(
+        strategy: Box<dyn EncryptionStrategy>,
+        iv: &[DataChunk],
+        stealing: Stealing,
+    ) -> Result<Self> {
+        check_iv_len(strategy.as_ref(), iv)?;
+
+        Ok(Self {
+            strategy,
+            iv: iv.concat(),
+            stealing,
+        })
+    }
+
+    pub fn encrypt(&self, plaintext: &[u8]) -> Vec<u8> {
+        let block_len = self.block_len();
+        let last_len = self.last_block_len(plaintext.len());
+
+        let mut padded_plaintext = plaintext.to_vec();
+        padded_plaintext.resize(plaintext.len() + block_len - last_len, 0);
+
+        let mut ciphertext = Vec::with_capacity(padded_plaintext.len());
+        let mut feedback = self.iv.clone();
+
+        for plaintext_block in padded_plaintext.chunks(block_len) {
+            feedback = encrypt_block(
+                self.strategy.as_ref(),
+                &xor_bytes(plaintext_block, &feedback),
+            );
+            ciphertext.extend_from_slice(&feedback);
+        }
+
+        if ciphertext.len() > block_len {
+            let penultimate = ciphertext.len() - 2 * block_len;
+
+            // Only as many bytes of the penultimate block are kept as the last block had
+            ciphertext.drain(penultimate + last_len..penultimate + block_len);
+
+            if self.swaps_last_blocks(last_len) {
+                ciphertext[penultimate..].rotate_left(last_len);
+            }
+        }
+
+        ciphertext
+    }
+
+    pub fn decrypt(&self, ciphertext: &[u8]) -> Vec<u8> {
+        let block_len = self.block_len();
+        let last_len = self.last_block_len(ciphertext.len());
+
+        let mut full_ciphertext = ciphertext.to_vec();
+
+        if ciphertext.len() > block_len {
+            let penultimate = ciphertext.len() - block_len - last_len;
+
+            if self.swaps_last_blocks(last_len) {
+                full_ciphertext[penultimate..].rotate_right(last_len);
+            }
+
+            // The stolen bytes are what the last block decrypts to past the plaintext's end
+            let stolen = decrypt_block(
+                self.strategy.as_ref(),
+                &full_ciphertext[full_ciphertext.len() - block_len..],
+            );
+            full_ciphertext.splice(
+                penultimate + last_len..penultimate + last_len,
+                stolen[last_len..].iter().copied(),
+            );
+        }
+
+        let mut plaintext = Vec::with_capacity(full_ciphertext.len());
+        let mut feedback = self.iv.as_slice();
+
+        for ciphertext_block in full_ciphertext.chunks(block_len) {
+            plaintext.extend(xor_bytes(
+                &decrypt_block(self.strategy.as_ref(), ciphertext_block),
+                feedback,
+            ));
+            feedback = ciphertext_block;
+        }
+
+        plaintext.truncate(ciphertext.len());
+
+        plaintext
+    }
+
+    fn block_len(&self) -> usize {
+        self.strategy.block_size() * BLOCK_CHUNK_SIZE
+    }
+
+    fn last_block_len(&self, data_len: usize) -> usize {
+        let block_len = self.block_len();
+
+        assert!(
+            data_len >= block_len,
+            "CBC-CS: Input must be at least one block long"
+        );
+
+        (data_len - 1) % block_len + 1
+    }
+
+    fn swaps_last_blocks(&self, last_len: usize) -> bool {
+        match self.stealing {
+            Stealing::Cs1 => false,
+            Stealing::Cs2 => last_len < self.block_len(),
+            Stealing::Cs3 => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{mode::tests::*, EncryptionSchemes};
+
+    use super::*;
+
+    fn helper_get_mode(stealing: Stealing) -> CbcCsMode {
+        CbcCsMode::new(
+            EncryptionSchemes::Aes128
+                .strategy(&NIST_KEY_128_BITS)
+                .unwrap(),
+            &NIST_IV,
+            stealing,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn encrypt_cs1() {
+        let left = helper_get_mode(Stealing::Cs1).encrypt(&NIST_PLAINTEXT.concat()[..45]);
+        let right = [
+            0x76, 0x49, 0xAB, 0xAC, 0x81, 0x19, 0xB2, 0x46, 0xCE, 0xE9, 0x8E, 0x9B, 0x12, 0xE9,
+            0x19, 0x7D, 0x50, 0x86, 0xCB, 0x9B, 0x50, 0x72, 0x19, 0xEE, 0x95, 0xDB, 0x11, 0x3A,
+            0x91, 0x64, 0x92, 0xF2, 0x30, 0x8A, 0xA7, 0xB8, 0xCD, 0x5C, 0x32, 0xC4, 0xE9, 0x63,
+            0x02, 0xDA, 0x0E,
+        ];
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn encrypt_cs2() {
+        let left = helper_get_mode(Stealing::Cs2).encrypt(&NIST_PLAINTEXT.concat()[..45]);
+        let right = [
+            0x76, 0x49, 0xAB, 0xAC, 0x81, 0x19, 0xB2, 0x46, 0xCE, 0xE9, 0x8E, 0x9B, 0x12, 0xE9,
+            0x19, 0x7D, 0x64, 0x92, 0xF2, 0x30, 0x8A, 0xA7, 0xB8, 0xCD, 0x5C, 0x32, 0xC4, 0xE9,
+            0x63, 0x02, 0xDA, 0x0E, 0x50, 0x86, 0xCB, 0x9B, 0x50, 0x72, 0x19, 0xEE, 0x95, 0xDB,
+            0x11, 0x3A, 0x91,
+        ];
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn encrypt_cs2_full_blocks_is_cbc() {
+        let left = helper_get_mode(Stealing::Cs2).encrypt(&NIST_PLAINTEXT.concat()[..32]);
+        let right = [
+            0x76, 0x49, 0xAB, 0xAC, 0x81, 0x19, 0xB2, 0x46, 0xCE, 0xE9, 0x8E, 0x9B, 0x12, 0xE9,
+            0x19, 0x7D, 0x50, 0x86, 0xCB, 0x9B, 0x50, 0x72, 0x19, 0xEE, 0x95, 0xDB, 0x11, 0x3A,
+            0x91, 0x76, 0x78, 0xB2,
+        ];
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn encrypt_cs3_full_blocks_swapped() {
+        let left = helper_get_mode(Stealing::Cs3).encrypt(&NIST_PLAINTEXT.concat()[..32]);
+        let right = [
+            0x50, 0x86, 0xCB, 0x9B, 0x50, 0x72, 0x19, 0xEE, 0x95, 0xDB, 0x11, 0x3A, 0x91, 0x76,
+            0x78, 0xB2, 0x76, 0x49, 0xAB, 0xAC, 0x81, 0x19, 0xB2, 0x46, 0xCE, 0xE9, 0x8E, 0x9B,
+            0x12, 0xE9, 0x19, 0x7D,
+        ];
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn encrypt_single_block_is_cbc() {
+        for stealing in [Stealing::Cs1, Stealing::Cs2, Stealing::Cs3] {
+            let left = helper_get_mode(stealing).encrypt(&NIST_PLAINTEXT.concat()[..16]);
+            let right = [
+                0x76, 0x49, 0xAB, 0xAC, 0x81, 0x19, 0xB2, 0x46, 0xCE, 0xE9, 0x8E, 0x9B, 0x12, 0xE9,
+                0x19, 0x7D,
+            ];
+
+            assert_eq!(left, right);
+        }
+    }
+
+    // RFC 3962, Appendix B, Kerberos' CTS is CBC-CS3 with a zero IV
+    #[test]
+    fn encrypt_cs3_rfc_3962() {
+        let mode = CbcCsMode::new(
+            EncryptionSchemes::Aes128
+                .strategy(&[
+                    [0x63, 0x68, 0x69, 0x63, 0x6B, 0x65, 0x6E, 0x20],
+                    [0x74, 0x65, 0x72, 0x69, 0x79, 0x61, 0x6B, 0x69],
+                ])
+                .unwrap(),
+            &[[0; BLOCK_CHUNK_SIZE]; 2],
+            Stealing::Cs3,
+        )
+        .unwrap();
+
+        let left = mode.encrypt(b"I would like the General Gau's ");
+        let right = [
+            0xFC, 0x00, 0x78, 0x3E, 0x0E, 0xFD, 0xB2, 0xC1, 0xD4, 0x45, 0xD4, 0xC8, 0xEF, 0xF7,
+            0xED, 0x22, 0x97, 0x68, 0x72, 0x68, 0xD6, 0xEC, 0xCC, 0xC0, 0xC0, 0x7B, 0x25, 0xE2,
+            0x5E, 0xCF, 0xE5,
+        ];
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn decrypt_all_lengths() {
+        for stealing in [Stealing::Cs1, Stealing::Cs2, Stealing::Cs3] {
+            let mode = helper_get_mode(stealing);
+
+            for len in 16..=64 {
+                let left = mode.decrypt(&mode.encrypt(&NIST_PLAINTEXT.concat()[..len]));
+                let right = &NIST_PLAINTEXT.concat()[..len];
+
+                assert_eq!(left, right);
+            }
+        }
+    }
+
+    #[test]
+    fn des_all_lengths() {
+        for stealing in [Stealing::Cs1, Stealing::Cs2, Stealing::Cs3] {
+            let mode = CbcCsMode::new(
+                EncryptionSchemes::Des.strategy(&KEY_64_BITS).unwrap(),
+                &IV_64_BITS,
+                stealing,
+            )
+            .unwrap();
+
+            for len in 8..=64 {
+                let ciphertext = mode.encrypt(&NIST_PLAINTEXT.concat()[..len]);
+
+                assert_eq!(ciphertext.len(), len);
+
+                let left = mode.decrypt(&ciphertext);
+                let right = &NIST_PLAINTEXT.concat()[..len];
+
+                assert_eq!(left, right);
+            }
+        }
+    }
+
+    #[should_panic]
+    #[test]
+    fn shorter_than_block() {
+        helper_get_mode(Stealing::Cs1).encrypt(&NIST_PLAINTEXT.concat()[..15]);
+    }
+}
@@ -0,0 +1,509 @@
+use crate::strategy::{EncryptionStrategy, AES_BLOCK_CHUNKS};
+
+use super::{constant_time_eq, encrypt_block, to_chunks, AuthenticatedMode, CtrMode};
+
+pub const CCM_MIN_NONCE_LEN: usize = 7;
+pub const CCM_MAX_NONCE_LEN: usize = 13;
+pub const CCM_MIN_TAG_LEN: usize = 4;
+pub const CCM_MAX_TAG_LEN: usize = 16;
+
+const CCM_BLOCK_LEN: usize = 16;
+
+// The payload length has to fit the length field, which takes what the nonce leaves of the block
+pub(crate) fn ccm_max_payload_len(nonce_len: usize) -> Option<u128> {
+    (CCM_MIN_NONCE_LEN..=CCM_MAX_NONCE_LEN)
+        .contains(&nonce_len)
+        .then(|| (1 << ((CCM_BLOCK_LEN - 1 - nonce_len) * u8::BITS as usize)) - 1)
+}
+
+pub(crate) struct CcmMode {
+    ctr: CtrMode,
+    nonce: Vec<u8>,
+    tag_len: usize,
+}
+
+impl CcmMode {
+    pub fn new(strategy: Box<dyn EncryptionStrategy>, nonce: &[u8], tag_len: usize) -> Self {
+        assert!(
+            strategy.block_size() == AES_BLOCK_CHUNKS,
+            "CCM: Cipher block size must be 128 bits"
+        );
+
+        assert!(
+            (CCM_MIN_NONCE_LEN..=CCM_MAX_NONCE_LEN).contains(&nonce.len()),
+            "CCM: Nonce must be between 7 and 13 bytes long"
+        );
+
+        assert!(
+            (CCM_MIN_TAG_LEN..=CCM_MAX_TAG_LEN).contains(&tag_len) && tag_len.is_multiple_of(2),
+            "CCM: Tag must be an even number of bytes between 4 and 16"
+        );
+
+        // A(i) = flags || nonce || i, with the counter taking up the remaining L bytes
+        let length_field_len = CCM_BLOCK_LEN - 1 - nonce.len();
+        let mut counter_block = vec![(length_field_len - 1) as u8];
+        counter_block.extend_from_slice(nonce);
+        counter_block.resize(CCM_BLOCK_LEN, 0);
+
+        Self {
+            // With the nonce length asserted, the counter takes 2 to 8 bytes of the block
+            ctr: CtrMode::new(
+                strategy,
+                &to_chunks(&counter_block),
+                length_field_len * u8::BITS as usize,
+            )
+            .expect("🙀🧨 CCM: Counter layout rejected. This was not supposed to happen."),
+            nonce: nonce.to_vec(),
+            tag_len,
+        }
+    }
+
+    fn length_field_len(&self) -> usize {
+        CCM_BLOCK_LEN - 1 - self.nonce.len()
+    }
+
+    // B(0) || encoded AAD || payload, each part zero padded to a whole block
+    fn format_input(&self, aad: &[u8], payload: &[u8]) -> Vec<u8> {
+        let length_field_len = self.length_field_len();
+
+        // check_message_len refuses longer payloads before they get here
+        assert!(
+            ccm_max_payload_len(self.nonce.len())
+                .is_some_and(|max_len| payload.len() as u128 <= max_len),
+            "🙀🧨 CCM: Payload too long for the nonce length. This was not supposed to happen."
+        );
+
+        let aad_flag = match aad.is_empty() {
+            true => 0,
+            false => 0x40,
+        };
+        let tag_flag = ((self.tag_len - 2) / 2) << 3;
+
+        let mut formatted = vec![aad_flag | tag_flag as u8 | (length_field_len - 1) as u8];
+        formatted.extend_from_slice(&self.nonce);
+        formatted.extend_from_slice(
+            &(payload.len() as u64).to_be_bytes()[size_of::<u64>() - length_field_len..],
+        );
+
+        if !aad.is_empty() {
+            match aad.len() {
+                len if len < 0xFF00 => formatted.extend_from_slice(&(len as u16).to_be_bytes()),
+                len if len <= u32::MAX as usize => {
+                    formatted.extend_from_slice(&[0xFF, 0xFE]);
+                    formatted.extend_from_slice(&(len as u32).to_be_bytes());
+                }
+                len => {
+                    formatted.extend_from_slice(&[0xFF, 0xFF]);
+                    formatted.extend_from_slice(&(len as u64).to_be_bytes());
+                }
+            }
+
+            formatted.extend_from_slice(aad);
+            pad_to_block(&mut formatted);
+        }
+
+        formatted.extend_from_slice(payload);
+        pad_to_block(&mut formatted);
+
+        formatted
+    }
+
+    fn tag(&mut self, aad: &[u8], plaintext: &[u8]) -> Vec<u8> {
+        let mut mac = [0u8; CCM_BLOCK_LEN].to_vec();
+
+        for block in self.format_input(aad, plaintext).chunks(CCM_BLOCK_LEN) {
+            for (mac_byte, byte) in mac.iter_mut().zip(block) {
+                *mac_byte ^= byte;
+            }
+
+            mac = encrypt_block(self.ctr.strategy(), &mac);
+        }
+
+        mac.truncate(self.tag_len);
+
+        self.ctr.seek(0);
+        self.ctr
+            .apply_keystream(&mut mac)
+            .expect("🙀🧨 CCM: Counter space exhausted. This was not supposed to happen.");
+
+        mac
+    }
+
+    fn apply_keystream(&mut self, data: &mut [u8]) {
+        self.ctr.seek(CCM_BLOCK_LEN as u64);
+        // The payload length is checked against the length field, which is as wide as the counter
+        self.ctr
+            .apply_keystream(data)
+            .expect("🙀🧨 CCM: Counter space exhausted. This was not supposed to happen.");
+    }
+}
+
+impl AuthenticatedMode for CcmMode {
+    fn seal(&mut self, aad: &[u8], plaintext: &[u8]) -> Vec<u8> {
+        let tag = self.tag(aad, plaintext);
+        let mut ciphertext = plaintext.to_vec();
+
+        self.apply_keystream(&mut ciphertext);
+        ciphertext.extend_from_slice(&tag);
+
+        ciphertext
+    }
+
+    fn open(&mut self, aad: &[u8], ciphertext: &[u8]) -> Option<Vec<u8>> {
+        let tag_position = ciphertext.len().checked_sub(self.tag_len)?;
+        let (ciphertext, tag) = ciphertext.split_at(tag_position);
+
+        // CCM authenticates the plaintext, so it has to be decrypted before it can be checked
+        let mut plaintext = ciphertext.to_vec();
+        self.apply_keystream(&mut plaintext);
+
+        match constant_time_eq(&self.tag(aad, &plaintext), tag) {
+            true => Some(plaintext),
+            false => None,
+        }
+    }
+}
+
+fn pad_to_block(data: &mut Vec<u8>) {
+    data.resize(data.len().next_multiple_of(CCM_BLOCK_LEN), 0);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{mode::tests::*, DataChunk, EncryptionSchemes};
+
+    use super::*;
+
+    // RFC 3610, packet vectors 1 to 3, the first 8 bytes of each packet are the AAD and are
+    // left out of the ciphertexts
+    const RFC_KEY_128_BITS: [DataChunk; 2] = [
+        [0xC0, 0xC1, 0xC2, 0xC3, 0xC4, 0xC5, 0xC6, 0xC7],
+        [0xC8, 0xC9, 0xCA, 0xCB, 0xCC, 0xCD, 0xCE, 0xCF],
+    ];
+
+    const RFC_PACKET: [u8; 33] = [
+        0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E,
+        0x0F, 0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18, 0x19, 0x1A, 0x1B, 0x1C, 0x1D,
+        0x1E, 0x1F, 0x20,
+    ];
+
+    const RFC_PACKET_1_NONCE: [u8; 13] = [
+        0x00, 0x00, 0x00, 0x03, 0x02, 0x01, 0x00, 0xA0, 0xA1, 0xA2, 0xA3, 0xA4, 0xA5,
+    ];
+
+    const RFC_PACKET_1_CIPHERTEXT: [u8; 31] = [
+        0x58, 0x8C, 0x97, 0x9A, 0x61, 0xC6, 0x63, 0xD2, 0xF0, 0x66, 0xD0, 0xC2, 0xC0, 0xF9, 0x89,
+        0x80, 0x6D, 0x5F, 0x6B, 0x61, 0xDA, 0xC3, 0x84, 0x17, 0xE8, 0xD1, 0x2C, 0xFD, 0xF9, 0x26,
+        0xE0,
+    ];
+
+    const RFC_PACKET_2_NONCE: [u8; 13] = [
+        0x00, 0x00, 0x00, 0x04, 0x03, 0x02, 0x01, 0xA0, 0xA1, 0xA2, 0xA3, 0xA4, 0xA5,
+    ];
+
+    const RFC_PACKET_2_CIPHERTEXT: [u8; 32] = [
+        0x72, 0xC9, 0x1A, 0x36, 0xE1, 0x35, 0xF8, 0xCF, 0x29, 0x1C, 0xA8, 0x94, 0x08, 0x5C, 0x87,
+        0xE3, 0xCC, 0x15, 0xC4, 0x39, 0xC9, 0xE4, 0x3A, 0x3B, 0xA0, 0x91, 0xD5, 0x6E, 0x10, 0x40,
+        0x09, 0x16,
+    ];
+
+    const RFC_PACKET_3_NONCE: [u8; 13] = [
+        0x00, 0x00, 0x00, 0x05, 0x04, 0x03, 0x02, 0xA0, 0xA1, 0xA2, 0xA3, 0xA4, 0xA5,
+    ];
+
+    const RFC_PACKET_3_CIPHERTEXT: [u8; 33] = [
+        0x51, 0xB1, 0xE5, 0xF4, 0x4A, 0x19, 0x7D, 0x1D, 0xA4, 0x6B, 0x0F, 0x8E, 0x2D, 0x28, 0x2A,
+        0xE8, 0x71, 0xE8, 0x38, 0xBB, 0x64, 0xDA, 0x85, 0x96, 0x57, 0x4A, 0xDA, 0xA7, 0x6F, 0xBD,
+        0x9F, 0xB0, 0xC5,
+    ];
+
+    // NIST SP 800-38C, Appendix C, the AES-192 and AES-256 keys extend the same byte sequence
+    const NIST_CCM_KEY_256_BITS: [DataChunk; 4] = [
+        [0x40, 0x41, 0x42, 0x43, 0x44, 0x45, 0x46, 0x47],
+        [0x48, 0x49, 0x4A, 0x4B, 0x4C, 0x4D, 0x4E, 0x4F],
+        [0x50, 0x51, 0x52, 0x53, 0x54, 0x55, 0x56, 0x57],
+        [0x58, 0x59, 0x5A, 0x5B, 0x5C, 0x5D, 0x5E, 0x5F],
+    ];
+
+    const NIST_CCM_NONCE: [u8; 13] = [
+        0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18, 0x19, 0x1A, 0x1B, 0x1C,
+    ];
+
+    const NIST_CCM_AAD: [u8; 20] = [
+        0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E,
+        0x0F, 0x10, 0x11, 0x12, 0x13,
+    ];
+
+    const NIST_CCM_PLAINTEXT: [u8; 24] = [
+        0x20, 0x21, 0x22, 0x23, 0x24, 0x25, 0x26, 0x27, 0x28, 0x29, 0x2A, 0x2B, 0x2C, 0x2D, 0x2E,
+        0x2F, 0x30, 0x31, 0x32, 0x33, 0x34, 0x35, 0x36, 0x37,
+    ];
+
+    fn helper_get_mode(nonce: &[u8]) -> CcmMode {
+        CcmMode::new(
+            EncryptionSchemes::Aes128
+                .strategy(&RFC_KEY_128_BITS)
+                .unwrap(),
+            nonce,
+            8,
+        )
+    }
+
+    fn helper_seal(nonce: &[u8], packet: &[u8]) -> Vec<u8> {
+        let (aad, plaintext) = packet.split_at(8);
+
+        helper_get_mode(nonce).seal(aad, plaintext)
+    }
+
+    #[test]
+    fn seal_rfc_packet_1() {
+        let left = helper_seal(&RFC_PACKET_1_NONCE, &RFC_PACKET[..31]);
+        let right = RFC_PACKET_1_CIPHERTEXT;
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn seal_rfc_packet_2() {
+        let left = helper_seal(&RFC_PACKET_2_NONCE, &RFC_PACKET[..32]);
+        let right = RFC_PACKET_2_CIPHERTEXT;
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn seal_rfc_packet_3() {
+        let left = helper_seal(&RFC_PACKET_3_NONCE, &RFC_PACKET);
+        let right = RFC_PACKET_3_CIPHERTEXT;
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn open_rfc_packet_3() {
+        let left =
+            helper_get_mode(&RFC_PACKET_3_NONCE).open(&RFC_PACKET[..8], &RFC_PACKET_3_CIPHERTEXT);
+        let right = Some(RFC_PACKET[8..].to_vec());
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn open_tampered_ciphertext() {
+        let mut ciphertext = RFC_PACKET_3_CIPHERTEXT;
+        ciphertext[4] ^= 0x01;
+
+        let left = helper_get_mode(&RFC_PACKET_3_NONCE).open(&RFC_PACKET[..8], &ciphertext);
+
+        assert!(left.is_none());
+    }
+
+    #[test]
+    fn open_wrong_aad() {
+        let left =
+            helper_get_mode(&RFC_PACKET_3_NONCE).open(&RFC_PACKET[1..8], &RFC_PACKET_3_CIPHERTEXT);
+
+        assert!(left.is_none());
+    }
+
+    // Example 1: 7-byte nonce, 4-byte tag
+    #[test]
+    fn seal_shortest_nonce_and_tag() {
+        let mut mode = CcmMode::new(
+            EncryptionSchemes::Aes128
+                .strategy(&NIST_CCM_KEY_256_BITS[..2])
+                .unwrap(),
+            &NIST_CCM_NONCE[..7],
+            4,
+        );
+
+        let left = mode.seal(&NIST_CCM_AAD[..8], &NIST_CCM_PLAINTEXT[..4]);
+        let right = [0x71, 0x62, 0x01, 0x5B, 0x4D, 0xAC, 0x25, 0x5D];
+
+        assert_eq!(left, right);
+    }
+
+    // Example 2: 8-byte nonce, 6-byte tag
+    #[test]
+    fn seal_6_byte_tag() {
+        let mut mode = CcmMode::new(
+            EncryptionSchemes::Aes128
+                .strategy(&NIST_CCM_KEY_256_BITS[..2])
+                .unwrap(),
+            &NIST_CCM_NONCE[..8],
+            6,
+        );
+
+        let left = mode.seal(&NIST_CCM_AAD[..16], &NIST_CCM_PLAINTEXT[..16]);
+        let right = [
+            0xD2, 0xA1, 0xF0, 0xE0, 0x51, 0xEA, 0x5F, 0x62, 0x08, 0x1A, 0x77, 0x92, 0x07, 0x3D,
+            0x59, 0x3D, 0x1F, 0xC6, 0x4F, 0xBF, 0xAC, 0xCD,
+        ];
+
+        assert_eq!(left, right);
+    }
+
+    // Example 3: 12-byte nonce, 8-byte tag
+    #[test]
+    fn seal_8_byte_tag() {
+        let mut mode = CcmMode::new(
+            EncryptionSchemes::Aes128
+                .strategy(&NIST_CCM_KEY_256_BITS[..2])
+                .unwrap(),
+            &NIST_CCM_NONCE[..12],
+            8,
+        );
+
+        let left = mode.seal(&NIST_CCM_AAD, &NIST_CCM_PLAINTEXT);
+        let right = [
+            0xE3, 0xB2, 0x01, 0xA9, 0xF5, 0xB7, 0x1A, 0x7A, 0x9B, 0x1C, 0xEA, 0xEC, 0xCD, 0x97,
+            0xE7, 0x0B, 0x61, 0x76, 0xAA, 0xD9, 0xA4, 0x42, 0x8A, 0xA5, 0x48, 0x43, 0x92, 0xFB,
+            0xC1, 0xB0, 0x99, 0x51,
+        ];
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn seal_aes192() {
+        let mut mode = CcmMode::new(
+            EncryptionSchemes::Aes192
+                .strategy(&NIST_CCM_KEY_256_BITS[..3])
+                .unwrap(),
+            &NIST_CCM_NONCE[..12],
+            16,
+        );
+
+        let left = mode.seal(&NIST_CCM_AAD, &NIST_CCM_PLAINTEXT);
+        let right = [
+            0x80, 0x81, 0x31, 0x6F, 0xD8, 0x96, 0x24, 0xD6, 0x2C, 0xE7, 0x63, 0x7F, 0xB9, 0x49,
+            0x95, 0xB6, 0x63, 0x1C, 0x50, 0xD6, 0x15, 0x86, 0xDE, 0x01, 0x0E, 0xC1, 0x05, 0x72,
+            0xFE, 0xCD, 0x75, 0xE2, 0x3A, 0xC3, 0x6B, 0x5D, 0xD3, 0x35, 0xEA, 0xCA,
+        ];
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn seal_aes256() {
+        let mut mode = CcmMode::new(
+            EncryptionSchemes::Aes256
+                .strategy(&NIST_CCM_KEY_256_BITS)
+                .unwrap(),
+            &NIST_CCM_NONCE[..12],
+            16,
+        );
+
+        let left = mode.seal(&NIST_CCM_AAD, &NIST_CCM_PLAINTEXT);
+        let right = [
+            0x04, 0xF8, 0x83, 0xAE, 0xB3, 0xBD, 0x07, 0x30, 0xEA, 0xF5, 0x0B, 0xB6, 0xDE, 0x4F,
+            0xA2, 0x21, 0x20, 0x34, 0xE4, 0xE4, 0x1B, 0x0E, 0x75, 0xE5, 0x9B, 0xBA, 0x3F, 0x3A,
+            0x10, 0x7F, 0x32, 0x39, 0xBD, 0x63, 0x90, 0x29, 0x23, 0xF8, 0x03, 0x71,
+        ];
+
+        assert_eq!(left, right);
+    }
+
+    // AAD of 0xFF00 bytes or more switches to the 6-byte length encoding
+    #[test]
+    fn seal_long_aad() {
+        let mut mode = CcmMode::new(
+            EncryptionSchemes::Aes128
+                .strategy(&NIST_CCM_KEY_256_BITS[..2])
+                .unwrap(),
+            &NIST_CCM_NONCE,
+            16,
+        );
+
+        let left = mode.seal(&[0xAB; 0xFF00], &[]);
+        let right = [
+            0x68, 0x5C, 0xA4, 0xBB, 0x02, 0x89, 0x1F, 0x79, 0xF7, 0xFC, 0xDA, 0x3F, 0x32, 0xF1,
+            0xCF, 0x49,
+        ];
+
+        assert_eq!(left, right);
+    }
+
+    #[should_panic]
+    #[test]
+    fn payload_too_long_for_nonce() {
+        let mut mode = CcmMode::new(
+            EncryptionSchemes::Aes128
+                .strategy(&NIST_CCM_KEY_256_BITS[..2])
+                .unwrap(),
+            &NIST_CCM_NONCE,
+            16,
+        );
+
+        mode.seal(&[], &[0u8; 0x10000]);
+    }
+
+    #[should_panic]
+    #[test]
+    fn odd_tag_length() {
+        CcmMode::new(
+            EncryptionSchemes::Aes128
+                .strategy(&RFC_KEY_128_BITS)
+                .unwrap(),
+            &RFC_PACKET_1_NONCE,
+            5,
+        );
+    }
+
+    #[should_panic]
+    #[test]
+    fn nonce_too_short() {
+        helper_get_mode(&RFC_PACKET_1_NONCE[..6]);
+    }
+
+    #[should_panic]
+    #[test]
+    fn des_not_supported() {
+        CcmMode::new(
+            EncryptionSchemes::Des.strategy(&KEY_64_BITS).unwrap(),
+            &RFC_PACKET_1_NONCE,
+            8,
+        );
+    }
+
+    #[test]
+    fn every_nonce_and_tag_length_round_trips() {
+        let plaintext = b"sensor reading: 21.5C";
+
+        for nonce_len in CCM_MIN_NONCE_LEN..=CCM_MAX_NONCE_LEN {
+            for tag_len in (CCM_MIN_TAG_LEN..=CCM_MAX_TAG_LEN).step_by(2) {
+                let mut mode = CcmMode::new(
+                    EncryptionSchemes::Aes192
+                        .strategy(&NIST_CCM_KEY_256_BITS[..3])
+                        .unwrap(),
+                    &NIST_CCM_NONCE[..nonce_len],
+                    tag_len,
+                );
+                let ciphertext = mode.seal(b"device-7", plaintext);
+
+                assert_eq!(ciphertext.len(), plaintext.len() + tag_len);
+
+                let left = mode.open(b"device-7", &ciphertext);
+                let right = Some(plaintext.to_vec());
+
+                assert_eq!(left, right);
+            }
+        }
+    }
+
+    #[test]
+    fn open_truncated_tag() {
+        let ciphertext = helper_seal(&RFC_PACKET_3_NONCE, &RFC_PACKET);
+        let mut mode = CcmMode::new(
+            EncryptionSchemes::Aes128
+                .strategy(&RFC_KEY_128_BITS)
+                .unwrap(),
+            &RFC_PACKET_3_NONCE,
+            4,
+        );
+
+        let left = mode.open(&RFC_PACKET[..8], &ciphertext[..ciphertext.len() - 4]);
+
+        assert!(left.is_none());
+    }
+}
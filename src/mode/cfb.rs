@@ -0,0 +1,116 @@
+use crate::{error::Result, strategy::EncryptionStrategy, DataChunk};
+
+use super::{check_iv_len, encrypt_block, process_bytes, EncryptionMode};
+
+pub(super) struct CfbMode {
+    strategy: Box<dyn EncryptionStrategy>,
+    keystream: Vec<u8>,
+    feedback: Vec<u8>,
+    position: usize,
+}
+
+impl CfbMode {
+    pub fn new(strategy: Box<dyn EncryptionStrategy>, iv: &[DataChunk]) -> Result<Self> {
+        check_iv_len(strategy.as_ref(), iv)?;
+
+        let feedback = iv.concat();
+
+        Ok(Self {
+            strategy,
+            keystream: Vec::new(),
+            position: feedback.len(),
+            feedback,
+        })
+    }
+
+    fn process(&mut self, data: &mut [u8], decrypt: bool) {
+        for byte in data.iter_mut() {
+            if self.position == self.feedback.len() {
+                self.keystream = encrypt_block(self.strategy.as_ref(), &self.feedback);
+                self.position = 0;
+            }
+
+            let input = *byte;
+            *byte ^= self.keystream[self.position];
+
+            // The next block is keyed off the ciphertext regardless of direction
+            self.feedback[self.position] = if decrypt { input } else { *byte };
+            self.position += 1;
+        }
+    }
+}
+
+impl EncryptionMode for CfbMode {
+    fn encrypt(&mut self, plaintext: &[DataChunk]) -> Result<Vec<DataChunk>> {
+        Ok(process_bytes(plaintext, |bytes| self.process(bytes, false)))
+    }
+
+    fn decrypt(&mut self, ciphertext: &[DataChunk]) -> Result<Vec<DataChunk>> {
+        Ok(process_bytes(ciphertext, |bytes| self.process(bytes, true)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{mode::tests::*, EncryptionModes, EncryptionSchemes};
+
+    use super::*;
+
+    const NIST_CFB_AES128_CIPHERTEXT: [DataChunk; 8] = [
+        [0x3B, 0x3F, 0xD9, 0x2E, 0xB7, 0x2D, 0xAD, 0x20],
+        [0x33, 0x34, 0x49, 0xF8, 0xE8, 0x3C, 0xFB, 0x4A],
+        [0xC8, 0xA6, 0x45, 0x37, 0xA0, 0xB3, 0xA9, 0x3F],
+        [0xCD, 0xE3, 0xCD, 0xAD, 0x9F, 0x1C, 0xE5, 0x8B],
+        [0x26, 0x75, 0x1F, 0x67, 0xA3, 0xCB, 0xB1, 0x40],
+        [0xB1, 0x80, 0x8C, 0xF1, 0x87, 0xA4, 0xF4, 0xDF],
+        [0xC0, 0x4B, 0x05, 0x35, 0x7C, 0x5D, 0x1C, 0x0E],
+        [0xEA, 0xC4, 0xC6, 0x6F, 0x9F, 0xF7, 0xF2, 0xE6],
+    ];
+
+    fn helper_get_mode() -> CfbMode {
+        CfbMode::new(
+            EncryptionSchemes::Aes128
+                .strategy(&NIST_KEY_128_BITS)
+                .unwrap(),
+            &NIST_IV,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn encrypt() {
+        let left = helper_get_mode().encrypt(&NIST_PLAINTEXT).unwrap();
+        let right = NIST_CFB_AES128_CIPHERTEXT;
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn decrypt() {
+        let left = helper_get_mode()
+            .decrypt(&NIST_CFB_AES128_CIPHERTEXT)
+            .unwrap();
+        let right = NIST_PLAINTEXT;
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn encrypt_odd_length_across_calls() {
+        let mut mode = helper_get_mode();
+        let mut data = NIST_PLAINTEXT.concat();
+
+        mode.process(&mut data[..7], false);
+        mode.process(&mut data[7..29], false);
+
+        let left = &data[..29];
+        let right = &NIST_CFB_AES128_CIPHERTEXT.concat()[..29];
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn des_encrypt_and_decrypt() {
+        helper_des_round_trip(EncryptionModes::Cfb);
+    }
+}
@@ -0,0 +1,106 @@
+use crate::{error::Result, strategy::EncryptionStrategy, DataChunk};
+
+use super::{check_iv_len, encrypt_block, process_bytes, EncryptionMode};
+
+pub(super) struct Cfb8Mode {
+    strategy: Box<dyn EncryptionStrategy>,
+    register: Vec<u8>,
+}
+
+impl Cfb8Mode {
+    pub fn new(strategy: Box<dyn EncryptionStrategy>, iv: &[DataChunk]) -> Result<Self> {
+        check_iv_len(strategy.as_ref(), iv)?;
+
+        Ok(Self {
+            strategy,
+            register: iv.concat(),
+        })
+    }
+
+    fn process(&mut self, data: &mut [u8], decrypt: bool) {
+        for byte in data.iter_mut() {
+            let input = *byte;
+            *byte ^= encrypt_block(self.strategy.as_ref(), &self.register)[0];
+
+            // Shift the register left by one byte and feed the ciphertext byte in
+            self.register.rotate_left(1);
+            *self.register.last_mut().unwrap() = if decrypt { input } else { *byte };
+        }
+    }
+}
+
+impl EncryptionMode for Cfb8Mode {
+    fn encrypt(&mut self, plaintext: &[DataChunk]) -> Result<Vec<DataChunk>> {
+        Ok(process_bytes(plaintext, |bytes| self.process(bytes, false)))
+    }
+
+    fn decrypt(&mut self, ciphertext: &[DataChunk]) -> Result<Vec<DataChunk>> {
+        Ok(process_bytes(ciphertext, |bytes| self.process(bytes, true)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{mode::tests::*, EncryptionModes, EncryptionSchemes};
+
+    use super::*;
+
+    const CFB8_AES128_CIPHERTEXT: [DataChunk; 8] = [
+        [0x3B, 0x79, 0x42, 0x4C, 0x9C, 0x0D, 0xD4, 0x36],
+        [0xBA, 0xCE, 0x9E, 0x0E, 0xD4, 0x58, 0x6A, 0x4F],
+        [0x32, 0xB9, 0xDE, 0xD5, 0x0A, 0xE3, 0xBA, 0x69],
+        [0xD4, 0x72, 0xE8, 0x82, 0x67, 0xFB, 0x50, 0x52],
+        [0x70, 0xCB, 0xAD, 0x1E, 0x25, 0x76, 0x91, 0xF7],
+        [0xC4, 0x7C, 0x50, 0x38, 0x29, 0x7E, 0xDD, 0xA3],
+        [0x2F, 0xF2, 0x6D, 0x0E, 0xD1, 0x91, 0x74, 0x09],
+        [0x61, 0x61, 0xEC, 0xC1, 0x40, 0x86, 0xDD, 0x62],
+    ];
+
+    fn helper_get_mode() -> Cfb8Mode {
+        Cfb8Mode::new(
+            EncryptionSchemes::Aes128
+                .strategy(&NIST_KEY_128_BITS)
+                .unwrap(),
+            &NIST_IV,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn encrypt() {
+        let left = helper_get_mode().encrypt(&NIST_PLAINTEXT).unwrap();
+        let right = CFB8_AES128_CIPHERTEXT;
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn decrypt() {
+        let left = helper_get_mode().decrypt(&CFB8_AES128_CIPHERTEXT).unwrap();
+        let right = NIST_PLAINTEXT;
+
+        assert_eq!(left, right);
+    }
+
+    // NIST SP 800-38A, F.3.7 covers the first 18 bytes
+    #[test]
+    fn encrypt_nist_prefix() {
+        let mut mode = helper_get_mode();
+        let mut data = NIST_PLAINTEXT.concat()[..18].to_vec();
+
+        mode.process(&mut data, false);
+
+        let left = data;
+        let right = [
+            0x3B, 0x79, 0x42, 0x4C, 0x9C, 0x0D, 0xD4, 0x36, 0xBA, 0xCE, 0x9E, 0x0E, 0xD4, 0x58,
+            0x6A, 0x4F, 0x32, 0xB9,
+        ];
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn des_encrypt_and_decrypt() {
+        helper_des_round_trip(EncryptionModes::Cfb8);
+    }
+}
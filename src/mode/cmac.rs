@@ -0,0 +1,141 @@
+use crate::{strategy::EncryptionStrategy, BLOCK_CHUNK_SIZE};
+
+use super::{encrypt_block, xor_bytes};
+
+// Low byte of the reduction polynomial used to double subkeys, per block size in bytes
+const CMAC_RB_64_BITS: u8 = 0x1B;
+const CMAC_RB_128_BITS: u8 = 0x87;
+
+pub(super) fn cmac(strategy: &dyn EncryptionStrategy, data: &[u8]) -> Vec<u8> {
+    let block_len = strategy.block_size() * BLOCK_CHUNK_SIZE;
+    let first_subkey = dbl(&encrypt_block(strategy, &vec![0u8; block_len]));
+
+    let last_block_start = data.len().saturating_sub(1) / block_len * block_len;
+    let (blocks, last_block) = data.split_at(last_block_start);
+
+    // A complete last block is masked with K1, a partial or empty one is padded and masked with K2
+    let last_block = match last_block.len() == block_len {
+        true => xor_bytes(last_block, &first_subkey),
+        false => {
+            let mut padded_block = last_block.to_vec();
+            padded_block.push(0x80);
+            padded_block.resize(block_len, 0);
+
+            xor_bytes(&padded_block, &dbl(&first_subkey))
+        }
+    };
+
+    let mut mac = vec![0u8; block_len];
+
+    for block in blocks.chunks(block_len).chain([last_block.as_slice()]) {
+        mac = encrypt_block(strategy, &xor_bytes(&mac, block));
+    }
+
+    mac
+}
+
+// Multiplication by x in GF(2^n), big-endian as used by CMAC and S2V
+pub(super) fn dbl(block: &[u8]) -> Vec<u8> {
+    let reduction = match block.len() {
+        8 => CMAC_RB_64_BITS,
+        16 => CMAC_RB_128_BITS,
+        _ => unreachable!("🙀🧨 CMAC: Unsupported block size. This was not supposed to happen."),
+    };
+
+    let mut doubled: Vec<u8> = block
+        .windows(2)
+        .map(|pair| (pair[0] << 1) | (pair[1] >> 7))
+        .collect();
+
+    doubled.push((block[block.len() - 1] << 1) ^ ((block[0] >> 7) * reduction));
+
+    doubled
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{mode::tests::*, EncryptionSchemes};
+
+    use super::*;
+
+    // RFC 4493, section 4, messages are prefixes of the NIST SP 800-38A plaintext
+    fn helper_cmac(message_len: usize) -> Vec<u8> {
+        let strategy = EncryptionSchemes::Aes128
+            .strategy(&NIST_KEY_128_BITS)
+            .unwrap();
+
+        cmac(strategy.as_ref(), &NIST_PLAINTEXT.concat()[..message_len])
+    }
+
+    #[test]
+    fn cmac_empty_message() {
+        let left = helper_cmac(0);
+        let right = [
+            0xBB, 0x1D, 0x69, 0x29, 0xE9, 0x59, 0x37, 0x28, 0x7F, 0xA3, 0x7D, 0x12, 0x9B, 0x75,
+            0x67, 0x46,
+        ];
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn cmac_single_block() {
+        let left = helper_cmac(16);
+        let right = [
+            0x07, 0x0A, 0x16, 0xB4, 0x6B, 0x4D, 0x41, 0x44, 0xF7, 0x9B, 0xDD, 0x9D, 0xD0, 0x4A,
+            0x28, 0x7C,
+        ];
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn cmac_partial_last_block() {
+        let left = helper_cmac(40);
+        let right = [
+            0xDF, 0xA6, 0x67, 0x47, 0xDE, 0x9A, 0xE6, 0x30, 0x30, 0xCA, 0x32, 0x61, 0x14, 0x97,
+            0xC8, 0x27,
+        ];
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn cmac_four_blocks() {
+        let left = helper_cmac(64);
+        let right = [
+            0x51, 0xF0, 0xBE, 0xBF, 0x7E, 0x3B, 0x9D, 0x92, 0xFC, 0x49, 0x74, 0x17, 0x79, 0x36,
+            0x3C, 0xFE,
+        ];
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn dbl_reduces_128_bit_block() {
+        let mut block = [0u8; 16];
+        block[0] = 0x80;
+
+        let left = dbl(&block);
+        let mut right = [0u8; 16];
+        right[15] = 0x87;
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn dbl_reduces_64_bit_block() {
+        let left = dbl(&[0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01]);
+        let right = [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x1B ^ 0x02];
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn dbl_carries_between_bytes() {
+        let left = dbl(&[0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x80]);
+        let right = [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x03, 0x00];
+
+        assert_eq!(left, right);
+    }
+}
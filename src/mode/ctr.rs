@@ -0,0 +1,346 @@
+use crate::{
+    error::{Result, SymciphError},
+    strategy::EncryptionStrategy,
+    DataChunk, BLOCK_CHUNK_SIZE,
+};
+
+use super::{check_iv_len, encrypt_block, to_chunks, EncryptionMode};
+
+pub(crate) struct CtrMode {
+    strategy: Box<dyn EncryptionStrategy>,
+    initial_counter_block: Vec<u8>,
+    counter_bits: usize,
+    offset: u64,
+    keystream_index: u64,
+    keystream: Vec<u8>,
+}
+
+impl CtrMode {
+    pub fn new(
+        strategy: Box<dyn EncryptionStrategy>,
+        iv: &[DataChunk],
+        counter_bits: usize,
+    ) -> Result<Self> {
+        let block_bits = strategy.block_size() * BLOCK_CHUNK_SIZE * u8::BITS as usize;
+
+        check_iv_len(strategy.as_ref(), iv)?;
+
+        if counter_bits == 0 || counter_bits > block_bits || !counter_bits.is_multiple_of(8) {
+            return Err(SymciphError::InvalidInput(
+                "🧩🙀: Counter must be a whole number of bytes no longer than the cipher block."
+                    .to_string(),
+            ));
+        }
+
+        Ok(Self {
+            strategy,
+            initial_counter_block: iv.concat(),
+            counter_bits,
+            offset: 0,
+            keystream_index: 0,
+            keystream: Vec::new(),
+        })
+    }
+
+    // Restarts the keystream from a new counter block, for modes that derive it per message
+    pub(super) fn reset(&mut self, iv: &[DataChunk]) -> Result<()> {
+        check_iv_len(self.strategy.as_ref(), iv)?;
+
+        self.initial_counter_block = iv.concat();
+        self.offset = 0;
+        self.keystream.clear();
+
+        Ok(())
+    }
+
+    pub(super) fn strategy(&self) -> &dyn EncryptionStrategy {
+        self.strategy.as_ref()
+    }
+
+    pub fn seek(&mut self, offset: u64) {
+        self.offset = offset;
+    }
+
+    pub fn apply_keystream(&mut self, data: &mut [u8]) -> Result<()> {
+        let block_len = self.initial_counter_block.len() as u64;
+
+        for byte in data.iter_mut() {
+            let block_index = self.offset / block_len;
+
+            if self.keystream.is_empty() || self.keystream_index != block_index {
+                self.keystream = self.keystream_block(block_index)?;
+                self.keystream_index = block_index;
+            }
+
+            *byte ^= self.keystream[(self.offset % block_len) as usize];
+            self.offset += 1;
+        }
+
+        Ok(())
+    }
+
+    fn keystream_block(&self, block_index: u64) -> Result<Vec<u8>> {
+        if self.counter_bits < u64::BITS as usize && block_index >> self.counter_bits != 0 {
+            return Err(counter_exhausted_error());
+        }
+
+        Ok(encrypt_block(
+            self.strategy.as_ref(),
+            &self.counter_block(block_index),
+        ))
+    }
+
+    fn counter_block(&self, block_index: u64) -> Vec<u8> {
+        let mut counter_block = self.initial_counter_block.clone();
+        let mut carry = block_index as u128;
+
+        // Big-endian addition confined to the counter field, the nonce is never touched
+        for byte in counter_block
+            .iter_mut()
+            .rev()
+            .take(self.counter_bits / u8::BITS as usize)
+        {
+            let sum = *byte as u128 + (carry & 0xFF);
+
+            *byte = sum as u8;
+            carry = (carry >> u8::BITS) + (sum >> u8::BITS);
+        }
+
+        counter_block
+    }
+}
+
+impl EncryptionMode for CtrMode {
+    fn encrypt(&mut self, plaintext: &[DataChunk]) -> Result<Vec<DataChunk>> {
+        let mut bytes = plaintext.concat();
+
+        self.apply_keystream(&mut bytes)?;
+
+        Ok(to_chunks(&bytes))
+    }
+
+    fn decrypt(&mut self, ciphertext: &[DataChunk]) -> Result<Vec<DataChunk>> {
+        self.encrypt(ciphertext)
+    }
+}
+
+pub(crate) fn counter_exhausted_error() -> SymciphError {
+    SymciphError::InvalidInput(
+        "🧩🙀: Input too long for the CTR counter, the keystream would repeat.".to_string(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{mode::tests::*, EncryptionSchemes};
+
+    use super::*;
+
+    // NIST SP 800-38A, F.5.1 starts from a counter block of F0F1..FEFF
+    const NIST_CTR_COUNTER_BLOCK: [DataChunk; 2] = [
+        [0xF0, 0xF1, 0xF2, 0xF3, 0xF4, 0xF5, 0xF6, 0xF7],
+        [0xF8, 0xF9, 0xFA, 0xFB, 0xFC, 0xFD, 0xFE, 0xFF],
+    ];
+
+    const NIST_CTR_AES128_CIPHERTEXT: [DataChunk; 8] = [
+        [0x87, 0x4D, 0x61, 0x91, 0xB6, 0x20, 0xE3, 0x26],
+        [0x1B, 0xEF, 0x68, 0x64, 0x99, 0x0D, 0xB6, 0xCE],
+        [0x98, 0x06, 0xF6, 0x6B, 0x79, 0x70, 0xFD, 0xFF],
+        [0x86, 0x17, 0x18, 0x7B, 0xB9, 0xFF, 0xFD, 0xFF],
+        [0x5A, 0xE4, 0xDF, 0x3E, 0xDB, 0xD5, 0xD3, 0x5E],
+        [0x5B, 0x4F, 0x09, 0x02, 0x0D, 0xB0, 0x3E, 0xAB],
+        [0x1E, 0x03, 0x1D, 0xDA, 0x2F, 0xBE, 0x03, 0xD1],
+        [0x79, 0x21, 0x70, 0xA0, 0xF3, 0x00, 0x9C, 0xEE],
+    ];
+
+    fn helper_get_mode(counter_bits: usize) -> CtrMode {
+        CtrMode::new(
+            EncryptionSchemes::Aes128
+                .strategy(&NIST_KEY_128_BITS)
+                .unwrap(),
+            &NIST_CTR_COUNTER_BLOCK,
+            counter_bits,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn encrypt() {
+        let left = helper_get_mode(32).encrypt(&NIST_PLAINTEXT).unwrap();
+        let right = NIST_CTR_AES128_CIPHERTEXT;
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn decrypt() {
+        let left = helper_get_mode(32)
+            .decrypt(&NIST_CTR_AES128_CIPHERTEXT)
+            .unwrap();
+        let right = NIST_PLAINTEXT;
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn encrypt_odd_length_across_calls() {
+        let mut mode = helper_get_mode(32);
+        let mut data = NIST_PLAINTEXT.concat();
+
+        mode.apply_keystream(&mut data[..7]).unwrap();
+        mode.apply_keystream(&mut data[7..29]).unwrap();
+
+        let left = &data[..29];
+        let right = &NIST_CTR_AES128_CIPHERTEXT.concat()[..29];
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn decrypt_from_offset() {
+        let mut mode = helper_get_mode(32);
+        let mut data = NIST_CTR_AES128_CIPHERTEXT.concat()[21..37].to_vec();
+
+        mode.seek(21);
+        mode.apply_keystream(&mut data).unwrap();
+
+        let left = data.as_slice();
+        let right = &NIST_PLAINTEXT.concat()[21..37];
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn seek_back_after_encrypt() {
+        let mut mode = helper_get_mode(32);
+        let mut data = NIST_PLAINTEXT.concat();
+
+        mode.apply_keystream(&mut data).unwrap();
+        mode.seek(0);
+        mode.apply_keystream(&mut data).unwrap();
+
+        let left = data;
+        let right = NIST_PLAINTEXT.concat();
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn counter_wraps_within_counter_field() {
+        let mut iv = NIST_IV;
+        iv[1][4..].copy_from_slice(&[0xFF; 4]);
+        let mode = CtrMode::new(
+            EncryptionSchemes::Aes128
+                .strategy(&NIST_KEY_128_BITS)
+                .unwrap(),
+            &iv,
+            32,
+        )
+        .unwrap();
+
+        let left = mode.counter_block(1);
+        let right = [
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x00, 0x00,
+            0x00, 0x00,
+        ];
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn counter_carries_across_bytes() {
+        let mode = CtrMode::new(
+            EncryptionSchemes::Aes128
+                .strategy(&NIST_KEY_128_BITS)
+                .unwrap(),
+            &NIST_IV,
+            64,
+        )
+        .unwrap();
+
+        let left = mode.counter_block(0x1F1);
+        let right = [
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D,
+            0x10, 0x00,
+        ];
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn counter_space_exhausted() {
+        let mut mode = helper_get_mode(8);
+        let mut data = [0u8; 16 * 256 + 1];
+
+        assert!(mode.apply_keystream(&mut data[..16 * 256]).is_ok());
+        assert!(mode.apply_keystream(&mut data[16 * 256..]).is_err());
+    }
+
+    #[test]
+    fn des_encrypt_and_decrypt() {
+        let mut data = NIST_PLAINTEXT.concat()[..45].to_vec();
+
+        let mut mode = CtrMode::new(
+            EncryptionSchemes::Des.strategy(&KEY_64_BITS).unwrap(),
+            &IV_64_BITS,
+            32,
+        )
+        .unwrap();
+        mode.apply_keystream(&mut data).unwrap();
+        mode.seek(0);
+        mode.apply_keystream(&mut data).unwrap();
+
+        let left = data.as_slice();
+        let right = &NIST_PLAINTEXT.concat()[..45];
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn des_counter_longer_than_block() {
+        let left = CtrMode::new(
+            EncryptionSchemes::Des.strategy(&KEY_64_BITS).unwrap(),
+            &IV_64_BITS,
+            96,
+        );
+
+        assert!(left.is_err());
+    }
+
+    #[test]
+    fn counter_not_whole_bytes() {
+        let left = CtrMode::new(
+            EncryptionSchemes::Aes128
+                .strategy(&NIST_KEY_128_BITS)
+                .unwrap(),
+            &NIST_CTR_COUNTER_BLOCK,
+            12,
+        );
+
+        assert!(left.is_err());
+    }
+
+    #[test]
+    fn patch_middle_of_ciphertext() {
+        let mut plaintext: Vec<u8> = (0..=255).cycle().take(1000).collect();
+        let mut ciphertext = plaintext.clone();
+        helper_get_mode(32)
+            .apply_keystream(&mut ciphertext)
+            .unwrap();
+
+        let patch = b"patched in place";
+        plaintext[333..333 + patch.len()].copy_from_slice(patch);
+
+        let mut encrypted_patch = *patch;
+        let mut mode = helper_get_mode(32);
+        mode.seek(333);
+        mode.apply_keystream(&mut encrypted_patch).unwrap();
+        ciphertext[333..333 + patch.len()].copy_from_slice(&encrypted_patch);
+
+        helper_get_mode(32)
+            .apply_keystream(&mut ciphertext)
+            .unwrap();
+
+        assert_eq!(ciphertext, plaintext);
+    }
+}
@@ -0,0 +1,284 @@
+use crate::{strategy::EncryptionStrategy, BLOCK_CHUNK_SIZE};
+
+use super::{cmac::cmac, constant_time_eq, to_chunks, xor_bytes, AuthenticatedMode, CtrMode};
+
+pub const EAX_MIN_TAG_LEN: usize = 4;
+
+// Domain separators for the three OMAC passes
+const EAX_NONCE_DOMAIN: u8 = 0;
+const EAX_AAD_DOMAIN: u8 = 1;
+const EAX_CIPHERTEXT_DOMAIN: u8 = 2;
+
+pub(crate) struct EaxMode {
+    ctr: CtrMode,
+    nonce_mac: Vec<u8>,
+    tag_len: usize,
+}
+
+impl EaxMode {
+    pub fn new(strategy: Box<dyn EncryptionStrategy>, nonce: &[u8], tag_len: usize) -> Self {
+        let block_len = strategy.block_size() * BLOCK_CHUNK_SIZE;
+
+        assert!(
+            (EAX_MIN_TAG_LEN..=block_len).contains(&tag_len),
+            "EAX: Tag must be between 4 bytes and the cipher block size"
+        );
+
+        // The whole block is the counter, starting from the OMAC of the nonce
+        let nonce_mac = omac(strategy.as_ref(), EAX_NONCE_DOMAIN, nonce);
+
+        Self {
+            ctr: CtrMode::new(
+                strategy,
+                &to_chunks(&nonce_mac),
+                block_len * u8::BITS as usize,
+            )
+            .expect("🙀🧨 EAX: Counter layout rejected. This was not supposed to happen."),
+            nonce_mac,
+            tag_len,
+        }
+    }
+
+    fn tag(&self, aad: &[u8], ciphertext: &[u8]) -> Vec<u8> {
+        let strategy = self.ctr.strategy();
+
+        let mut tag = xor_bytes(
+            &xor_bytes(&self.nonce_mac, &omac(strategy, EAX_AAD_DOMAIN, aad)),
+            &omac(strategy, EAX_CIPHERTEXT_DOMAIN, ciphertext),
+        );
+        tag.truncate(self.tag_len);
+
+        tag
+    }
+
+    fn apply_keystream(&mut self, data: &mut [u8]) {
+        self.ctr.seek(0);
+        // A whole-block counter of 64 bits or more outlasts any byte offset
+        self.ctr
+            .apply_keystream(data)
+            .expect("🙀🧨 EAX: Counter space exhausted. This was not supposed to happen.");
+    }
+}
+
+impl AuthenticatedMode for EaxMode {
+    fn seal(&mut self, aad: &[u8], plaintext: &[u8]) -> Vec<u8> {
+        let mut ciphertext = plaintext.to_vec();
+
+        self.apply_keystream(&mut ciphertext);
+
+        let tag = self.tag(aad, &ciphertext);
+        ciphertext.extend_from_slice(&tag);
+
+        ciphertext
+    }
+
+    fn open(&mut self, aad: &[u8], ciphertext: &[u8]) -> Option<Vec<u8>> {
+        let tag_position = ciphertext.len().checked_sub(self.tag_len)?;
+        let (ciphertext, tag) = ciphertext.split_at(tag_position);
+
+        if !constant_time_eq(&self.tag(aad, ciphertext), tag) {
+            return None;
+        }
+
+        let mut plaintext = ciphertext.to_vec();
+        self.apply_keystream(&mut plaintext);
+
+        Some(plaintext)
+    }
+}
+
+// OMAC with a domain block [t] prepended, t being the last byte of an all-zero block
+fn omac(strategy: &dyn EncryptionStrategy, domain: u8, data: &[u8]) -> Vec<u8> {
+    let block_len = strategy.block_size() * BLOCK_CHUNK_SIZE;
+    let mut input = vec![0u8; block_len];
+
+    input[block_len - 1] = domain;
+    input.extend_from_slice(data);
+
+    cmac(strategy, &input)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{mode::tests::*, DataChunk, EncryptionSchemes};
+
+    use super::*;
+
+    fn helper_seal(key: &[u8], nonce: &[u8], aad: &[u8], plaintext: &[u8]) -> Vec<u8> {
+        let cipher = match key.len() {
+            16 => EncryptionSchemes::Aes128,
+            24 => EncryptionSchemes::Aes192,
+            _ => EncryptionSchemes::Aes256,
+        };
+
+        EaxMode::new(cipher.strategy(&to_chunks(key)).unwrap(), nonce, 16).seal(aad, plaintext)
+    }
+
+    fn helper_get_tdes_mode(tag_len: usize) -> EaxMode {
+        let key: [DataChunk; 3] = [KEY_64_BITS[0], IV_64_BITS[0], KEY_64_BITS[0]];
+
+        EaxMode::new(
+            EncryptionSchemes::Tdes.strategy(&key).unwrap(),
+            &IV_64_BITS[0],
+            tag_len,
+        )
+    }
+
+    // Bellare, Rogaway & Wagner, The EAX Mode of Operation, Appendix E
+    #[test]
+    fn seal_empty_message() {
+        let left = helper_seal(
+            &[
+                0x23, 0x39, 0x52, 0xDE, 0xE4, 0xD5, 0xED, 0x5F, 0x9B, 0x9C, 0x6D, 0x6F, 0xF8, 0x0F,
+                0xF4, 0x78,
+            ],
+            &[
+                0x62, 0xEC, 0x67, 0xF9, 0xC3, 0xA4, 0xA4, 0x07, 0xFC, 0xB2, 0xA8, 0xC4, 0x90, 0x31,
+                0xA8, 0xB3,
+            ],
+            &[0x6B, 0xFB, 0x91, 0x4F, 0xD0, 0x7E, 0xAE, 0x6B],
+            &[],
+        );
+        let right = [
+            0xE0, 0x37, 0x83, 0x0E, 0x83, 0x89, 0xF2, 0x7B, 0x02, 0x5A, 0x2D, 0x65, 0x27, 0xE7,
+            0x9D, 0x01,
+        ];
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn seal_2_byte_message() {
+        let left = helper_seal(
+            &[
+                0x91, 0x94, 0x5D, 0x3F, 0x4D, 0xCB, 0xEE, 0x0B, 0xF4, 0x5E, 0xF5, 0x22, 0x55, 0xF0,
+                0x95, 0xA4,
+            ],
+            &[
+                0xBE, 0xCA, 0xF0, 0x43, 0xB0, 0xA2, 0x3D, 0x84, 0x31, 0x94, 0xBA, 0x97, 0x2C, 0x66,
+                0xDE, 0xBD,
+            ],
+            &[0xFA, 0x3B, 0xFD, 0x48, 0x06, 0xEB, 0x53, 0xFA],
+            &[0xF7, 0xFB],
+        );
+        let right = [
+            0x19, 0xDD, 0x5C, 0x4C, 0x93, 0x31, 0x04, 0x9D, 0x0B, 0xDA, 0xB0, 0x27, 0x74, 0x08,
+            0xF6, 0x79, 0x67, 0xE5,
+        ];
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn seal_5_byte_message() {
+        let left = helper_seal(
+            &[
+                0x01, 0xF7, 0x4A, 0xD6, 0x40, 0x77, 0xF2, 0xE7, 0x04, 0xC0, 0xF6, 0x0A, 0xDA, 0x3D,
+                0xD5, 0x23,
+            ],
+            &[
+                0x70, 0xC3, 0xDB, 0x4F, 0x0D, 0x26, 0x36, 0x84, 0x00, 0xA1, 0x0E, 0xD0, 0x5D, 0x2B,
+                0xFF, 0x5E,
+            ],
+            &[0x23, 0x4A, 0x34, 0x63, 0xC1, 0x26, 0x4A, 0xC6],
+            &[0x1A, 0x47, 0xCB, 0x49, 0x33],
+        );
+        let right = [
+            0xD8, 0x51, 0xD5, 0xBA, 0xE0, 0x3A, 0x59, 0xF2, 0x38, 0xA2, 0x3E, 0x39, 0x19, 0x9D,
+            0xC9, 0x26, 0x66, 0x26, 0xC4, 0x0F, 0x80,
+        ];
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn seal_aes192() {
+        let data: Vec<u8> = (0..40).collect();
+
+        let left = helper_seal(&data[..24], &data[16..32], &data[..20], &data);
+        let right = [
+            0x80, 0xF8, 0xAF, 0x2E, 0x8A, 0x3C, 0x80, 0x31, 0xD7, 0xDA, 0x6C, 0xC0, 0x74, 0x5F,
+            0x89, 0x05, 0x3E, 0x85, 0x74, 0xAA, 0xDA, 0xEF, 0x42, 0x8E, 0xC8, 0xDD, 0xA6, 0xA7,
+            0x7D, 0x26, 0xD0, 0x20, 0xE6, 0x02, 0x6B, 0x07, 0xA8, 0x0F, 0x1E, 0x84, 0xC5, 0x69,
+            0x8B, 0x9F, 0x06, 0xA6, 0x61, 0xE1, 0x37, 0xA8, 0x45, 0x4E, 0xCE, 0x23, 0xEF, 0xF1,
+        ];
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn seal_aes256() {
+        let data: Vec<u8> = (0..40).collect();
+
+        let left = helper_seal(&data[..32], &data[16..32], &data[..20], &data);
+        let right = [
+            0x40, 0x94, 0xC8, 0xB5, 0x03, 0x15, 0xB3, 0xB4, 0x11, 0x4D, 0x36, 0xD6, 0xE8, 0xCC,
+            0x8E, 0x4C, 0xA6, 0x68, 0x9B, 0xAE, 0x75, 0xD1, 0x8C, 0x8B, 0x3B, 0x2C, 0x82, 0xA9,
+            0x18, 0xAE, 0x8A, 0x1D, 0x47, 0xD2, 0x13, 0xB3, 0x5C, 0xBF, 0xDD, 0xB6, 0x0C, 0x2F,
+            0x99, 0xE8, 0x78, 0xFB, 0xBD, 0x42, 0x38, 0x19, 0xA6, 0x72, 0x27, 0xE0, 0xD7, 0xD0,
+        ];
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn open_aes128() {
+        let mut mode = EaxMode::new(
+            EncryptionSchemes::Aes128
+                .strategy(&NIST_KEY_128_BITS)
+                .unwrap(),
+            &NIST_IV.concat(),
+            16,
+        );
+        let ciphertext = mode.seal(b"header", &NIST_PLAINTEXT.concat());
+
+        let left = mode.open(b"header", &ciphertext);
+        let right = Some(NIST_PLAINTEXT.concat());
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn open_tampered_ciphertext() {
+        let mut mode = EaxMode::new(
+            EncryptionSchemes::Aes128
+                .strategy(&NIST_KEY_128_BITS)
+                .unwrap(),
+            &NIST_IV.concat(),
+            12,
+        );
+        let mut ciphertext = mode.seal(b"header", &NIST_PLAINTEXT.concat());
+        ciphertext[0] ^= 0x01;
+
+        let left = mode.open(b"header", &ciphertext);
+
+        assert!(left.is_none());
+    }
+
+    #[test]
+    fn tdes_encrypt_and_decrypt() {
+        let ciphertext = helper_get_tdes_mode(8).seal(b"header", &NIST_PLAINTEXT.concat()[..45]);
+
+        assert_eq!(ciphertext.len(), 45 + 8);
+
+        let left = helper_get_tdes_mode(8).open(b"header", &ciphertext);
+        let right = Some(NIST_PLAINTEXT.concat()[..45].to_vec());
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn tdes_open_wrong_aad() {
+        let ciphertext = helper_get_tdes_mode(8).seal(b"header", &NIST_PLAINTEXT.concat());
+
+        let left = helper_get_tdes_mode(8).open(b"footer", &ciphertext);
+
+        assert!(left.is_none());
+    }
+
+    #[should_panic]
+    #[test]
+    fn tdes_tag_longer_than_block() {
+        helper_get_tdes_mode(16);
+    }
+}
@@ -0,0 +1,81 @@
+use crate::{error::Result, strategy::EncryptionStrategy, DataChunk};
+
+use super::EncryptionMode;
+
+pub(super) struct EcbMode {
+    strategy: Box<dyn EncryptionStrategy>,
+}
+
+impl EcbMode {
+    pub fn new(strategy: Box<dyn EncryptionStrategy>) -> Self {
+        Self { strategy }
+    }
+}
+
+impl EncryptionMode for EcbMode {
+    fn encrypt(&mut self, plaintext: &[DataChunk]) -> Result<Vec<DataChunk>> {
+        self.strategy.encrypt(plaintext)
+    }
+
+    fn decrypt(&mut self, ciphertext: &[DataChunk]) -> Result<Vec<DataChunk>> {
+        self.strategy.decrypt(ciphertext)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{mode::tests::*, EncryptionModes, EncryptionSchemes};
+
+    use super::*;
+
+    const NIST_ECB_AES128_CIPHERTEXT: [DataChunk; 8] = [
+        [0x3A, 0xD7, 0x7B, 0xB4, 0x0D, 0x7A, 0x36, 0x60],
+        [0xA8, 0x9E, 0xCA, 0xF3, 0x24, 0x66, 0xEF, 0x97],
+        [0xF5, 0xD3, 0xD5, 0x85, 0x03, 0xB9, 0x69, 0x9D],
+        [0xE7, 0x85, 0x89, 0x5A, 0x96, 0xFD, 0xBA, 0xAF],
+        [0x43, 0xB1, 0xCD, 0x7F, 0x59, 0x8E, 0xCE, 0x23],
+        [0x88, 0x1B, 0x00, 0xE3, 0xED, 0x03, 0x06, 0x88],
+        [0x7B, 0x0C, 0x78, 0x5E, 0x27, 0xE8, 0xAD, 0x3F],
+        [0x82, 0x23, 0x20, 0x71, 0x04, 0x72, 0x5D, 0xD4],
+    ];
+
+    fn helper_get_mode() -> EcbMode {
+        EcbMode::new(
+            EncryptionSchemes::Aes128
+                .strategy(&NIST_KEY_128_BITS)
+                .unwrap(),
+        )
+    }
+
+    #[test]
+    fn encrypt() {
+        let left = helper_get_mode().encrypt(&NIST_PLAINTEXT).unwrap();
+        let right = NIST_ECB_AES128_CIPHERTEXT;
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn decrypt() {
+        let left = helper_get_mode()
+            .decrypt(&NIST_ECB_AES128_CIPHERTEXT)
+            .unwrap();
+        let right = NIST_PLAINTEXT;
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn encrypt_repeats_blocks() {
+        let ciphertext = helper_get_mode()
+            .encrypt(&[&NIST_PLAINTEXT[..2], &NIST_PLAINTEXT[..2]].concat())
+            .unwrap();
+
+        assert_eq!(ciphertext[..2], ciphertext[2..]);
+    }
+
+    #[test]
+    fn des_encrypt_and_decrypt() {
+        helper_des_round_trip(EncryptionModes::Ecb);
+    }
+}
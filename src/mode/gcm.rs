@@ -0,0 +1,311 @@
+use crate::{
+    strategy::{EncryptionStrategy, AES_BLOCK_CHUNKS},
+    DataChunk,
+};
+
+use super::{constant_time_eq, encrypt_block, ghash::Ghash, to_chunks, AuthenticatedMode, CtrMode};
+
+pub const GCM_NONCE_LEN: usize = 12;
+pub const GCM_TAG_LEN: usize = 16;
+
+const GCM_BLOCK_LEN: usize = 16;
+const GCM_COUNTER_BITS: usize = 32;
+
+// NIST SP 800-38D caps the plaintext at 2^39 - 256 bits, so the 32-bit counter never wraps
+pub(crate) const GCM_MAX_PAYLOAD_LEN: u128 = (u32::MAX as u128 - 1) * GCM_BLOCK_LEN as u128;
+
+pub(crate) struct GcmMode {
+    ctr: CtrMode,
+    hash_key: u128,
+}
+
+impl GcmMode {
+    pub fn new(strategy: Box<dyn EncryptionStrategy>, nonce: &[u8]) -> Self {
+        assert!(
+            strategy.block_size() == AES_BLOCK_CHUNKS,
+            "GCM: Cipher block size must be 128 bits"
+        );
+
+        let hash_key = encrypt_block(strategy.as_ref(), &[0u8; GCM_BLOCK_LEN]);
+
+        Self {
+            // A 32-bit counter fits any 128-bit block
+            ctr: CtrMode::new(strategy, &pre_counter_block(nonce), GCM_COUNTER_BITS)
+                .expect("🙀🧨 GCM: Counter layout rejected. This was not supposed to happen."),
+            hash_key: u128::from_be_bytes(
+                hash_key
+                    .try_into()
+                    // If the block size assertion is affirmed, this will never happen
+                    .expect("🙀🧨 GCM: Hash key not 128 bits. This was not supposed to happen."),
+            ),
+        }
+    }
+
+    // Starts a new message under the same key without deriving the hash key again
+    pub(super) fn set_nonce(&mut self, nonce: &[u8]) {
+        // Pre-counter blocks are always one 128-bit block, whatever the nonce length
+        self.ctr
+            .reset(&pre_counter_block(nonce))
+            .expect("🙀🧨 GCM: Counter block not 128 bits. This was not supposed to happen.");
+    }
+
+    fn apply_keystream(&mut self, data: &mut [u8]) {
+        self.ctr.seek(GCM_BLOCK_LEN as u64);
+        self.ctr
+            .apply_keystream(data)
+            .expect("🙀🧨 GCM: Counter space exhausted. This was not supposed to happen.");
+    }
+
+    fn tag(&mut self, aad: &[u8], ciphertext: &[u8]) -> [u8; GCM_TAG_LEN] {
+        let mut ghash = Ghash::new(self.hash_key);
+
+        ghash.update(aad);
+        ghash.update(ciphertext);
+
+        let mut tag = ghash.finalize(aad.len(), ciphertext.len());
+
+        self.ctr.seek(0);
+        // The tag takes the first counter block, which always exists
+        self.ctr
+            .apply_keystream(&mut tag)
+            .expect("🙀🧨 GCM: Counter space exhausted. This was not supposed to happen.");
+
+        tag
+    }
+}
+
+impl AuthenticatedMode for GcmMode {
+    fn seal(&mut self, aad: &[u8], plaintext: &[u8]) -> Vec<u8> {
+        let mut ciphertext = plaintext.to_vec();
+
+        self.apply_keystream(&mut ciphertext);
+
+        let tag = self.tag(aad, &ciphertext);
+        ciphertext.extend_from_slice(&tag);
+
+        ciphertext
+    }
+
+    fn open(&mut self, aad: &[u8], ciphertext: &[u8]) -> Option<Vec<u8>> {
+        let tag_position = ciphertext.len().checked_sub(GCM_TAG_LEN)?;
+        let (ciphertext, tag) = ciphertext.split_at(tag_position);
+
+        if !constant_time_eq(&self.tag(aad, ciphertext), tag) {
+            return None;
+        }
+
+        let mut plaintext = ciphertext.to_vec();
+        self.apply_keystream(&mut plaintext);
+
+        Some(plaintext)
+    }
+}
+
+// J0 = nonce || 1, the tag is masked with E(J0) and the data starts at J0 + 1
+fn pre_counter_block(nonce: &[u8]) -> Vec<DataChunk> {
+    assert!(
+        nonce.len() == GCM_NONCE_LEN,
+        "GCM: Nonce must be 96 bits long"
+    );
+
+    let mut pre_counter_block = nonce.to_vec();
+    pre_counter_block.extend_from_slice(&1u32.to_be_bytes());
+
+    to_chunks(&pre_counter_block)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{mode::tests::*, EncryptionSchemes};
+
+    use super::*;
+
+    // McGrew & Viega, GCM specification, test cases 4, 10 and 16, the shorter keys are prefixes
+    const GCM_KEY_256_BITS: [DataChunk; 4] = [
+        [0xFE, 0xFF, 0xE9, 0x92, 0x86, 0x65, 0x73, 0x1C],
+        [0x6D, 0x6A, 0x8F, 0x94, 0x67, 0x30, 0x83, 0x08],
+        [0xFE, 0xFF, 0xE9, 0x92, 0x86, 0x65, 0x73, 0x1C],
+        [0x6D, 0x6A, 0x8F, 0x94, 0x67, 0x30, 0x83, 0x08],
+    ];
+
+    const GCM_NONCE: [u8; GCM_NONCE_LEN] = [
+        0xCA, 0xFE, 0xBA, 0xBE, 0xFA, 0xCE, 0xDB, 0xAD, 0xDE, 0xCA, 0xF8, 0x88,
+    ];
+
+    const GCM_AAD: [u8; 20] = [
+        0xFE, 0xED, 0xFA, 0xCE, 0xDE, 0xAD, 0xBE, 0xEF, 0xFE, 0xED, 0xFA, 0xCE, 0xDE, 0xAD, 0xBE,
+        0xEF, 0xAB, 0xAD, 0xDA, 0xD2,
+    ];
+
+    const GCM_PLAINTEXT: [u8; 60] = [
+        0xD9, 0x31, 0x32, 0x25, 0xF8, 0x84, 0x06, 0xE5, 0xA5, 0x59, 0x09, 0xC5, 0xAF, 0xF5, 0x26,
+        0x9A, 0x86, 0xA7, 0xA9, 0x53, 0x15, 0x34, 0xF7, 0xDA, 0x2E, 0x4C, 0x30, 0x3D, 0x8A, 0x31,
+        0x8A, 0x72, 0x1C, 0x3C, 0x0C, 0x95, 0x95, 0x68, 0x09, 0x53, 0x2F, 0xCF, 0x0E, 0x24, 0x49,
+        0xA6, 0xB5, 0x25, 0xB1, 0x6A, 0xED, 0xF5, 0xAA, 0x0D, 0xE6, 0x57, 0xBA, 0x63, 0x7B, 0x39,
+    ];
+
+    const GCM_AES128_CIPHERTEXT: [u8; 76] = [
+        0x42, 0x83, 0x1E, 0xC2, 0x21, 0x77, 0x74, 0x24, 0x4B, 0x72, 0x21, 0xB7, 0x84, 0xD0, 0xD4,
+        0x9C, 0xE3, 0xAA, 0x21, 0x2F, 0x2C, 0x02, 0xA4, 0xE0, 0x35, 0xC1, 0x7E, 0x23, 0x29, 0xAC,
+        0xA1, 0x2E, 0x21, 0xD5, 0x14, 0xB2, 0x54, 0x66, 0x93, 0x1C, 0x7D, 0x8F, 0x6A, 0x5A, 0xAC,
+        0x84, 0xAA, 0x05, 0x1B, 0xA3, 0x0B, 0x39, 0x6A, 0x0A, 0xAC, 0x97, 0x3D, 0x58, 0xE0, 0x91,
+        0x5B, 0xC9, 0x4F, 0xBC, 0x32, 0x21, 0xA5, 0xDB, 0x94, 0xFA, 0xE9, 0x5A, 0xE7, 0x12, 0x1A,
+        0x47,
+    ];
+
+    const GCM_AES192_CIPHERTEXT: [u8; 76] = [
+        0x39, 0x80, 0xCA, 0x0B, 0x3C, 0x00, 0xE8, 0x41, 0xEB, 0x06, 0xFA, 0xC4, 0x87, 0x2A, 0x27,
+        0x57, 0x85, 0x9E, 0x1C, 0xEA, 0xA6, 0xEF, 0xD9, 0x84, 0x62, 0x85, 0x93, 0xB4, 0x0C, 0xA1,
+        0xE1, 0x9C, 0x7D, 0x77, 0x3D, 0x00, 0xC1, 0x44, 0xC5, 0x25, 0xAC, 0x61, 0x9D, 0x18, 0xC8,
+        0x4A, 0x3F, 0x47, 0x18, 0xE2, 0x44, 0x8B, 0x2F, 0xE3, 0x24, 0xD9, 0xCC, 0xDA, 0x27, 0x10,
+        0x25, 0x19, 0x49, 0x8E, 0x80, 0xF1, 0x47, 0x8F, 0x37, 0xBA, 0x55, 0xBD, 0x6D, 0x27, 0x61,
+        0x8C,
+    ];
+
+    const GCM_AES256_CIPHERTEXT: [u8; 76] = [
+        0x52, 0x2D, 0xC1, 0xF0, 0x99, 0x56, 0x7D, 0x07, 0xF4, 0x7F, 0x37, 0xA3, 0x2A, 0x84, 0x42,
+        0x7D, 0x64, 0x3A, 0x8C, 0xDC, 0xBF, 0xE5, 0xC0, 0xC9, 0x75, 0x98, 0xA2, 0xBD, 0x25, 0x55,
+        0xD1, 0xAA, 0x8C, 0xB0, 0x8E, 0x48, 0x59, 0x0D, 0xBB, 0x3D, 0xA7, 0xB0, 0x8B, 0x10, 0x56,
+        0x82, 0x88, 0x38, 0xC5, 0xF6, 0x1E, 0x63, 0x93, 0xBA, 0x7A, 0x0A, 0xBC, 0xC9, 0xF6, 0x62,
+        0x76, 0xFC, 0x6E, 0xCE, 0x0F, 0x4E, 0x17, 0x68, 0xCD, 0xDF, 0x88, 0x53, 0xBB, 0x2D, 0x55,
+        0x1B,
+    ];
+
+    fn helper_get_mode() -> GcmMode {
+        GcmMode::new(
+            EncryptionSchemes::Aes128
+                .strategy(&GCM_KEY_256_BITS[..2])
+                .unwrap(),
+            &GCM_NONCE,
+        )
+    }
+
+    #[test]
+    fn seal_aes128() {
+        let left = helper_get_mode().seal(&GCM_AAD, &GCM_PLAINTEXT);
+        let right = GCM_AES128_CIPHERTEXT;
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn seal_aes192() {
+        let mut mode = GcmMode::new(
+            EncryptionSchemes::Aes192
+                .strategy(&GCM_KEY_256_BITS[..3])
+                .unwrap(),
+            &GCM_NONCE,
+        );
+
+        let left = mode.seal(&GCM_AAD, &GCM_PLAINTEXT);
+        let right = GCM_AES192_CIPHERTEXT;
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn seal_aes256() {
+        let mut mode = GcmMode::new(
+            EncryptionSchemes::Aes256
+                .strategy(&GCM_KEY_256_BITS)
+                .unwrap(),
+            &GCM_NONCE,
+        );
+
+        let left = mode.seal(&GCM_AAD, &GCM_PLAINTEXT);
+        let right = GCM_AES256_CIPHERTEXT;
+
+        assert_eq!(left, right);
+    }
+
+    // McGrew & Viega, GCM specification, test case 1
+    #[test]
+    fn seal_empty_plaintext() {
+        let mut mode = GcmMode::new(
+            EncryptionSchemes::Aes128.strategy(&[[0u8; 8]; 2]).unwrap(),
+            &[0u8; GCM_NONCE_LEN],
+        );
+
+        let left = mode.seal(&[], &[]);
+        let right = [
+            0x58, 0xE2, 0xFC, 0xCE, 0xFA, 0x7E, 0x30, 0x61, 0x36, 0x7F, 0x1D, 0x57, 0xA4, 0xE7,
+            0x45, 0x5A,
+        ];
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn open_aes128() {
+        let left = helper_get_mode().open(&GCM_AAD, &GCM_AES128_CIPHERTEXT);
+        let right = Some(GCM_PLAINTEXT.to_vec());
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn open_tampered_ciphertext() {
+        let mut ciphertext = GCM_AES128_CIPHERTEXT;
+        ciphertext[7] ^= 0x01;
+
+        let left = helper_get_mode().open(&GCM_AAD, &ciphertext);
+
+        assert!(left.is_none());
+    }
+
+    #[test]
+    fn open_tampered_tag() {
+        let mut ciphertext = GCM_AES128_CIPHERTEXT;
+        ciphertext[75] ^= 0x80;
+
+        let left = helper_get_mode().open(&GCM_AAD, &ciphertext);
+
+        assert!(left.is_none());
+    }
+
+    #[test]
+    fn open_wrong_aad() {
+        let left = helper_get_mode().open(&GCM_AAD[1..], &GCM_AES128_CIPHERTEXT);
+
+        assert!(left.is_none());
+    }
+
+    #[test]
+    fn open_shorter_than_tag() {
+        let left = helper_get_mode().open(&GCM_AAD, &GCM_AES128_CIPHERTEXT[..GCM_TAG_LEN - 1]);
+
+        assert!(left.is_none());
+    }
+
+    #[should_panic]
+    #[test]
+    fn des_not_supported() {
+        GcmMode::new(
+            EncryptionSchemes::Des.strategy(&KEY_64_BITS).unwrap(),
+            &GCM_NONCE,
+        );
+    }
+
+    #[should_panic]
+    #[test]
+    fn nonce_not_96_bits() {
+        GcmMode::new(
+            EncryptionSchemes::Aes128
+                .strategy(&NIST_KEY_128_BITS)
+                .unwrap(),
+            &GCM_NONCE[..8],
+        );
+    }
+
+    #[test]
+    fn open_any_flipped_bit() {
+        let ciphertext = helper_get_mode().seal(&GCM_AAD, &GCM_PLAINTEXT);
+
+        for bit in 0..ciphertext.len() * 8 {
+            let mut tampered = ciphertext.clone();
+            tampered[bit / 8] ^= 1 << (bit % 8);
+
+            assert!(helper_get_mode().open(&GCM_AAD, &tampered).is_none());
+        }
+    }
+}
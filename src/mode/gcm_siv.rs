@@ -0,0 +1,352 @@
+use crate::{
+    error::{Result, SymciphError},
+    strategy::{EncryptionStrategy, AES128_KEY_CHUNKS, AES256_KEY_CHUNKS},
+    DataChunk, EncryptionSchemes, BLOCK_CHUNK_SIZE,
+};
+
+use super::{
+    constant_time_eq, encrypt_block,
+    ghash::{mul_x, Ghash},
+    AuthenticatedMode,
+};
+
+pub const GCM_SIV_NONCE_LEN: usize = 12;
+pub const GCM_SIV_TAG_LEN: usize = 16;
+
+const GCM_SIV_BLOCK_LEN: usize = 16;
+
+// Plaintexts are limited to 2^36 bytes by RFC 8452
+pub(crate) const GCM_SIV_MAX_PLAINTEXT_LEN: u64 = 1 << 36;
+
+pub(crate) struct GcmSivMode {
+    authentication_key: [u8; GCM_SIV_BLOCK_LEN],
+    strategy: Box<dyn EncryptionStrategy>,
+    nonce: [u8; GCM_SIV_NONCE_LEN],
+}
+
+impl GcmSivMode {
+    // An empty nonce gives deterministic encryption under the all-zero nonce
+    pub fn new(key_generating_key: &[DataChunk], nonce: &[u8]) -> Result<Self> {
+        let cipher = match key_generating_key.len() {
+            AES128_KEY_CHUNKS => EncryptionSchemes::Aes128,
+            AES256_KEY_CHUNKS => EncryptionSchemes::Aes256,
+            _ => {
+                return Err(SymciphError::KeyLength(
+                    "🔑🙀: GCM-SIV keys must be 128 or 256 bits long.".to_string(),
+                ))
+            }
+        };
+
+        assert!(
+            nonce.is_empty() || nonce.len() == GCM_SIV_NONCE_LEN,
+            "GCM-SIV: Nonce must be 96 bits long or empty"
+        );
+
+        let mut fixed_nonce = [0u8; GCM_SIV_NONCE_LEN];
+        fixed_nonce[..nonce.len()].copy_from_slice(nonce);
+
+        // Per-nonce keys are the first half of E(KGK, LE32(i) || nonce) for consecutive i
+        let key_generating_strategy = cipher.strategy(key_generating_key)?;
+        let derived_key: Vec<DataChunk> = (0..2 + key_generating_key.len() as u32)
+            .map(|counter| {
+                let mut block = counter.to_le_bytes().to_vec();
+                block.extend_from_slice(&fixed_nonce);
+
+                let mut chunk = [0u8; BLOCK_CHUNK_SIZE];
+                chunk.copy_from_slice(
+                    &encrypt_block(key_generating_strategy.as_ref(), &block)[..BLOCK_CHUNK_SIZE],
+                );
+
+                chunk
+            })
+            .collect();
+
+        let (authentication_key, encryption_key) = derived_key.split_at(2);
+        let mut authentication_key_bytes = [0u8; GCM_SIV_BLOCK_LEN];
+        authentication_key_bytes.copy_from_slice(&authentication_key.concat());
+
+        Ok(Self {
+            authentication_key: authentication_key_bytes,
+            strategy: cipher.strategy(encryption_key)?,
+            nonce: fixed_nonce,
+        })
+    }
+
+    // POLYVAL expressed through GHASH with byte-reversed blocks, RFC 8452, Appendix A
+    fn polyval(&self, aad: &[u8], plaintext: &[u8]) -> [u8; GCM_SIV_BLOCK_LEN] {
+        let hash_key = mul_x(u128::from_le_bytes(self.authentication_key));
+        let mut ghash = Ghash::new(hash_key);
+
+        let aad_bits = aad.len() as u64 * u8::BITS as u64;
+        let plaintext_bits = plaintext.len() as u64 * u8::BITS as u64;
+        let length_block = [aad_bits.to_le_bytes(), plaintext_bits.to_le_bytes()].concat();
+
+        for block in aad
+            .chunks(GCM_SIV_BLOCK_LEN)
+            .chain(plaintext.chunks(GCM_SIV_BLOCK_LEN))
+            .chain([length_block.as_slice()])
+        {
+            let mut reversed_block = [0u8; GCM_SIV_BLOCK_LEN];
+            reversed_block[..block.len()].copy_from_slice(block);
+            reversed_block.reverse();
+
+            ghash.update(&reversed_block);
+        }
+
+        let mut digest = ghash.digest();
+        digest.reverse();
+
+        digest
+    }
+
+    fn tag(&self, aad: &[u8], plaintext: &[u8]) -> Vec<u8> {
+        let mut block = self.polyval(aad, plaintext);
+
+        for (byte, nonce_byte) in block.iter_mut().zip(self.nonce) {
+            *byte ^= nonce_byte;
+        }
+
+        block[GCM_SIV_BLOCK_LEN - 1] &= 0x7F;
+
+        encrypt_block(self.strategy.as_ref(), &block)
+    }
+
+    fn apply_keystream(&self, tag: &[u8], data: &mut [u8]) {
+        for (index, data_block) in data.chunks_mut(GCM_SIV_BLOCK_LEN).enumerate() {
+            let keystream = encrypt_block(self.strategy.as_ref(), &counter_block(tag, index));
+
+            for (byte, keystream_byte) in data_block.iter_mut().zip(keystream) {
+                *byte ^= keystream_byte;
+            }
+        }
+    }
+}
+
+impl AuthenticatedMode for GcmSivMode {
+    fn seal(&mut self, aad: &[u8], plaintext: &[u8]) -> Vec<u8> {
+        // check_message_len refuses longer plaintexts before they get here
+        assert!(
+            (plaintext.len() as u64) <= GCM_SIV_MAX_PLAINTEXT_LEN,
+            "🙀🧨 GCM-SIV: Plaintext longer than 2^36 bytes. This was not supposed to happen."
+        );
+
+        let tag = self.tag(aad, plaintext);
+        let mut ciphertext = plaintext.to_vec();
+
+        self.apply_keystream(&tag, &mut ciphertext);
+        ciphertext.extend_from_slice(&tag);
+
+        ciphertext
+    }
+
+    fn open(&mut self, aad: &[u8], ciphertext: &[u8]) -> Option<Vec<u8>> {
+        let tag_position = ciphertext.len().checked_sub(GCM_SIV_TAG_LEN)?;
+        let (ciphertext, tag) = ciphertext.split_at(tag_position);
+        let mut plaintext = ciphertext.to_vec();
+
+        self.apply_keystream(tag, &mut plaintext);
+
+        match constant_time_eq(&self.tag(aad, &plaintext), tag) {
+            true => Some(plaintext),
+            false => None,
+        }
+    }
+}
+
+// The tag with its top bit set is the initial counter, only the low 32-bit word increments
+fn counter_block(tag: &[u8], index: usize) -> Vec<u8> {
+    let mut counter_block = tag.to_vec();
+    counter_block[GCM_SIV_BLOCK_LEN - 1] |= 0x80;
+
+    let mut counter = [0u8; 4];
+    counter.copy_from_slice(&counter_block[..4]);
+
+    let counter = u32::from_le_bytes(counter).wrapping_add(index as u32);
+    counter_block[..4].copy_from_slice(&counter.to_le_bytes());
+
+    counter_block
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // RFC 8452, Appendix C.1 and C.2
+    const RFC_KEY_256_BITS: [DataChunk; 4] = [
+        [0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+        [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+        [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+        [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+    ];
+
+    const RFC_NONCE: [u8; GCM_SIV_NONCE_LEN] = [
+        0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ];
+
+    const RFC_PLAINTEXT: [u8; 32] = [
+        0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00,
+    ];
+
+    const RFC_SHORT_PLAINTEXT: [u8; 8] = [0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00];
+
+    fn helper_get_mode(key: &[DataChunk]) -> GcmSivMode {
+        GcmSivMode::new(key, &RFC_NONCE).unwrap()
+    }
+
+    #[test]
+    fn seal_empty_plaintext() {
+        let left = helper_get_mode(&RFC_KEY_256_BITS[..2]).seal(&[], &[]);
+        let right = [
+            0xDC, 0x20, 0xE2, 0xD8, 0x3F, 0x25, 0x70, 0x5B, 0xB4, 0x9E, 0x43, 0x9E, 0xCA, 0x56,
+            0xDE, 0x25,
+        ];
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn seal_partial_block() {
+        let left = helper_get_mode(&RFC_KEY_256_BITS[..2]).seal(&[], &RFC_SHORT_PLAINTEXT);
+        let right = [
+            0xB5, 0xD8, 0x39, 0x33, 0x0A, 0xC7, 0xB7, 0x86, 0x57, 0x87, 0x82, 0xFF, 0xF6, 0x01,
+            0x3B, 0x81, 0x5B, 0x28, 0x7C, 0x22, 0x49, 0x3A, 0x36, 0x4C,
+        ];
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn seal_with_aad() {
+        let left = helper_get_mode(&RFC_KEY_256_BITS[..2]).seal(&[0x01], &RFC_PLAINTEXT[..12]);
+        let right = [
+            0x29, 0x6C, 0x78, 0x89, 0xFD, 0x99, 0xF4, 0x19, 0x17, 0xF4, 0x46, 0x20, 0x08, 0x29,
+            0x9C, 0x51, 0x02, 0x74, 0x5A, 0xAA, 0x3A, 0x0C, 0x46, 0x9F, 0xAD, 0x9E, 0x07, 0x5A,
+        ];
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn seal_two_blocks() {
+        let left = helper_get_mode(&RFC_KEY_256_BITS[..2]).seal(&[0x01], &RFC_PLAINTEXT);
+        let right = [
+            0x62, 0x00, 0x48, 0xEF, 0x3C, 0x1E, 0x73, 0xE5, 0x7E, 0x02, 0xBB, 0x85, 0x62, 0xC4,
+            0x16, 0xA3, 0x19, 0xE7, 0x3E, 0x4C, 0xAA, 0xC8, 0xE9, 0x6A, 0x1E, 0xCB, 0x29, 0x33,
+            0x14, 0x5A, 0x1D, 0x71, 0xE6, 0xAF, 0x6A, 0x7F, 0x87, 0x28, 0x7D, 0xA0, 0x59, 0xA7,
+            0x16, 0x84, 0xED, 0x34, 0x98, 0xE1,
+        ];
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn seal_aes256() {
+        let left = helper_get_mode(&RFC_KEY_256_BITS).seal(&[0x01], &RFC_PLAINTEXT);
+        let right = [
+            0x07, 0xDA, 0xD3, 0x64, 0xBF, 0xC2, 0xB9, 0xDA, 0x89, 0x11, 0x6D, 0x7B, 0xEF, 0x6D,
+            0xAA, 0xAF, 0x6F, 0x25, 0x55, 0x10, 0xAA, 0x65, 0x4F, 0x92, 0x0A, 0xC8, 0x1B, 0x94,
+            0xE8, 0xBA, 0xD3, 0x65, 0xAE, 0xA1, 0xBA, 0xD1, 0x27, 0x02, 0xE1, 0x96, 0x56, 0x04,
+            0x37, 0x4A, 0xAB, 0x96, 0xDB, 0xBC,
+        ];
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn seal_deterministic_uses_zero_nonce() {
+        let left = GcmSivMode::new(&RFC_KEY_256_BITS[..2], &[])
+            .unwrap()
+            .seal(&[], &RFC_SHORT_PLAINTEXT);
+        let right = [
+            0xA2, 0xE9, 0x55, 0x37, 0x8A, 0xCA, 0xF1, 0x21, 0x0C, 0x54, 0x03, 0xA6, 0xED, 0x77,
+            0x1B, 0x2A, 0xBF, 0x78, 0x98, 0x4A, 0xAA, 0xCD, 0x97, 0x46,
+        ];
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn open_two_blocks() {
+        let ciphertext = helper_get_mode(&RFC_KEY_256_BITS).seal(&[0x01], &RFC_PLAINTEXT);
+
+        let left = helper_get_mode(&RFC_KEY_256_BITS).open(&[0x01], &ciphertext);
+        let right = Some(RFC_PLAINTEXT.to_vec());
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn open_tampered_ciphertext() {
+        let mut ciphertext = helper_get_mode(&RFC_KEY_256_BITS).seal(&[0x01], &RFC_PLAINTEXT);
+        ciphertext[3] ^= 0x01;
+
+        let left = helper_get_mode(&RFC_KEY_256_BITS).open(&[0x01], &ciphertext);
+
+        assert!(left.is_none());
+    }
+
+    #[test]
+    fn counter_wraps_low_word_only() {
+        let mut tag = [0u8; GCM_SIV_TAG_LEN];
+        tag[..5].copy_from_slice(&[0xFF, 0xFF, 0xFF, 0xFF, 0x01]);
+
+        let left = counter_block(&tag, 1);
+        let right = [
+            0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x80,
+        ];
+
+        assert_eq!(left, right);
+    }
+
+    #[should_panic]
+    #[test]
+    fn aes192_not_supported() {
+        GcmSivMode::new(&RFC_KEY_256_BITS[..3], &RFC_NONCE).unwrap();
+    }
+
+    #[should_panic]
+    #[test]
+    fn nonce_not_96_bits() {
+        GcmSivMode::new(&RFC_KEY_256_BITS[..2], &RFC_NONCE[..8]).unwrap();
+    }
+
+    #[test]
+    fn seal_equal_records_without_nonce() {
+        let records: [&[u8]; 4] = [b"alice", b"bob", b"alice", b"carol"];
+
+        let ciphertexts: Vec<Vec<u8>> = records
+            .iter()
+            .map(|record| {
+                GcmSivMode::new(&RFC_KEY_256_BITS, &[])
+                    .unwrap()
+                    .seal(b"users.name", record)
+            })
+            .collect();
+
+        assert_eq!(ciphertexts[0], ciphertexts[2]);
+        assert_ne!(ciphertexts[0], ciphertexts[1]);
+        assert_ne!(ciphertexts[0], ciphertexts[3]);
+    }
+
+    #[test]
+    fn seal_nonce_changes_ciphertext() {
+        let left = helper_get_mode(&RFC_KEY_256_BITS).seal(&[], b"alice");
+        let right = GcmSivMode::new(&RFC_KEY_256_BITS, &[])
+            .unwrap()
+            .seal(&[], b"alice");
+
+        assert_ne!(left, right);
+    }
+
+    #[test]
+    fn open_wrong_aad() {
+        let ciphertext = helper_get_mode(&RFC_KEY_256_BITS).seal(b"users.name", b"alice");
+
+        let left = helper_get_mode(&RFC_KEY_256_BITS).open(b"users.email", &ciphertext);
+
+        assert!(left.is_none());
+    }
+}
@@ -0,0 +1,147 @@
+const GHASH_BLOCK_LEN: usize = 16;
+
+// x^128 + x^7 + x^2 + x + 1 in GCM's reflected bit order
+const REDUCTION_POLYNOMIAL: u128 = 0xE1 << 120;
+
+pub(super) struct Ghash {
+    hash_key: u128,
+    state: u128,
+}
+
+impl Ghash {
+    pub fn new(hash_key: u128) -> Self {
+        Self { hash_key, state: 0 }
+    }
+
+    // Every call is zero padded to a whole block, GCM pads the AAD and ciphertext separately
+    pub fn update(&mut self, data: &[u8]) {
+        for block in data.chunks(GHASH_BLOCK_LEN) {
+            let mut padded_block = [0u8; GHASH_BLOCK_LEN];
+            padded_block[..block.len()].copy_from_slice(block);
+
+            self.state = gf_mul(
+                self.state ^ u128::from_be_bytes(padded_block),
+                self.hash_key,
+            );
+        }
+    }
+
+    pub fn finalize(mut self, aad_len: usize, ciphertext_len: usize) -> [u8; GHASH_BLOCK_LEN] {
+        let aad_bits = aad_len as u128 * u8::BITS as u128;
+        let ciphertext_bits = ciphertext_len as u128 * u8::BITS as u128;
+
+        self.state = gf_mul(
+            self.state ^ (aad_bits << 64 | ciphertext_bits),
+            self.hash_key,
+        );
+
+        self.digest()
+    }
+
+    pub fn digest(self) -> [u8; GHASH_BLOCK_LEN] {
+        self.state.to_be_bytes()
+    }
+}
+
+// Multiplication by x, which is a right shift in GCM's reflected bit order
+pub(super) fn mul_x(element: u128) -> u128 {
+    match element & 1 {
+        1 => (element >> 1) ^ REDUCTION_POLYNOMIAL,
+        _ => element >> 1,
+    }
+}
+
+// Multiplication in GF(2^128), bit 0 of the field element is the most significant bit
+fn gf_mul(left: u128, right: u128) -> u128 {
+    let mut product = 0;
+    let mut multiple = right;
+
+    for bit in (0..u128::BITS).rev() {
+        if (left >> bit) & 1 == 1 {
+            product ^= multiple;
+        }
+
+        multiple = mul_x(multiple);
+    }
+
+    product
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HASH_KEY: u128 = 0x66E94BD4EF8A2C3B884CFA59CA342B2E;
+
+    const MULTIPLICATIVE_IDENTITY: u128 = 1 << 127;
+
+    #[test]
+    fn gf_mul_by_identity() {
+        let left = gf_mul(HASH_KEY, MULTIPLICATIVE_IDENTITY);
+        let right = HASH_KEY;
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn gf_mul_commutes() {
+        let other = 0x0388DACE60B6A392F328C2B971B2FE78;
+
+        let left = gf_mul(HASH_KEY, other);
+        let right = gf_mul(other, HASH_KEY);
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn mul_x_matches_gf_mul() {
+        let left = mul_x(HASH_KEY);
+        let right = gf_mul(HASH_KEY, MULTIPLICATIVE_IDENTITY >> 1);
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn gf_mul_by_zero() {
+        let left = gf_mul(HASH_KEY, 0);
+        let right = 0;
+
+        assert_eq!(left, right);
+    }
+
+    // McGrew & Viega, GCM specification, test case 2
+    #[test]
+    fn ghash_single_block() {
+        let ciphertext = [
+            0x03, 0x88, 0xDA, 0xCE, 0x60, 0xB6, 0xA3, 0x92, 0xF3, 0x28, 0xC2, 0xB9, 0x71, 0xB2,
+            0xFE, 0x78,
+        ];
+
+        let mut ghash = Ghash::new(HASH_KEY);
+        ghash.update(&[]);
+        ghash.update(&ciphertext);
+
+        let left = ghash.finalize(0, ciphertext.len());
+        let right = [
+            0xF3, 0x8C, 0xBB, 0x1A, 0xD6, 0x92, 0x23, 0xDC, 0xC3, 0x45, 0x7A, 0xE5, 0xB6, 0xB0,
+            0xF8, 0x85,
+        ];
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn ghash_pads_each_update() {
+        let mut split = Ghash::new(HASH_KEY);
+        split.update(&[0x01]);
+        split.update(&[0x02]);
+
+        let mut joined = Ghash::new(HASH_KEY);
+        joined.update(&[0x01, 0x02]);
+
+        let left = split.finalize(1, 1);
+        let right = joined.finalize(1, 1);
+
+        assert_ne!(left, right);
+    }
+}
@@ -0,0 +1,437 @@
+use crate::strategy::{EncryptionStrategy, AES_BLOCK_CHUNKS};
+
+use super::{constant_time_eq, decrypt_block, encrypt_block, xor_bytes, AuthenticatedMode};
+
+pub const OCB_MIN_NONCE_LEN: usize = 1;
+pub const OCB_MAX_NONCE_LEN: usize = 15;
+pub const OCB_MIN_TAG_LEN: usize = 4;
+pub const OCB_MAX_TAG_LEN: usize = 16;
+
+const OCB_BLOCK_LEN: usize = 16;
+
+pub(crate) struct OcbMode {
+    strategy: Box<dyn EncryptionStrategy>,
+    l_star: u128,
+    l_dollar: u128,
+    l: Vec<u128>,
+    initial_offset: u128,
+    tag_len: usize,
+}
+
+impl OcbMode {
+    pub fn new(strategy: Box<dyn EncryptionStrategy>, nonce: &[u8], tag_len: usize) -> Self {
+        assert!(
+            strategy.block_size() == AES_BLOCK_CHUNKS,
+            "OCB: Cipher block size must be 128 bits"
+        );
+
+        assert!(
+            (OCB_MIN_NONCE_LEN..=OCB_MAX_NONCE_LEN).contains(&nonce.len()),
+            "OCB: Nonce must be between 1 and 15 bytes long"
+        );
+
+        assert!(
+            (OCB_MIN_TAG_LEN..=OCB_MAX_TAG_LEN).contains(&tag_len),
+            "OCB: Tag must be between 4 and 16 bytes long"
+        );
+
+        let l_star = encipher(strategy.as_ref(), 0);
+        let l_dollar = double(l_star);
+
+        // L(i) is only ever indexed by the number of trailing zeros of a block index
+        let mut l = vec![double(l_dollar)];
+        for _ in 1..usize::BITS {
+            l.push(double(l[l.len() - 1]));
+        }
+
+        Self {
+            initial_offset: initial_offset(strategy.as_ref(), nonce, tag_len),
+            strategy,
+            l_star,
+            l_dollar,
+            l,
+            tag_len,
+        }
+    }
+
+    fn hash(&self, aad: &[u8]) -> u128 {
+        let mut sum = 0;
+        let mut offset = 0;
+
+        let blocks = aad.chunks_exact(OCB_BLOCK_LEN);
+        let partial_block = blocks.remainder();
+
+        for (index, block) in blocks.enumerate() {
+            offset ^= self.l[(index + 1).trailing_zeros() as usize];
+            sum ^= encipher(self.strategy.as_ref(), to_block(block) ^ offset);
+        }
+
+        if !partial_block.is_empty() {
+            offset ^= self.l_star;
+            sum ^= encipher(self.strategy.as_ref(), pad(partial_block) ^ offset);
+        }
+
+        sum
+    }
+
+    // One pass over the data, returning the output and the full-length tag
+    fn process(&self, aad: &[u8], input: &[u8], decrypt: bool) -> (Vec<u8>, u128) {
+        let strategy = self.strategy.as_ref();
+        let mut offset = self.initial_offset;
+        let mut checksum = 0;
+        let mut output = Vec::with_capacity(input.len());
+
+        let blocks = input.chunks_exact(OCB_BLOCK_LEN);
+        let partial_block = blocks.remainder();
+
+        for (index, block) in blocks.enumerate() {
+            offset ^= self.l[(index + 1).trailing_zeros() as usize];
+
+            let (plaintext_block, output_block) = match decrypt {
+                true => {
+                    let plaintext_block = offset ^ decipher(strategy, to_block(block) ^ offset);
+
+                    (plaintext_block, plaintext_block)
+                }
+                false => {
+                    let plaintext_block = to_block(block);
+
+                    (
+                        plaintext_block,
+                        offset ^ encipher(strategy, plaintext_block ^ offset),
+                    )
+                }
+            };
+
+            output.extend_from_slice(&output_block.to_be_bytes());
+            checksum ^= plaintext_block;
+        }
+
+        if !partial_block.is_empty() {
+            offset ^= self.l_star;
+
+            let output_block = xor_bytes(partial_block, &encipher(strategy, offset).to_be_bytes());
+
+            checksum ^= match decrypt {
+                true => pad(&output_block),
+                false => pad(partial_block),
+            };
+            output.extend_from_slice(&output_block);
+        }
+
+        let tag = encipher(strategy, checksum ^ offset ^ self.l_dollar) ^ self.hash(aad);
+
+        (output, tag)
+    }
+}
+
+impl AuthenticatedMode for OcbMode {
+    fn seal(&mut self, aad: &[u8], plaintext: &[u8]) -> Vec<u8> {
+        let (mut ciphertext, tag) = self.process(aad, plaintext, false);
+
+        ciphertext.extend_from_slice(&tag.to_be_bytes()[..self.tag_len]);
+
+        ciphertext
+    }
+
+    fn open(&mut self, aad: &[u8], ciphertext: &[u8]) -> Option<Vec<u8>> {
+        let tag_position = ciphertext.len().checked_sub(self.tag_len)?;
+        let (ciphertext, tag) = ciphertext.split_at(tag_position);
+
+        let (plaintext, expected_tag) = self.process(aad, ciphertext, true);
+
+        match constant_time_eq(&expected_tag.to_be_bytes()[..self.tag_len], tag) {
+            true => Some(plaintext),
+            false => None,
+        }
+    }
+}
+
+// Offset(0) is a 128-bit window into E(K, formatted nonce) stretched to 192 bits
+fn initial_offset(strategy: &dyn EncryptionStrategy, nonce: &[u8], tag_len: usize) -> u128 {
+    let mut nonce_block = [0u8; OCB_BLOCK_LEN];
+
+    nonce_block[OCB_BLOCK_LEN - nonce.len()..].copy_from_slice(nonce);
+    nonce_block[OCB_BLOCK_LEN - 1 - nonce.len()] |= 0x01;
+    nonce_block[0] |= (((tag_len * u8::BITS as usize) % 128) as u8) << 1;
+
+    let nonce_block = u128::from_be_bytes(nonce_block);
+    let bottom = (nonce_block & 0x3F) as u32;
+
+    let ktop = encipher(strategy, nonce_block & !0x3F);
+    let stretch_tail = ((ktop >> 64) ^ (ktop >> 56)) as u64;
+
+    match bottom {
+        0 => ktop,
+        _ => (ktop << bottom) | (stretch_tail >> (64 - bottom)) as u128,
+    }
+}
+
+fn encipher(strategy: &dyn EncryptionStrategy, block: u128) -> u128 {
+    to_block(&encrypt_block(strategy, &block.to_be_bytes()))
+}
+
+fn decipher(strategy: &dyn EncryptionStrategy, block: u128) -> u128 {
+    to_block(&decrypt_block(strategy, &block.to_be_bytes()))
+}
+
+fn to_block(bytes: &[u8]) -> u128 {
+    u128::from_be_bytes(
+        bytes
+            .try_into()
+            // Blocks are either whole chunks of the input or AES outputs, so this will never happen
+            .expect("🙀🧨 OCB: Block not 128 bits long. This was not supposed to happen."),
+    )
+}
+
+fn pad(partial_block: &[u8]) -> u128 {
+    let mut block = [0u8; OCB_BLOCK_LEN];
+
+    block[..partial_block.len()].copy_from_slice(partial_block);
+    block[partial_block.len()] = 0x80;
+
+    u128::from_be_bytes(block)
+}
+
+// Same doubling as CMAC subkeys, kept on u128 as OCB works on whole blocks
+fn double(block: u128) -> u128 {
+    (block << 1) ^ ((block >> 127) * 0x87)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        mode::{tests::*, to_chunks},
+        DataChunk, EncryptionSchemes,
+    };
+
+    use super::*;
+
+    // RFC 7253, Appendix A, the nonce's last byte varies between samples
+    const RFC_KEY_128_BITS: [DataChunk; 2] = [
+        [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07],
+        [0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E, 0x0F],
+    ];
+
+    const RFC_NONCE_PREFIX: [u8; 11] = [
+        0xBB, 0xAA, 0x99, 0x88, 0x77, 0x66, 0x55, 0x44, 0x33, 0x22, 0x11,
+    ];
+
+    const RFC_DATA: [u8; 40] = [
+        0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E,
+        0x0F, 0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18, 0x19, 0x1A, 0x1B, 0x1C, 0x1D,
+        0x1E, 0x1F, 0x20, 0x21, 0x22, 0x23, 0x24, 0x25, 0x26, 0x27,
+    ];
+
+    fn helper_get_mode(key: &[DataChunk], nonce_suffix: u8, tag_len: usize) -> OcbMode {
+        let cipher = match key.len() {
+            2 => EncryptionSchemes::Aes128,
+            3 => EncryptionSchemes::Aes192,
+            _ => EncryptionSchemes::Aes256,
+        };
+        let nonce = [RFC_NONCE_PREFIX.as_slice(), &[nonce_suffix]].concat();
+
+        OcbMode::new(cipher.strategy(key).unwrap(), &nonce, tag_len)
+    }
+
+    #[test]
+    fn seal_empty() {
+        let left = helper_get_mode(&RFC_KEY_128_BITS, 0x00, 16).seal(&[], &[]);
+        let right = [
+            0x78, 0x54, 0x07, 0xBF, 0xFF, 0xC8, 0xAD, 0x9E, 0xDC, 0xC5, 0x52, 0x0A, 0xC9, 0x11,
+            0x1E, 0xE6,
+        ];
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn seal_partial_block() {
+        let left =
+            helper_get_mode(&RFC_KEY_128_BITS, 0x01, 16).seal(&RFC_DATA[..8], &RFC_DATA[..8]);
+        let right = [
+            0x68, 0x20, 0xB3, 0x65, 0x7B, 0x6F, 0x61, 0x5A, 0x57, 0x25, 0xBD, 0xA0, 0xD3, 0xB4,
+            0xEB, 0x3A, 0x25, 0x7C, 0x9A, 0xF1, 0xF8, 0xF0, 0x30, 0x09,
+        ];
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn seal_aad_only() {
+        let left = helper_get_mode(&RFC_KEY_128_BITS, 0x02, 16).seal(&RFC_DATA[..8], &[]);
+        let right = [
+            0x81, 0x01, 0x7F, 0x82, 0x03, 0xF0, 0x81, 0x27, 0x71, 0x52, 0xFA, 0xDE, 0x69, 0x4A,
+            0x0A, 0x00,
+        ];
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn seal_plaintext_only() {
+        let left = helper_get_mode(&RFC_KEY_128_BITS, 0x03, 16).seal(&[], &RFC_DATA[..8]);
+        let right = [
+            0x45, 0xDD, 0x69, 0xF8, 0xF5, 0xAA, 0xE7, 0x24, 0x14, 0x05, 0x4C, 0xD1, 0xF3, 0x5D,
+            0x82, 0x76, 0x0B, 0x2C, 0xD0, 0x0D, 0x2F, 0x99, 0xBF, 0xA9,
+        ];
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn seal_full_block() {
+        let left =
+            helper_get_mode(&RFC_KEY_128_BITS, 0x04, 16).seal(&RFC_DATA[..16], &RFC_DATA[..16]);
+        let right = [
+            0x57, 0x1D, 0x53, 0x5B, 0x60, 0xB2, 0x77, 0x18, 0x8B, 0xE5, 0x14, 0x71, 0x70, 0xA9,
+            0xA2, 0x2C, 0x3A, 0xD7, 0xA4, 0xFF, 0x38, 0x35, 0xB8, 0xC5, 0x70, 0x1C, 0x1C, 0xCE,
+            0xC8, 0xFC, 0x33, 0x58,
+        ];
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn seal_multiple_blocks() {
+        let left = helper_get_mode(&RFC_KEY_128_BITS, 0x05, 16).seal(&RFC_DATA, &RFC_DATA);
+        let right = [
+            0x9F, 0xFD, 0x50, 0xF1, 0x47, 0x69, 0x4C, 0xDE, 0x96, 0x54, 0xEC, 0x6E, 0x7C, 0xE7,
+            0xD4, 0x0A, 0xCD, 0x54, 0x19, 0xAB, 0x0F, 0x4C, 0xD1, 0x09, 0xF7, 0x7A, 0x72, 0x2A,
+            0x52, 0x5E, 0x68, 0xF9, 0xEB, 0x8F, 0x90, 0x26, 0x6C, 0x3A, 0xBC, 0x8C, 0x71, 0xC1,
+            0x6F, 0xFB, 0xDE, 0x76, 0x82, 0x54, 0x81, 0xF2, 0x3F, 0xA8, 0xB1, 0xBE, 0xF9, 0x9E,
+        ];
+
+        assert_eq!(left, right);
+    }
+
+    // RFC 7253, Appendix A, the 96-bit tag sample with key 0F0E..00
+    #[test]
+    fn seal_96_bit_tag() {
+        let mut key = RFC_KEY_128_BITS.concat();
+        key.reverse();
+
+        let left = helper_get_mode(&to_chunks(&key), 0x0D, 12).seal(&RFC_DATA, &RFC_DATA);
+        let right = [
+            0x17, 0x92, 0xA4, 0xE3, 0x1E, 0x07, 0x55, 0xFB, 0x03, 0xE3, 0x1B, 0x22, 0x11, 0x6E,
+            0x6C, 0x2D, 0xDF, 0x9E, 0xFD, 0x6E, 0x33, 0xD5, 0x36, 0xF1, 0xA0, 0x12, 0x4B, 0x0A,
+            0x55, 0xBA, 0xE8, 0x84, 0xED, 0x93, 0x48, 0x15, 0x29, 0xC7, 0x6B, 0x6A, 0xD0, 0xC5,
+            0x15, 0xF4, 0xD1, 0xCD, 0xD4, 0xFD, 0xAC, 0x4F, 0x02, 0xAA,
+        ];
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn seal_aes192() {
+        let key = to_chunks(&(0..24).collect::<Vec<u8>>());
+
+        let left = helper_get_mode(&key, 0x0F, 16).seal(&RFC_DATA, &RFC_DATA);
+        let right = [
+            0x0D, 0xA6, 0x35, 0x83, 0xC1, 0xDF, 0x94, 0x74, 0x68, 0xD8, 0x99, 0xFB, 0x2C, 0x65,
+            0xDC, 0x95, 0x46, 0x1A, 0xC5, 0x75, 0xFD, 0x01, 0x0A, 0x47, 0x09, 0x5E, 0x35, 0x1C,
+            0x9A, 0xEF, 0x37, 0x2A, 0xB5, 0x84, 0x65, 0x4F, 0x3B, 0x09, 0xBA, 0xA2, 0x77, 0xFE,
+            0x06, 0xC0, 0x58, 0xD3, 0x61, 0xBF, 0x4F, 0xE5, 0xF8, 0x7A, 0x94, 0x7E, 0xA3, 0x61,
+        ];
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn seal_aes256() {
+        let key = to_chunks(&(0..32).collect::<Vec<u8>>());
+
+        let left = helper_get_mode(&key, 0x0F, 16).seal(&RFC_DATA, &RFC_DATA);
+        let right = [
+            0x38, 0xA6, 0xCB, 0xA4, 0xD0, 0xD8, 0xD9, 0xE9, 0x88, 0x11, 0x3C, 0x62, 0xE8, 0x02,
+            0x48, 0xDD, 0xCD, 0x2A, 0xE8, 0x4F, 0xBF, 0xE1, 0x8B, 0x22, 0x11, 0xA2, 0xB5, 0x5F,
+            0xAA, 0x83, 0xA6, 0xF2, 0x79, 0x79, 0xC6, 0xEF, 0x72, 0x11, 0x5D, 0xD1, 0x7A, 0xDA,
+            0xD7, 0xFC, 0xA3, 0x9B, 0xD6, 0x1C, 0xE6, 0x5A, 0x18, 0xEE, 0x6C, 0x19, 0xD5, 0xE6,
+        ];
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn seal_short_nonce() {
+        let mut mode = OcbMode::new(
+            EncryptionSchemes::Aes128
+                .strategy(&RFC_KEY_128_BITS)
+                .unwrap(),
+            &RFC_DATA[..7],
+            16,
+        );
+
+        let left = mode.seal(&RFC_DATA, &RFC_DATA);
+        let right = [
+            0x83, 0x5D, 0xD7, 0x14, 0xDE, 0x05, 0x7D, 0x05, 0x5D, 0x6C, 0xF3, 0x8D, 0x7C, 0xCA,
+            0x75, 0x3D, 0x24, 0x4B, 0x53, 0x19, 0x24, 0x45, 0x09, 0xDD, 0x03, 0xE1, 0x7B, 0xEA,
+            0x96, 0x6E, 0x21, 0x05, 0x52, 0xCF, 0x9B, 0xC5, 0x8F, 0x07, 0x89, 0x32, 0x64, 0x9D,
+            0x20, 0x44, 0x0F, 0x5A, 0x40, 0x92, 0x2F, 0x1A, 0xC9, 0x74, 0x78, 0x4A, 0xE6, 0x55,
+        ];
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn open_multiple_blocks() {
+        let ciphertext = helper_get_mode(&RFC_KEY_128_BITS, 0x05, 16).seal(&RFC_DATA, &RFC_DATA);
+
+        let left = helper_get_mode(&RFC_KEY_128_BITS, 0x05, 16).open(&RFC_DATA, &ciphertext);
+        let right = Some(RFC_DATA.to_vec());
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn open_tampered_partial_block() {
+        let mut ciphertext =
+            helper_get_mode(&RFC_KEY_128_BITS, 0x05, 16).seal(&RFC_DATA, &RFC_DATA);
+        ciphertext[35] ^= 0x01;
+
+        let left = helper_get_mode(&RFC_KEY_128_BITS, 0x05, 16).open(&RFC_DATA, &ciphertext);
+
+        assert!(left.is_none());
+    }
+
+    #[test]
+    fn open_wrong_aad() {
+        let ciphertext = helper_get_mode(&RFC_KEY_128_BITS, 0x05, 16).seal(&RFC_DATA, &RFC_DATA);
+
+        let left = helper_get_mode(&RFC_KEY_128_BITS, 0x05, 16).open(&RFC_DATA[1..], &ciphertext);
+
+        assert!(left.is_none());
+    }
+
+    #[test]
+    fn double_reduces() {
+        let left = double(1 << 127 | 1);
+        let right = 0x87 ^ 0x02;
+
+        assert_eq!(left, right);
+    }
+
+    #[should_panic]
+    #[test]
+    fn nonce_too_long() {
+        OcbMode::new(
+            EncryptionSchemes::Aes128
+                .strategy(&RFC_KEY_128_BITS)
+                .unwrap(),
+            &RFC_DATA[..16],
+            16,
+        );
+    }
+
+    #[should_panic]
+    #[test]
+    fn tdes_not_supported() {
+        OcbMode::new(
+            EncryptionSchemes::Tdes
+                .strategy(&[KEY_64_BITS[0]; 3])
+                .unwrap(),
+            &RFC_NONCE_PREFIX,
+            8,
+        );
+    }
+}
@@ -0,0 +1,115 @@
+use crate::{error::Result, strategy::EncryptionStrategy, DataChunk};
+
+use super::{check_iv_len, encrypt_block, process_bytes, EncryptionMode};
+
+pub(super) struct OfbMode {
+    strategy: Box<dyn EncryptionStrategy>,
+    register: Vec<u8>,
+    position: usize,
+}
+
+impl OfbMode {
+    pub fn new(strategy: Box<dyn EncryptionStrategy>, iv: &[DataChunk]) -> Result<Self> {
+        check_iv_len(strategy.as_ref(), iv)?;
+
+        let register = iv.concat();
+
+        Ok(Self {
+            strategy,
+            position: register.len(),
+            register,
+        })
+    }
+
+    // Keystream is independent of the data, so both directions are the same operation
+    fn apply_keystream(&mut self, data: &mut [u8]) {
+        for byte in data.iter_mut() {
+            if self.position == self.register.len() {
+                self.register = encrypt_block(self.strategy.as_ref(), &self.register);
+                self.position = 0;
+            }
+
+            *byte ^= self.register[self.position];
+            self.position += 1;
+        }
+    }
+}
+
+impl EncryptionMode for OfbMode {
+    fn encrypt(&mut self, plaintext: &[DataChunk]) -> Result<Vec<DataChunk>> {
+        Ok(process_bytes(plaintext, |bytes| {
+            self.apply_keystream(bytes)
+        }))
+    }
+
+    fn decrypt(&mut self, ciphertext: &[DataChunk]) -> Result<Vec<DataChunk>> {
+        Ok(process_bytes(ciphertext, |bytes| {
+            self.apply_keystream(bytes)
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{mode::tests::*, EncryptionModes, EncryptionSchemes};
+
+    use super::*;
+
+    const NIST_OFB_AES128_CIPHERTEXT: [DataChunk; 8] = [
+        [0x3B, 0x3F, 0xD9, 0x2E, 0xB7, 0x2D, 0xAD, 0x20],
+        [0x33, 0x34, 0x49, 0xF8, 0xE8, 0x3C, 0xFB, 0x4A],
+        [0x77, 0x89, 0x50, 0x8D, 0x16, 0x91, 0x8F, 0x03],
+        [0xF5, 0x3C, 0x52, 0xDA, 0xC5, 0x4E, 0xD8, 0x25],
+        [0x97, 0x40, 0x05, 0x1E, 0x9C, 0x5F, 0xEC, 0xF6],
+        [0x43, 0x44, 0xF7, 0xA8, 0x22, 0x60, 0xED, 0xCC],
+        [0x30, 0x4C, 0x65, 0x28, 0xF6, 0x59, 0xC7, 0x78],
+        [0x66, 0xA5, 0x10, 0xD9, 0xC1, 0xD6, 0xAE, 0x5E],
+    ];
+
+    fn helper_get_mode() -> OfbMode {
+        OfbMode::new(
+            EncryptionSchemes::Aes128
+                .strategy(&NIST_KEY_128_BITS)
+                .unwrap(),
+            &NIST_IV,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn encrypt() {
+        let left = helper_get_mode().encrypt(&NIST_PLAINTEXT).unwrap();
+        let right = NIST_OFB_AES128_CIPHERTEXT;
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn decrypt() {
+        let left = helper_get_mode()
+            .decrypt(&NIST_OFB_AES128_CIPHERTEXT)
+            .unwrap();
+        let right = NIST_PLAINTEXT;
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn encrypt_odd_length_across_calls() {
+        let mut mode = helper_get_mode();
+        let mut data = NIST_PLAINTEXT.concat();
+
+        mode.apply_keystream(&mut data[..7]);
+        mode.apply_keystream(&mut data[7..29]);
+
+        let left = &data[..29];
+        let right = &NIST_OFB_AES128_CIPHERTEXT.concat()[..29];
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn des_encrypt_and_decrypt() {
+        helper_des_round_trip(EncryptionModes::Ofb);
+    }
+}
@@ -0,0 +1,121 @@
+use crate::{error::Result, strategy::EncryptionStrategy, DataChunk};
+
+use super::{check_iv_len, xor_chunks, EncryptionMode};
+
+pub(super) struct PcbcMode {
+    strategy: Box<dyn EncryptionStrategy>,
+    feedback: Vec<DataChunk>,
+}
+
+impl PcbcMode {
+    pub fn new(strategy: Box<dyn EncryptionStrategy>, iv: &[DataChunk]) -> Result<Self> {
+        check_iv_len(strategy.as_ref(), iv)?;
+
+        Ok(Self {
+            strategy,
+            feedback: iv.to_vec(),
+        })
+    }
+}
+
+impl EncryptionMode for PcbcMode {
+    fn encrypt(&mut self, plaintext: &[DataChunk]) -> Result<Vec<DataChunk>> {
+        let mut ciphertext_blocks: Vec<DataChunk> = Vec::with_capacity(plaintext.len());
+
+        for plaintext_block in plaintext.chunks(self.strategy.block_size()) {
+            let ciphertext_block = self
+                .strategy
+                .encrypt(&xor_chunks(plaintext_block, &self.feedback))?;
+
+            ciphertext_blocks.extend_from_slice(&ciphertext_block);
+            self.feedback = xor_chunks(plaintext_block, &ciphertext_block);
+        }
+
+        Ok(ciphertext_blocks)
+    }
+
+    fn decrypt(&mut self, ciphertext: &[DataChunk]) -> Result<Vec<DataChunk>> {
+        let mut plaintext_blocks: Vec<DataChunk> = Vec::with_capacity(ciphertext.len());
+
+        for ciphertext_block in ciphertext.chunks(self.strategy.block_size()) {
+            let plaintext_block =
+                xor_chunks(&self.strategy.decrypt(ciphertext_block)?, &self.feedback);
+
+            plaintext_blocks.extend_from_slice(&plaintext_block);
+            self.feedback = xor_chunks(&plaintext_block, ciphertext_block);
+        }
+
+        Ok(plaintext_blocks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{mode::tests::*, EncryptionModes, EncryptionSchemes};
+
+    use super::*;
+
+    // No NIST vectors exist for PCBC, these follow C(i) = E(P(i) ^ P(i-1) ^ C(i-1))
+    const PCBC_AES128_CIPHERTEXT: [DataChunk; 8] = [
+        [0x76, 0x49, 0xAB, 0xAC, 0x81, 0x19, 0xB2, 0x46],
+        [0xCE, 0xE9, 0x8E, 0x9B, 0x12, 0xE9, 0x19, 0x7D],
+        [0x9E, 0x8B, 0xAF, 0xF1, 0x2A, 0xD5, 0x27, 0x0A],
+        [0x0D, 0x1E, 0xEF, 0x93, 0xD7, 0x03, 0x79, 0x94],
+        [0x57, 0x00, 0xB3, 0x98, 0x03, 0x77, 0x9F, 0xA3],
+        [0x5A, 0x3C, 0x60, 0x0A, 0x49, 0xA1, 0x63, 0xC0],
+        [0x33, 0xAE, 0x19, 0x9F, 0x27, 0x37, 0x9F, 0x21],
+        [0xBE, 0x6D, 0xD5, 0x7D, 0x29, 0x5C, 0xC8, 0x7D],
+    ];
+
+    fn helper_get_mode() -> PcbcMode {
+        PcbcMode::new(
+            EncryptionSchemes::Aes128
+                .strategy(&NIST_KEY_128_BITS)
+                .unwrap(),
+            &NIST_IV,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn encrypt() {
+        let left = helper_get_mode().encrypt(&NIST_PLAINTEXT).unwrap();
+        let right = PCBC_AES128_CIPHERTEXT;
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn decrypt() {
+        let left = helper_get_mode().decrypt(&PCBC_AES128_CIPHERTEXT).unwrap();
+        let right = NIST_PLAINTEXT;
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn decrypt_chains_across_calls() {
+        let mut mode = helper_get_mode();
+
+        let mut left = mode.decrypt(&PCBC_AES128_CIPHERTEXT[..2]).unwrap();
+        left.extend(mode.decrypt(&PCBC_AES128_CIPHERTEXT[2..]).unwrap());
+        let right = NIST_PLAINTEXT;
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn corrupted_block_propagates() {
+        let mut ciphertext = PCBC_AES128_CIPHERTEXT;
+        ciphertext[0][0] ^= 0x01;
+
+        let plaintext = helper_get_mode().decrypt(&ciphertext).unwrap();
+
+        assert_ne!(plaintext[6..], NIST_PLAINTEXT[6..]);
+    }
+
+    #[test]
+    fn des_encrypt_and_decrypt() {
+        helper_des_round_trip(EncryptionModes::Pcbc);
+    }
+}
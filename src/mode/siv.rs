@@ -0,0 +1,344 @@
+use crate::{
+    strategy::{EncryptionStrategy, AES_BLOCK_CHUNKS},
+    BLOCK_CHUNK_SIZE,
+};
+
+use super::{
+    cmac::{cmac, dbl},
+    constant_time_eq, to_chunks, xor_bytes, AuthenticatedMode, CtrMode,
+};
+
+pub const SIV_TAG_LEN: usize = 16;
+
+const SIV_BLOCK_LEN: usize = 16;
+const SIV_COUNTER_BITS: usize = 128;
+
+pub(crate) struct SivMode {
+    mac_strategy: Box<dyn EncryptionStrategy>,
+    ctr: CtrMode,
+    nonce: Vec<u8>,
+}
+
+impl SivMode {
+    // An empty nonce gives deterministic encryption
+    pub fn new(
+        mac_strategy: Box<dyn EncryptionStrategy>,
+        ctr_strategy: Box<dyn EncryptionStrategy>,
+        nonce: &[u8],
+    ) -> Self {
+        assert!(
+            mac_strategy.block_size() == AES_BLOCK_CHUNKS
+                && ctr_strategy.block_size() == AES_BLOCK_CHUNKS,
+            "SIV: Cipher block size must be 128 bits"
+        );
+
+        Self {
+            mac_strategy,
+            // The counter block is only known once the synthetic IV has been computed
+            ctr: CtrMode::new(
+                ctr_strategy,
+                &[[0u8; BLOCK_CHUNK_SIZE]; AES_BLOCK_CHUNKS],
+                SIV_COUNTER_BITS,
+            )
+            .expect("🙀🧨 SIV: Counter layout rejected. This was not supposed to happen."),
+            nonce: nonce.to_vec(),
+        }
+    }
+
+    // S2V over the AAD and nonce, each only when present, followed by the plaintext
+    fn s2v(&self, aad: &[u8], plaintext: &[u8]) -> Vec<u8> {
+        let mac_strategy = self.mac_strategy.as_ref();
+        let mut digest = cmac(mac_strategy, &[0u8; SIV_BLOCK_LEN]);
+
+        for component in [aad, &self.nonce].into_iter().filter(|c| !c.is_empty()) {
+            digest = xor_bytes(&dbl(&digest), &cmac(mac_strategy, component));
+        }
+
+        let last_component = match plaintext.len() >= SIV_BLOCK_LEN {
+            true => {
+                let (head, tail) = plaintext.split_at(plaintext.len() - SIV_BLOCK_LEN);
+
+                [head, &xor_bytes(tail, &digest)].concat()
+            }
+            false => {
+                let mut padded = plaintext.to_vec();
+                padded.push(0x80);
+                padded.resize(SIV_BLOCK_LEN, 0);
+
+                xor_bytes(&dbl(&digest), &padded)
+            }
+        };
+
+        cmac(mac_strategy, &last_component)
+    }
+
+    fn apply_keystream(&mut self, synthetic_iv: &[u8], data: &mut [u8]) {
+        let mut counter_block = synthetic_iv.to_vec();
+
+        // Clearing bit 31 of the two low words lets 64-bit counter arithmetic never overflow
+        counter_block[8] &= 0x7F;
+        counter_block[12] &= 0x7F;
+
+        // The synthetic IV is a CMAC output, so it is always one 128-bit block
+        self.ctr
+            .reset(&to_chunks(&counter_block))
+            .expect("🙀🧨 SIV: Counter block not 128 bits. This was not supposed to happen.");
+        // A whole-block counter outlasts any byte offset
+        self.ctr
+            .apply_keystream(data)
+            .expect("🙀🧨 SIV: Counter space exhausted. This was not supposed to happen.");
+    }
+}
+
+impl AuthenticatedMode for SivMode {
+    // Output is the synthetic IV followed by the ciphertext, as in RFC 5297
+    fn seal(&mut self, aad: &[u8], plaintext: &[u8]) -> Vec<u8> {
+        let synthetic_iv = self.s2v(aad, plaintext);
+        let mut ciphertext = plaintext.to_vec();
+
+        self.apply_keystream(&synthetic_iv, &mut ciphertext);
+
+        [synthetic_iv, ciphertext].concat()
+    }
+
+    fn open(&mut self, aad: &[u8], ciphertext: &[u8]) -> Option<Vec<u8>> {
+        if ciphertext.len() < SIV_TAG_LEN {
+            return None;
+        }
+
+        let (synthetic_iv, ciphertext) = ciphertext.split_at(SIV_TAG_LEN);
+        let mut plaintext = ciphertext.to_vec();
+
+        self.apply_keystream(synthetic_iv, &mut plaintext);
+
+        match constant_time_eq(&self.s2v(aad, &plaintext), synthetic_iv) {
+            true => Some(plaintext),
+            false => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{mode::tests::*, DataChunk, EncryptionSchemes};
+
+    use super::*;
+
+    // RFC 5297, Appendix A.1
+    const RFC_KEY_256_BITS: [DataChunk; 4] = [
+        [0xFF, 0xFE, 0xFD, 0xFC, 0xFB, 0xFA, 0xF9, 0xF8],
+        [0xF7, 0xF6, 0xF5, 0xF4, 0xF3, 0xF2, 0xF1, 0xF0],
+        [0xF0, 0xF1, 0xF2, 0xF3, 0xF4, 0xF5, 0xF6, 0xF7],
+        [0xF8, 0xF9, 0xFA, 0xFB, 0xFC, 0xFD, 0xFE, 0xFF],
+    ];
+
+    const RFC_AAD: [u8; 24] = [
+        0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18, 0x19, 0x1A, 0x1B, 0x1C, 0x1D, 0x1E,
+        0x1F, 0x20, 0x21, 0x22, 0x23, 0x24, 0x25, 0x26, 0x27,
+    ];
+
+    const RFC_PLAINTEXT: [u8; 14] = [
+        0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xAA, 0xBB, 0xCC, 0xDD, 0xEE,
+    ];
+
+    const RFC_CIPHERTEXT: [u8; 30] = [
+        0x85, 0x63, 0x2D, 0x07, 0xC6, 0xE8, 0xF3, 0x7F, 0x95, 0x0A, 0xCD, 0x32, 0x0A, 0x2E, 0xCC,
+        0x93, 0x40, 0xC0, 0x2B, 0x96, 0x90, 0xC4, 0xDC, 0x04, 0xDA, 0xEF, 0x7F, 0x6A, 0xFE, 0x5C,
+    ];
+
+    // RFC 5297, Appendix A.2 inputs, with the first AAD component and the nonce only
+    const NONCE_KEY_256_BITS: [DataChunk; 4] = [
+        [0x7F, 0x7E, 0x7D, 0x7C, 0x7B, 0x7A, 0x79, 0x78],
+        [0x77, 0x76, 0x75, 0x74, 0x73, 0x72, 0x71, 0x70],
+        [0x40, 0x41, 0x42, 0x43, 0x44, 0x45, 0x46, 0x47],
+        [0x48, 0x49, 0x4A, 0x4B, 0x4C, 0x4D, 0x4E, 0x4F],
+    ];
+
+    const NONCE_AAD: [u8; 40] = [
+        0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xAA, 0xBB, 0xCC, 0xDD, 0xEE,
+        0xFF, 0xDE, 0xAD, 0xDA, 0xDA, 0xDE, 0xAD, 0xDA, 0xDA, 0xFF, 0xEE, 0xDD, 0xCC, 0xBB, 0xAA,
+        0x99, 0x88, 0x77, 0x66, 0x55, 0x44, 0x33, 0x22, 0x11, 0x00,
+    ];
+
+    const NONCE: [u8; 16] = [
+        0x09, 0xF9, 0x11, 0x02, 0x9D, 0x74, 0xE3, 0x5B, 0xD8, 0x41, 0x56, 0xC5, 0x63, 0x56, 0x88,
+        0xC0,
+    ];
+
+    const NONCE_PLAINTEXT: [u8; 47] = [
+        0x74, 0x68, 0x69, 0x73, 0x20, 0x69, 0x73, 0x20, 0x73, 0x6F, 0x6D, 0x65, 0x20, 0x70, 0x6C,
+        0x61, 0x69, 0x6E, 0x74, 0x65, 0x78, 0x74, 0x20, 0x74, 0x6F, 0x20, 0x65, 0x6E, 0x63, 0x72,
+        0x79, 0x70, 0x74, 0x20, 0x75, 0x73, 0x69, 0x6E, 0x67, 0x20, 0x53, 0x49, 0x56, 0x2D, 0x41,
+        0x45, 0x53,
+    ];
+
+    fn helper_get_mode(cipher: EncryptionSchemes, key: &[DataChunk], nonce: &[u8]) -> SivMode {
+        let (mac_key, ctr_key) = key.split_at(key.len() / 2);
+
+        SivMode::new(
+            cipher.strategy(mac_key).unwrap(),
+            cipher.strategy(ctr_key).unwrap(),
+            nonce,
+        )
+    }
+
+    #[test]
+    fn seal_deterministic() {
+        let left = helper_get_mode(EncryptionSchemes::Aes128, &RFC_KEY_256_BITS, &[])
+            .seal(&RFC_AAD, &RFC_PLAINTEXT);
+        let right = RFC_CIPHERTEXT;
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn open_deterministic() {
+        let left = helper_get_mode(EncryptionSchemes::Aes128, &RFC_KEY_256_BITS, &[])
+            .open(&RFC_AAD, &RFC_CIPHERTEXT);
+        let right = Some(RFC_PLAINTEXT.to_vec());
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn seal_is_deterministic_without_nonce() {
+        let left = helper_get_mode(EncryptionSchemes::Aes128, &RFC_KEY_256_BITS, &[])
+            .seal(&RFC_AAD, &RFC_PLAINTEXT);
+        let right = helper_get_mode(EncryptionSchemes::Aes128, &RFC_KEY_256_BITS, &[])
+            .seal(&RFC_AAD, &RFC_PLAINTEXT);
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn seal_with_nonce() {
+        let left = helper_get_mode(EncryptionSchemes::Aes128, &NONCE_KEY_256_BITS, &NONCE)
+            .seal(&NONCE_AAD, &NONCE_PLAINTEXT);
+        let right = [
+            0x85, 0x82, 0x5E, 0x22, 0xE9, 0x0C, 0xF2, 0xDD, 0xDA, 0x2C, 0x54, 0x8D, 0xC7, 0xC1,
+            0xB6, 0x31, 0x0D, 0xCD, 0xAC, 0xA0, 0xCE, 0xBF, 0x9D, 0xC6, 0xCB, 0x90, 0x58, 0x3F,
+            0x5B, 0xF1, 0x50, 0x6E, 0x02, 0xCD, 0x48, 0x83, 0x2B, 0x00, 0xE4, 0xE5, 0x98, 0xB2,
+            0xB2, 0x2A, 0x53, 0xE6, 0x19, 0x9D, 0x4D, 0xF0, 0xC1, 0x66, 0x6A, 0x35, 0xA0, 0x43,
+            0x3B, 0x25, 0x0D, 0xC1, 0x34, 0xD7, 0x76,
+        ];
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn seal_empty_plaintext() {
+        let left =
+            helper_get_mode(EncryptionSchemes::Aes128, &RFC_KEY_256_BITS, &[]).seal(&RFC_AAD, &[]);
+        let right = [
+            0xB9, 0xD5, 0xCC, 0x97, 0x05, 0x4D, 0xCD, 0x3F, 0x6D, 0xFD, 0xA6, 0x29, 0xD4, 0xF4,
+            0xD3, 0x13,
+        ];
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn seal_aes192() {
+        let key: Vec<DataChunk> = to_chunks(&(0..48).collect::<Vec<u8>>());
+
+        let left = helper_get_mode(EncryptionSchemes::Aes192, &key, &[])
+            .seal(&NONCE_AAD, &NONCE_PLAINTEXT);
+        let right = [
+            0xBA, 0x49, 0x9D, 0x2F, 0x4F, 0x33, 0x9B, 0x0B, 0xEE, 0xB0, 0x6A, 0xCA, 0xB3, 0xED,
+            0x65, 0x93, 0x9B, 0xC2, 0xA5, 0xC8, 0xC7, 0xC1, 0x3C, 0xB0, 0x8E, 0x7E, 0xFD, 0x30,
+            0x58, 0xBC, 0x69, 0x24, 0x1B, 0xBC, 0xFD, 0x09, 0x35, 0x2D, 0x50, 0x64, 0x22, 0x5B,
+            0x48, 0x85, 0xD1, 0x68, 0xB9, 0x86, 0x7E, 0x82, 0xD8, 0x3D, 0xB6, 0x1B, 0x51, 0x03,
+            0xB0, 0xA3, 0xD0, 0x9F, 0x5F, 0x19, 0xAD,
+        ];
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn seal_aes256() {
+        let key: Vec<DataChunk> = to_chunks(&(0..64).collect::<Vec<u8>>());
+
+        let left = helper_get_mode(EncryptionSchemes::Aes256, &key, &[])
+            .seal(&NONCE_AAD, &NONCE_PLAINTEXT);
+        let right = [
+            0x33, 0x4F, 0x81, 0x1D, 0x23, 0xA6, 0xA1, 0x55, 0x06, 0x8C, 0x66, 0xFB, 0xE1, 0x39,
+            0x62, 0xB6, 0xBC, 0x35, 0x7D, 0xF8, 0xFC, 0x72, 0x83, 0x9B, 0xFF, 0xED, 0xDE, 0xAA,
+            0xC8, 0xEE, 0x8F, 0xD4, 0x76, 0x35, 0xDD, 0x42, 0xEA, 0xD0, 0x68, 0x6B, 0xEC, 0xEE,
+            0x27, 0xEB, 0xA0, 0xC6, 0xE1, 0xCA, 0x70, 0xE5, 0x89, 0x39, 0xA3, 0xF3, 0xCB, 0xA6,
+            0x79, 0x76, 0xFB, 0x72, 0x61, 0xE5, 0x67,
+        ];
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn open_tampered_ciphertext() {
+        let mut ciphertext = RFC_CIPHERTEXT;
+        ciphertext[20] ^= 0x01;
+
+        let left = helper_get_mode(EncryptionSchemes::Aes128, &RFC_KEY_256_BITS, &[])
+            .open(&RFC_AAD, &ciphertext);
+
+        assert!(left.is_none());
+    }
+
+    #[test]
+    fn open_wrong_nonce() {
+        let ciphertext = helper_get_mode(EncryptionSchemes::Aes128, &NONCE_KEY_256_BITS, &NONCE)
+            .seal(&NONCE_AAD, &NONCE_PLAINTEXT);
+
+        let left = helper_get_mode(EncryptionSchemes::Aes128, &NONCE_KEY_256_BITS, &NONCE[1..])
+            .open(&NONCE_AAD, &ciphertext);
+
+        assert!(left.is_none());
+    }
+
+    #[should_panic]
+    #[test]
+    fn des_not_supported() {
+        SivMode::new(
+            EncryptionSchemes::Des.strategy(&KEY_64_BITS).unwrap(),
+            EncryptionSchemes::Des.strategy(&KEY_64_BITS).unwrap(),
+            &[],
+        );
+    }
+
+    // Equal plaintexts must map to equal ciphertexts so encrypted columns can be joined on
+    #[test]
+    fn seal_equal_records_without_nonce() {
+        let records: [&[u8]; 4] = [b"alice", b"bob", b"alice", b"carol"];
+
+        let ciphertexts: Vec<Vec<u8>> = records
+            .iter()
+            .map(|record| {
+                helper_get_mode(EncryptionSchemes::Aes128, &RFC_KEY_256_BITS, &[])
+                    .seal(b"users.name", record)
+            })
+            .collect();
+
+        assert_eq!(ciphertexts[0], ciphertexts[2]);
+        assert_ne!(ciphertexts[0], ciphertexts[1]);
+        assert_ne!(ciphertexts[0], ciphertexts[3]);
+    }
+
+    #[test]
+    fn seal_nonce_changes_ciphertext() {
+        let left = helper_get_mode(EncryptionSchemes::Aes128, &NONCE_KEY_256_BITS, &NONCE)
+            .seal(&[], b"alice");
+        let right = helper_get_mode(EncryptionSchemes::Aes128, &NONCE_KEY_256_BITS, &[])
+            .seal(&[], b"alice");
+
+        assert_ne!(left, right);
+    }
+
+    #[test]
+    fn open_wrong_aad() {
+        let ciphertext = helper_get_mode(EncryptionSchemes::Aes128, &RFC_KEY_256_BITS, &[])
+            .seal(b"users.name", b"alice");
+
+        let left = helper_get_mode(EncryptionSchemes::Aes128, &RFC_KEY_256_BITS, &[])
+            .open(b"users.email", &ciphertext);
+
+        assert!(left.is_none());
+    }
+}
@@ -0,0 +1,273 @@
+use crate::{
+    error::{Result, SymciphError},
+    strategy::EncryptionStrategy,
+};
+
+use super::{
+    gcm::{GCM_NONCE_LEN, GCM_TAG_LEN},
+    AuthenticatedMode, GcmMode,
+};
+
+// Segment nonces are prefix || big-endian segment counter || last segment flag
+pub const STREAM_NONCE_PREFIX_LEN: usize = 7;
+pub const STREAM_TAG_LEN: usize = GCM_TAG_LEN;
+
+pub(crate) struct StreamMode {
+    gcm: GcmMode,
+    nonce_prefix: Vec<u8>,
+    // Wider than the 32-bit field in the nonce, so running past the last value is caught
+    counter: u64,
+    finished: bool,
+}
+
+impl StreamMode {
+    // Only the first 7 bytes of the GCM nonce are used, the rest identifies the segment
+    pub fn new(strategy: Box<dyn EncryptionStrategy>, nonce: &[u8]) -> Self {
+        Self {
+            gcm: GcmMode::new(strategy, nonce),
+            nonce_prefix: nonce[..STREAM_NONCE_PREFIX_LEN].to_vec(),
+            counter: 0,
+            finished: false,
+        }
+    }
+
+    pub fn seal_segment(
+        &mut self,
+        aad: &[u8],
+        plaintext: &[u8],
+        last_segment: bool,
+    ) -> Result<Vec<u8>> {
+        self.next_segment(last_segment)?;
+
+        Ok(self.gcm.seal(aad, plaintext))
+    }
+
+    pub fn open_segment(
+        &mut self,
+        aad: &[u8],
+        ciphertext: &[u8],
+        last_segment: bool,
+    ) -> Result<Option<Vec<u8>>> {
+        self.next_segment(last_segment)?;
+
+        Ok(self.gcm.open(aad, ciphertext))
+    }
+
+    // Continues at any segment, the caller tells which one is last from the ciphertext length
+    pub fn seek(&mut self, segment: u32) {
+        self.counter = u64::from(segment);
+        self.finished = false;
+    }
+
+    fn next_segment(&mut self, last_segment: bool) -> Result<()> {
+        if self.finished {
+            return Err(SymciphError::InvalidInput(
+                "🧩🙀: No GCM-STREAM segment can follow the last one.".to_string(),
+            ));
+        }
+
+        let counter = u32::try_from(self.counter).map_err(|_| segment_counter_error())?;
+
+        let mut nonce = Vec::with_capacity(GCM_NONCE_LEN);
+        nonce.extend_from_slice(&self.nonce_prefix);
+        nonce.extend_from_slice(&counter.to_be_bytes());
+        nonce.push(last_segment as u8);
+
+        self.gcm.set_nonce(&nonce);
+
+        self.finished = last_segment;
+        self.counter += 1;
+
+        Ok(())
+    }
+}
+
+pub(crate) fn segment_counter_error() -> SymciphError {
+    SymciphError::InvalidInput(
+        "🧩🙀: Input too long for GCM-STREAM, the segment counter would wrap.".to_string(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{mode::tests::*, EncryptionSchemes};
+
+    use super::*;
+
+    const NONCE: [u8; GCM_NONCE_LEN] = [
+        0xCA, 0xFE, 0xBA, 0xBE, 0xFA, 0xCE, 0xDB, 0xAD, 0xDE, 0xCA, 0xF8, 0x88,
+    ];
+
+    fn helper_get_mode() -> StreamMode {
+        StreamMode::new(
+            EncryptionSchemes::Aes128
+                .strategy(&NIST_KEY_128_BITS)
+                .unwrap(),
+            &NONCE,
+        )
+    }
+
+    fn helper_seal_segments(segments: &[&[u8]]) -> Vec<Vec<u8>> {
+        let mut mode = helper_get_mode();
+
+        segments
+            .iter()
+            .enumerate()
+            .map(|(index, segment)| {
+                mode.seal_segment(b"header", segment, index == segments.len() - 1)
+                    .unwrap()
+            })
+            .collect()
+    }
+
+    // Each segment is plain GCM under its own nonce
+    #[test]
+    fn segment_is_gcm() {
+        let left = helper_seal_segments(&[b"first", b"second"]);
+
+        let mut gcm = GcmMode::new(
+            EncryptionSchemes::Aes128
+                .strategy(&NIST_KEY_128_BITS)
+                .unwrap(),
+            &[&NONCE[..7], &[0x00, 0x00, 0x00, 0x01, 0x01]].concat(),
+        );
+        let right = gcm.seal(b"header", b"second");
+
+        assert_eq!(left[1], right);
+    }
+
+    #[test]
+    fn open_segments() {
+        let ciphertext = helper_seal_segments(&[b"first", b"second", b""]);
+        let mut mode = helper_get_mode();
+
+        assert_eq!(
+            mode.open_segment(b"header", &ciphertext[0], false)
+                .unwrap()
+                .unwrap(),
+            b"first"
+        );
+        assert_eq!(
+            mode.open_segment(b"header", &ciphertext[1], false)
+                .unwrap()
+                .unwrap(),
+            b"second"
+        );
+        assert_eq!(
+            mode.open_segment(b"header", &ciphertext[2], true)
+                .unwrap()
+                .unwrap(),
+            b""
+        );
+    }
+
+    #[test]
+    fn open_truncated_stream() {
+        let ciphertext = helper_seal_segments(&[b"first", b"second", b"third"]);
+        let mut mode = helper_get_mode();
+
+        mode.open_segment(b"header", &ciphertext[0], false)
+            .unwrap()
+            .unwrap();
+
+        assert!(mode
+            .open_segment(b"header", &ciphertext[1], true)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn open_reordered_segments() {
+        let ciphertext = helper_seal_segments(&[b"first", b"second", b"third"]);
+        let mut mode = helper_get_mode();
+
+        assert!(mode
+            .open_segment(b"header", &ciphertext[1], false)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn open_duplicated_segment() {
+        let ciphertext = helper_seal_segments(&[b"first", b"second", b"third"]);
+        let mut mode = helper_get_mode();
+
+        mode.open_segment(b"header", &ciphertext[0], false)
+            .unwrap()
+            .unwrap();
+
+        assert!(mode
+            .open_segment(b"header", &ciphertext[0], false)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn open_extended_stream() {
+        let ciphertext = helper_seal_segments(&[b"first", b"second"]);
+        let mut mode = helper_get_mode();
+
+        mode.open_segment(b"header", &ciphertext[0], false)
+            .unwrap()
+            .unwrap();
+
+        assert!(mode
+            .open_segment(b"header", &ciphertext[1], false)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn open_from_middle_segment() {
+        let ciphertext = helper_seal_segments(&[b"first", b"second", b"third"]);
+        let mut mode = helper_get_mode();
+
+        mode.seek(1);
+
+        assert_eq!(
+            mode.open_segment(b"header", &ciphertext[1], false)
+                .unwrap()
+                .unwrap(),
+            b"second"
+        );
+        assert_eq!(
+            mode.open_segment(b"header", &ciphertext[2], true)
+                .unwrap()
+                .unwrap(),
+            b"third"
+        );
+    }
+
+    #[test]
+    fn open_after_wrong_seek() {
+        let ciphertext = helper_seal_segments(&[b"first", b"second", b"third"]);
+        let mut mode = helper_get_mode();
+
+        mode.seek(2);
+
+        assert!(mode
+            .open_segment(b"header", &ciphertext[1], false)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn seal_past_segment_counter() {
+        let mut mode = helper_get_mode();
+
+        mode.seek(u32::MAX);
+        mode.seal_segment(&[], b"last counter value", false)
+            .unwrap();
+
+        assert!(mode.seal_segment(&[], b"one more", true).is_err());
+    }
+
+    #[test]
+    fn seal_after_last_segment() {
+        let mut mode = helper_get_mode();
+
+        mode.seal_segment(&[], b"last", true).unwrap();
+
+        assert!(mode.seal_segment(&[], b"one more", false).is_err());
+    }
+}
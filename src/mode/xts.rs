@@ -0,0 +1,263 @@
+use crate::strategy::{EncryptionStrategy, AES_BLOCK_CHUNKS};
+
+use super::{decrypt_block, encrypt_block};
+
+pub const XTS_BLOCK_LEN: usize = 16;
+
+pub(crate) struct XtsMode {
+    data_strategy: Box<dyn EncryptionStrategy>,
+    tweak_strategy: Box<dyn EncryptionStrategy>,
+}
+
+impl XtsMode {
+    pub fn new(
+        data_strategy: Box<dyn EncryptionStrategy>,
+        tweak_strategy: Box<dyn EncryptionStrategy>,
+    ) -> Self {
+        assert!(
+            data_strategy.block_size() == AES_BLOCK_CHUNKS
+                && tweak_strategy.block_size() == AES_BLOCK_CHUNKS,
+            "XTS: Cipher block size must be 128 bits"
+        );
+
+        Self {
+            data_strategy,
+            tweak_strategy,
+        }
+    }
+
+    pub fn encrypt_sector(&self, sector: u128, data: &mut [u8]) {
+        self.process_sector(sector, data, false);
+    }
+
+    pub fn decrypt_sector(&self, sector: u128, data: &mut [u8]) {
+        self.process_sector(sector, data, true);
+    }
+
+    fn process_sector(&self, sector: u128, data: &mut [u8], decrypt: bool) {
+        assert!(
+            data.len() >= XTS_BLOCK_LEN,
+            "XTS: Sector must be at least 16 bytes long"
+        );
+
+        let mut tweak = from_block(&encrypt_block(
+            self.tweak_strategy.as_ref(),
+            &sector.to_le_bytes(),
+        ));
+
+        let partial_len = data.len() % XTS_BLOCK_LEN;
+        let full_blocks = data.len() / XTS_BLOCK_LEN;
+
+        // With ciphertext stealing the last full block is handled together with the partial one
+        let plain_blocks = match partial_len {
+            0 => full_blocks,
+            _ => full_blocks - 1,
+        };
+
+        for block in data[..plain_blocks * XTS_BLOCK_LEN].chunks_exact_mut(XTS_BLOCK_LEN) {
+            self.process_block(block, tweak, decrypt);
+            tweak = double(tweak);
+        }
+
+        if partial_len == 0 {
+            return;
+        }
+
+        let (last_block, partial_block) =
+            data[plain_blocks * XTS_BLOCK_LEN..].split_at_mut(XTS_BLOCK_LEN);
+
+        // Decryption consumes the two tweaks in reverse order
+        let (first_tweak, second_tweak) = match decrypt {
+            true => (double(tweak), tweak),
+            false => (tweak, double(tweak)),
+        };
+
+        self.process_block(last_block, first_tweak, decrypt);
+
+        // Steal the tail of the processed block to pad the partial one
+        for (byte, last_byte) in partial_block.iter_mut().zip(last_block.iter_mut()) {
+            std::mem::swap(byte, last_byte);
+        }
+
+        self.process_block(last_block, second_tweak, decrypt);
+    }
+
+    fn process_block(&self, block: &mut [u8], tweak: u128, decrypt: bool) {
+        let input = (from_block(block) ^ tweak).to_le_bytes();
+
+        let output = match decrypt {
+            true => decrypt_block(self.data_strategy.as_ref(), &input),
+            false => encrypt_block(self.data_strategy.as_ref(), &input),
+        };
+
+        block.copy_from_slice(&(from_block(&output) ^ tweak).to_le_bytes());
+    }
+}
+
+fn from_block(bytes: &[u8]) -> u128 {
+    u128::from_le_bytes(
+        bytes
+            .try_into()
+            // Blocks are either whole chunks of a sector or AES outputs, so this will never happen
+            .expect("🙀🧨 XTS: Block not 128 bits long. This was not supposed to happen."),
+    )
+}
+
+// Multiplication by the primitive element of GF(2^128), little-endian as per IEEE 1619
+fn double(tweak: u128) -> u128 {
+    (tweak << 1) ^ ((tweak >> 127) * 0x87)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{mode::to_chunks, EncryptionSchemes};
+
+    use super::*;
+
+    fn helper_get_mode(key: &[u8]) -> XtsMode {
+        let cipher = match key.len() {
+            32 => EncryptionSchemes::Aes128,
+            _ => EncryptionSchemes::Aes256,
+        };
+        let (data_key, tweak_key) = key.split_at(key.len() / 2);
+
+        XtsMode::new(
+            cipher.strategy(&to_chunks(data_key)).unwrap(),
+            cipher.strategy(&to_chunks(tweak_key)).unwrap(),
+        )
+    }
+
+    fn helper_get_data(len: usize) -> Vec<u8> {
+        (0..len).map(|byte| byte as u8).collect()
+    }
+
+    // IEEE 1619-2007, Annex B, vector 2
+    #[test]
+    fn encrypt_ieee_vector() {
+        let key = [[0x11; 16], [0x22; 16]].concat();
+        let mut left = [0x44; 32];
+        helper_get_mode(&key).encrypt_sector(0x3333333333, &mut left);
+
+        let right = [
+            0xC4, 0x54, 0x18, 0x5E, 0x6A, 0x16, 0x93, 0x6E, 0x39, 0x33, 0x40, 0x38, 0xAC, 0xEF,
+            0x83, 0x8B, 0xFB, 0x18, 0x6F, 0xFF, 0x74, 0x80, 0xAD, 0xC4, 0x28, 0x93, 0x82, 0xEC,
+            0xD6, 0xD3, 0x94, 0xF0,
+        ];
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn encrypt_stealing_17_bytes() {
+        let mut left = helper_get_data(17);
+        helper_get_mode(&helper_get_data(32)).encrypt_sector(0x123456789A, &mut left);
+
+        let right = [
+            0x2B, 0x51, 0x4E, 0xDF, 0x10, 0xED, 0x5F, 0x8E, 0x39, 0x0B, 0xC7, 0x1C, 0xAA, 0xC4,
+            0xA0, 0xFE, 0x3C,
+        ];
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn encrypt_stealing_40_bytes() {
+        let mut left = helper_get_data(40);
+        helper_get_mode(&helper_get_data(32)).encrypt_sector(0x123456789A, &mut left);
+
+        let right = [
+            0x3C, 0xA6, 0xC4, 0x25, 0xE2, 0xF8, 0x3B, 0xF7, 0x89, 0x20, 0x6F, 0x14, 0x03, 0xF8,
+            0x53, 0x22, 0xC8, 0xDC, 0x48, 0x4F, 0x39, 0x00, 0xE8, 0x28, 0x5A, 0x71, 0x2D, 0x64,
+            0x3E, 0x87, 0x57, 0x61, 0x60, 0xFB, 0x0A, 0xBA, 0x46, 0x36, 0x53, 0x25,
+        ];
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn encrypt_aes256() {
+        let mut left = helper_get_data(64);
+        helper_get_mode(&helper_get_data(64)).encrypt_sector(0, &mut left);
+
+        let right = [
+            0xDC, 0x8C, 0x66, 0x5B, 0x97, 0xCB, 0xC0, 0x24, 0x6D, 0x4F, 0x16, 0x39, 0xA9, 0x67,
+            0x8A, 0x3E, 0x2A, 0x2D, 0xCF, 0x4A, 0x3F, 0xBF, 0x13, 0x42, 0xEB, 0xBB, 0x77, 0x12,
+            0x34, 0xF1, 0xA1, 0xC3, 0xCB, 0x88, 0x51, 0x82, 0xE5, 0x4E, 0x27, 0x7A, 0xA9, 0x08,
+            0x75, 0xBF, 0xB7, 0x79, 0xB2, 0x7C, 0x28, 0x56, 0x8D, 0x27, 0x31, 0xFD, 0x61, 0xD0,
+            0xB4, 0x32, 0x48, 0x04, 0x65, 0x97, 0xE3, 0x26,
+        ];
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn encrypt_aes256_stealing() {
+        let mut left = helper_get_data(37);
+        helper_get_mode(&helper_get_data(64)).encrypt_sector(0xFF, &mut left);
+
+        let right = [
+            0x75, 0xDE, 0x38, 0x10, 0x13, 0xF2, 0xA0, 0x9B, 0x66, 0x55, 0xCF, 0x5E, 0x40, 0x7C,
+            0xA7, 0x1C, 0xE7, 0x24, 0x9F, 0xC1, 0xF6, 0xB6, 0xE7, 0x2E, 0xA6, 0x16, 0x0E, 0xAB,
+            0x0F, 0x62, 0x26, 0xEF, 0xCB, 0x36, 0x23, 0xE1, 0xED,
+        ];
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn decrypt_stealing() {
+        let mode = helper_get_mode(&helper_get_data(32));
+
+        for len in [16, 17, 31, 32, 33, 512] {
+            let mut left = helper_get_data(len);
+            mode.encrypt_sector(7, &mut left);
+            mode.decrypt_sector(7, &mut left);
+
+            let right = helper_get_data(len);
+
+            assert_eq!(left, right);
+        }
+    }
+
+    #[test]
+    fn sector_number_changes_ciphertext() {
+        let mode = helper_get_mode(&helper_get_data(32));
+
+        let mut left = helper_get_data(32);
+        mode.encrypt_sector(0, &mut left);
+        let mut right = helper_get_data(32);
+        mode.encrypt_sector(1, &mut right);
+
+        assert_ne!(left, right);
+    }
+
+    #[should_panic]
+    #[test]
+    fn sector_shorter_than_block() {
+        helper_get_mode(&helper_get_data(32)).encrypt_sector(0, &mut [0; 15]);
+    }
+
+    #[test]
+    fn single_sector_decrypts_independently() {
+        let key = helper_get_data(32);
+        let image = helper_get_data(4 * 512 + 100);
+        let mode = helper_get_mode(&key);
+
+        let mut encrypted_image = image.clone();
+        for (index, sector) in encrypted_image.chunks_mut(512).enumerate() {
+            mode.encrypt_sector(2048 + index as u128, sector);
+        }
+
+        let mut left = encrypted_image[2 * 512..3 * 512].to_vec();
+        helper_get_mode(&key).decrypt_sector(2050, &mut left);
+        let right = &image[2 * 512..3 * 512];
+
+        assert_eq!(left, right);
+
+        let mut left = encrypted_image[4 * 512..].to_vec();
+        helper_get_mode(&key).decrypt_sector(2052, &mut left);
+        let right = &image[4 * 512..];
+
+        assert_eq!(left, right);
+    }
+}
@@ -0,0 +1,212 @@
+use rand::Rng;
+
+use super::{
+    error::{Result, SymciphError},
+    PaddingSchemes,
+};
+
+impl PaddingSchemes {
+    // Completes the final partial block, which may be empty, to exactly one full block
+    pub fn pad(&self, partial_block: &[u8], block_len: usize) -> Result<Vec<u8>> {
+        assert!(
+            partial_block.len() < block_len,
+            "Padding: Partial block must be shorter than the block"
+        );
+
+        let padding_len = block_len - partial_block.len();
+        let mut block = partial_block.to_vec();
+
+        match self {
+            Self::Pkcs7 => block.resize(block_len, padding_len as u8),
+            Self::AnsiX923 => {
+                block.resize(block_len - 1, 0);
+                block.push(padding_len as u8);
+            }
+            Self::Iso7816 => {
+                block.push(0x80);
+                block.resize(block_len, 0);
+            }
+            Self::Iso10126 => {
+                let mut rng = rand::thread_rng();
+
+                block.extend((1..padding_len).map(|_| rng.gen::<u8>()));
+                block.push(padding_len as u8);
+            }
+            Self::None if partial_block.is_empty() => block.clear(),
+            Self::None => return Err(SymciphError::BlockAlignment(
+                "🧩🙀: Input is not a multiple of the block size, a padding scheme is required."
+                    .to_string(),
+            )),
+        }
+
+        Ok(block)
+    }
+
+    // Returns how many bytes of the final block are data
+    pub fn unpad(&self, last_block: &[u8]) -> Result<usize> {
+        let data_len = match self {
+            Self::Pkcs7 => trailing_length(last_block).filter(|&data_len| {
+                last_block[data_len..]
+                    .iter()
+                    .all(|&byte| byte as usize == last_block.len() - data_len)
+            }),
+            Self::AnsiX923 => trailing_length(last_block).filter(|&data_len| {
+                last_block[data_len..last_block.len() - 1]
+                    .iter()
+                    .all(|&byte| byte == 0)
+            }),
+            Self::Iso7816 => last_block
+                .iter()
+                .rposition(|&byte| byte != 0)
+                .filter(|&position| last_block[position] == 0x80),
+            Self::Iso10126 => trailing_length(last_block),
+            Self::None => Some(last_block.len()),
+        };
+
+        data_len.ok_or_else(|| {
+            SymciphError::Padding(
+                "🧩🙀: Invalid padding, the key or ciphertext is wrong.".to_string(),
+            )
+        })
+    }
+}
+
+// Data length implied by a padding length byte at the end of the block
+fn trailing_length(last_block: &[u8]) -> Option<usize> {
+    let padding_len = *last_block.last()? as usize;
+
+    (1..=last_block.len())
+        .contains(&padding_len)
+        .then(|| last_block.len() - padding_len)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BLOCK_LEN: usize = 8;
+
+    const PARTIAL_BLOCK: [u8; 5] = [0xDD, 0x00, 0xDD, 0x00, 0x00];
+
+    #[test]
+    fn pkcs7_pad() {
+        let left = PaddingSchemes::Pkcs7
+            .pad(&PARTIAL_BLOCK, BLOCK_LEN)
+            .unwrap();
+        let right = [0xDD, 0x00, 0xDD, 0x00, 0x00, 0x03, 0x03, 0x03];
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn pkcs7_pad_empty_block() {
+        let left = PaddingSchemes::Pkcs7.pad(&[], BLOCK_LEN).unwrap();
+        let right = [0x08; BLOCK_LEN];
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn ansi_x923_pad() {
+        let left = PaddingSchemes::AnsiX923
+            .pad(&PARTIAL_BLOCK, BLOCK_LEN)
+            .unwrap();
+        let right = [0xDD, 0x00, 0xDD, 0x00, 0x00, 0x00, 0x00, 0x03];
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn iso7816_pad() {
+        let left = PaddingSchemes::Iso7816
+            .pad(&PARTIAL_BLOCK, BLOCK_LEN)
+            .unwrap();
+        let right = [0xDD, 0x00, 0xDD, 0x00, 0x00, 0x80, 0x00, 0x00];
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn iso10126_pad() {
+        let padded_block = PaddingSchemes::Iso10126
+            .pad(&PARTIAL_BLOCK, BLOCK_LEN)
+            .unwrap();
+
+        assert_eq!(padded_block[..5], PARTIAL_BLOCK);
+        assert_eq!(padded_block[7], 0x03);
+    }
+
+    #[test]
+    fn none_pad_empty_block() {
+        let left = PaddingSchemes::None.pad(&[], BLOCK_LEN).unwrap();
+
+        assert!(left.is_empty());
+    }
+
+    #[test]
+    fn none_pad_partial_block() {
+        let left = PaddingSchemes::None.pad(&PARTIAL_BLOCK, BLOCK_LEN);
+
+        assert!(left.is_err());
+    }
+
+    #[test]
+    fn unpad_keeps_trailing_zeros() {
+        for padding in [
+            PaddingSchemes::Pkcs7,
+            PaddingSchemes::AnsiX923,
+            PaddingSchemes::Iso7816,
+            PaddingSchemes::Iso10126,
+        ] {
+            let padded_block = padding.pad(&PARTIAL_BLOCK, BLOCK_LEN).unwrap();
+
+            let left = padding.unpad(&padded_block).unwrap();
+            let right = PARTIAL_BLOCK.len();
+
+            assert_eq!(left, right);
+        }
+    }
+
+    #[test]
+    fn pkcs7_unpad_inconsistent_bytes() {
+        let left = PaddingSchemes::Pkcs7.unpad(&[0xDD, 0x00, 0xDD, 0x00, 0x00, 0x02, 0x03, 0x03]);
+
+        assert!(left.is_err());
+    }
+
+    #[test]
+    fn pkcs7_unpad_zero_length() {
+        let left =
+            PaddingSchemes::Pkcs7.unpad(&[0xDD; 7].into_iter().chain([0x00]).collect::<Vec<u8>>());
+
+        assert!(left.is_err());
+    }
+
+    #[test]
+    fn pkcs7_unpad_longer_than_block() {
+        let left = PaddingSchemes::Pkcs7.unpad(&[0x09; BLOCK_LEN]);
+
+        assert!(left.is_err());
+    }
+
+    #[test]
+    fn ansi_x923_unpad_nonzero_filler() {
+        let left =
+            PaddingSchemes::AnsiX923.unpad(&[0xDD, 0x00, 0xDD, 0x00, 0x00, 0x01, 0x00, 0x03]);
+
+        assert!(left.is_err());
+    }
+
+    #[test]
+    fn iso7816_unpad_missing_marker() {
+        let left = PaddingSchemes::Iso7816.unpad(&[0xDD, 0x00, 0xDD, 0x00, 0x00, 0x00, 0x00, 0x00]);
+
+        assert!(left.is_err());
+    }
+
+    #[test]
+    fn unpad_empty_ciphertext() {
+        assert!(PaddingSchemes::Pkcs7.unpad(&[]).is_err());
+        assert_eq!(PaddingSchemes::None.unpad(&[]).unwrap(), 0);
+    }
+}
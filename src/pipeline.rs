@@ -0,0 +1,426 @@
+use std::{
+    fs::File,
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    error::{Result, SymciphError},
+    kdf::Kdf,
+    mode::{
+        ccm_max_payload_len, counter_exhausted_error, segment_counter_error, to_chunks,
+        GCM_MAX_PAYLOAD_LEN, GCM_SIV_MAX_PLAINTEXT_LEN, XTS_BLOCK_LEN,
+    },
+    DataChunk, EncryptionModes, EncryptionSchemes, BLOCK_CHUNK_SIZE,
+};
+
+const READ_BUFFER_LEN: usize = 2;
+
+pub(crate) const DEFAULT_ITERATIONS: u32 = 600_000;
+pub(crate) const DEFAULT_MEMORY_COST: u32 = 0x10000;
+pub(crate) const DEFAULT_TIME_COST: u32 = 3;
+pub(crate) const DEFAULT_PARALLELISM: u32 = 1;
+pub(crate) const DEFAULT_COUNTER_BITS: usize = 32;
+pub(crate) const DEFAULT_NONCE_LEN: usize = 12;
+pub(crate) const DEFAULT_TAG_LEN: usize = 16;
+pub(crate) const DEFAULT_SECTOR_SIZE: usize = 512;
+pub(crate) const DEFAULT_SEGMENT_SIZE: usize = 0x10000;
+
+// Containers are decrypted with whatever cost their header names, so the cost options are capped
+// where a crafted file could no longer stall or exhaust the machine opening it
+pub(crate) const MAX_ITERATIONS: u32 = 0x100_0000;
+pub(crate) const MAX_TIME_COST: u32 = 32;
+
+// Keys are taken as they are, passwords are stretched with the KDF named in the header
+pub(crate) enum Secret {
+    Key(Vec<u8>),
+    Password(String),
+}
+
+pub(crate) fn key_chunks(key: &[u8]) -> Result<Vec<DataChunk>> {
+    if !key.len().is_multiple_of(BLOCK_CHUNK_SIZE) {
+        return Err(SymciphError::KeyLength(format!(
+            "🔑🙀: Key is not a multiple of {} bytes.",
+            BLOCK_CHUNK_SIZE
+        )));
+    }
+
+    Ok(to_chunks(key))
+}
+
+pub(crate) fn password_key(
+    kdf: &Kdf,
+    password: &str,
+    mode: &EncryptionModes,
+    cipher: &EncryptionSchemes,
+) -> Result<Vec<DataChunk>> {
+    if password.is_empty() {
+        return Err(SymciphError::InvalidInput(
+            "🔑🙀: Password cannot be empty.".to_string(),
+        ));
+    }
+
+    Ok(to_chunks(&kdf.derive_key(
+        password.as_bytes(),
+        mode.key_size(cipher) * BLOCK_CHUNK_SIZE,
+    )))
+}
+
+// EAX tags are no longer than a cipher block, which is only 8 bytes in DES and 3DES
+pub(crate) fn default_tag_len(cipher: &EncryptionSchemes) -> usize {
+    DEFAULT_TAG_LEN.min(cipher.block_size() * BLOCK_CHUNK_SIZE)
+}
+
+// Reasons follow the option name, so the CLI parsers and the builders word errors alike
+pub(crate) type RangeCheck = std::result::Result<(), &'static str>;
+
+pub(crate) fn check_counter_bits(counter_bits: usize) -> RangeCheck {
+    match counter_bits {
+        8..=128 if counter_bits.is_multiple_of(8) => Ok(()),
+        _ => Err("must be a multiple of 8 between 8 and 128"),
+    }
+}
+
+// IEEE 1619 caps a data unit at 2^20 blocks
+pub(crate) fn check_sector_size(sector_size: usize) -> RangeCheck {
+    match sector_size {
+        16..=0x100_0000 => Ok(()),
+        _ => Err("must be between 16 bytes and 16 MiB"),
+    }
+}
+
+pub(crate) fn check_segment_size(segment_size: usize) -> RangeCheck {
+    match segment_size {
+        1..=0x100_0000 => Ok(()),
+        _ => Err("must be between 1 byte and 16 MiB"),
+    }
+}
+
+pub(crate) fn check_iterations(iterations: u32) -> RangeCheck {
+    match iterations {
+        1000..=MAX_ITERATIONS => Ok(()),
+        _ => Err("must be between 1000 and 16777216"),
+    }
+}
+
+// Argon2id needs 8 KiB per lane
+pub(crate) fn check_memory_cost(memory_cost: u32) -> RangeCheck {
+    match memory_cost {
+        8..=0x40_0000 => Ok(()),
+        _ => Err("must be between 8 KiB and 4 GiB"),
+    }
+}
+
+pub(crate) fn check_time_cost(time_cost: u32) -> RangeCheck {
+    match time_cost {
+        1..=MAX_TIME_COST => Ok(()),
+        _ => Err("must be between 1 and 32"),
+    }
+}
+
+pub(crate) fn check_parallelism(parallelism: u32) -> RangeCheck {
+    match parallelism {
+        1..=255 => Ok(()),
+        _ => Err("must be between 1 and 255"),
+    }
+}
+
+pub(crate) fn check_option(name: &str, check: RangeCheck) -> Result<()> {
+    check.map_err(|reason| SymciphError::InvalidInput(format!("🧩🙀: {} {}.", name, reason)))
+}
+
+pub(crate) fn check_mode_support(
+    mode: &EncryptionModes,
+    cipher: &EncryptionSchemes,
+    key_len: usize,
+    counter_bits: usize,
+    nonce_len: usize,
+    tag_len: usize,
+) -> Result<()> {
+    check_key_support(mode, cipher, key_len)?;
+
+    if !mode.supports_counter_bits(cipher, counter_bits) {
+        return Err(SymciphError::InvalidInput(
+            "🧩🙀: Counter bits must not exceed the block size of the selected cipher.".to_string(),
+        ));
+    }
+
+    if !mode.supports_nonce_and_tag(cipher, nonce_len, tag_len) {
+        return Err(SymciphError::InvalidInput(
+            "🧩🙀: Nonce or tag length not supported by the selected mode.".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+// Keys do not depend on the nonce or tag, so generating one only needs the cipher and mode
+pub(crate) fn check_key_support(
+    mode: &EncryptionModes,
+    cipher: &EncryptionSchemes,
+    key_len: usize,
+) -> Result<()> {
+    if !mode.supports_cipher(cipher) {
+        return Err(SymciphError::InvalidInput(
+            "🧩🙀: Mode of operation not supported by the selected cipher.".to_string(),
+        ));
+    }
+
+    if !mode.supports_key_size(cipher, key_len) {
+        return Err(SymciphError::KeyLength(
+            "🔑🙀: Key length does not match the selected cipher and mode.".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+// Modes with two keys, such as XTS and SIV, have every half checked on its own
+pub(crate) fn check_key_strength(
+    mode: &EncryptionModes,
+    cipher: &EncryptionSchemes,
+    key: &[DataChunk],
+) -> Result<()> {
+    let keys_num = mode.key_size(cipher) / cipher.key_size();
+
+    for cipher_key in key.chunks(key.len() / keys_num) {
+        cipher.check_key(cipher_key)?;
+    }
+
+    Ok(())
+}
+
+pub(crate) fn check_encrypt_support(cipher: &EncryptionSchemes) -> Result<()> {
+    if cipher.is_legacy() {
+        return Err(SymciphError::InvalidInput(
+            "🧩🙀: Legacy ciphers only decrypt existing files, encrypt with des or tdes instead."
+                .to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+// Counters must not wrap, or the keystream or segment nonces would repeat. File inputs are refused
+// before anything is written, CTR and GCM-STREAM catch stream inputs once the counter runs out and
+// GCM and CCM messages are checked once they are whole.
+pub(crate) fn check_message_len(
+    mode: &EncryptionModes,
+    cipher: &EncryptionSchemes,
+    counter_bits: usize,
+    nonce_len: usize,
+    segment_size: usize,
+    message_len: u64,
+) -> Result<()> {
+    let block_len = (cipher.block_size() * BLOCK_CHUNK_SIZE) as u128;
+
+    let (max_len, error): (Option<u128>, fn() -> SymciphError) = match mode {
+        EncryptionModes::Ctr => (
+            1u128
+                .checked_shl(counter_bits as u32)
+                .and_then(|num_blocks| num_blocks.checked_mul(block_len)),
+            counter_exhausted_error,
+        ),
+        EncryptionModes::GcmStream => (
+            Some((u128::from(u32::MAX) + 1) * segment_size as u128),
+            segment_counter_error,
+        ),
+        EncryptionModes::Gcm => (Some(GCM_MAX_PAYLOAD_LEN), || {
+            SymciphError::InvalidInput(
+                "🧩🙀: Input too long for GCM, which takes at most 64 GiB under one nonce."
+                    .to_string(),
+            )
+        }),
+        EncryptionModes::GcmSiv => (Some(u128::from(GCM_SIV_MAX_PLAINTEXT_LEN)), || {
+            SymciphError::InvalidInput(
+                "🧩🙀: Input too long for GCM-SIV, which takes at most 64 GiB under one key and nonce."
+                    .to_string(),
+            )
+        }),
+        EncryptionModes::Ccm => (ccm_max_payload_len(nonce_len), || {
+            SymciphError::InvalidInput(
+                "🧩🙀: Input too long for CCM with this nonce length, shorter nonces allow longer messages."
+                    .to_string(),
+            )
+        }),
+        _ => return Ok(()),
+    };
+
+    match max_len.is_some_and(|max_len| u128::from(message_len) > max_len) {
+        true => Err(error()),
+        false => Ok(()),
+    }
+}
+
+// Headerless files have nowhere to keep the salt
+pub(crate) fn check_password_support(raw: bool) -> Result<()> {
+    if raw {
+        return Err(SymciphError::InvalidInput("🔑🙀: Password-based keys need the container header to store the salt, leave out --raw.".to_string()));
+    }
+
+    Ok(())
+}
+
+type Process = Box<dyn FnMut(&[u8], bool) -> Result<Vec<u8>>>;
+
+// Cuts data into the units a mode works on. A unit is only handed over once more data follows
+// it, so the last one can be flagged for padding or the final segment tag. Whole-message modes
+// have no unit length and see everything at once when the data ends.
+pub(crate) struct Transform {
+    unit_len: Option<usize>,
+    pending: Vec<u8>,
+    process: Process,
+}
+
+impl Transform {
+    pub(crate) fn whole(process: impl FnMut(&[u8], bool) -> Result<Vec<u8>> + 'static) -> Self {
+        Self {
+            unit_len: None,
+            pending: Vec::new(),
+            process: Box::new(process),
+        }
+    }
+
+    pub(crate) fn units(
+        unit_len: usize,
+        process: impl FnMut(&[u8], bool) -> Result<Vec<u8>> + 'static,
+    ) -> Self {
+        Self {
+            unit_len: Some(unit_len),
+            pending: Vec::new(),
+            process: Box::new(process),
+        }
+    }
+
+    // Batches are whole cipher blocks, so only the last one can end in a partial block
+    pub(crate) fn blocks(
+        block_len: usize,
+        process: impl FnMut(&[u8], bool) -> Result<Vec<u8>> + 'static,
+    ) -> Self {
+        Self::units(READ_BUFFER_LEN * block_len, process)
+    }
+
+    pub(crate) fn sectors(
+        sector_size: usize,
+        start_sector: u64,
+        process: impl Fn(u128, &mut [u8]) + 'static,
+    ) -> Self {
+        let mut sector_number = u128::from(start_sector);
+
+        Self::units(sector_size, move |sector, _| {
+            if sector.is_empty() {
+                return Ok(Vec::new());
+            }
+
+            // Ciphertext stealing needs at least one full block to borrow from
+            if sector.len() < XTS_BLOCK_LEN {
+                return Err(SymciphError::BlockAlignment(
+                    "🧩🙀: Last sector is shorter than a cipher block.".to_string(),
+                ));
+            }
+
+            let mut sector = sector.to_vec();
+            process(sector_number, &mut sector);
+            sector_number += 1;
+
+            Ok(sector)
+        })
+    }
+
+    pub(crate) fn push(&mut self, data: &[u8]) -> Result<Vec<u8>> {
+        self.pending.extend_from_slice(data);
+
+        let Some(unit_len) = self.unit_len else {
+            return Ok(Vec::new());
+        };
+
+        let mut output = Vec::new();
+        let mut unit_start = 0;
+
+        while self.pending.len() - unit_start > unit_len {
+            output.extend((self.process)(
+                &self.pending[unit_start..unit_start + unit_len],
+                false,
+            )?);
+            unit_start += unit_len;
+        }
+
+        self.pending.drain(..unit_start);
+
+        Ok(output)
+    }
+
+    // An empty input still makes one last unit, which pads to a full block or seals an empty segment
+    pub(crate) fn finish(&mut self) -> Result<Vec<u8>> {
+        let last_unit = std::mem::take(&mut self.pending);
+
+        (self.process)(&last_unit, true)
+    }
+}
+
+// Modes run on whole chunks, the zero-filled tail of a partial one is cut off again
+pub(crate) fn process_batch(
+    batch: &[u8],
+    process: impl FnOnce(&[DataChunk]) -> Result<Vec<DataChunk>>,
+) -> Result<Vec<u8>> {
+    if batch.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut output = process(&to_chunks(batch))?.concat();
+    output.truncate(batch.len());
+
+    Ok(output)
+}
+
+// Stolen bytes come from the penultimate block, so at least one full block is needed
+pub(crate) fn check_stealing_len(data_len: usize, block_len: usize) -> Result<()> {
+    if data_len < block_len {
+        return Err(SymciphError::BlockAlignment(
+            "🧩🙀: Ciphertext stealing needs at least one full block of input.".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+// Output files only appear once there is something to put in them, so a run that fails
+// before writing, such as a failed tag check, leaves nothing behind
+pub(crate) struct LazyFile {
+    path: PathBuf,
+    file: Option<File>,
+}
+
+impl LazyFile {
+    pub(crate) fn new(path: impl AsRef<Path>) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            file: None,
+        }
+    }
+
+    fn file(&mut self) -> io::Result<&mut File> {
+        match self.file {
+            Some(ref mut file) => Ok(file),
+            None => Ok(self.file.insert(File::create(&self.path)?)),
+        }
+    }
+
+    // Successful runs with empty output still leave an empty file
+    pub(crate) fn create(&mut self) -> Result<()> {
+        Ok(self.file()?.flush()?)
+    }
+}
+
+impl Write for LazyFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.file()?.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self.file {
+            Some(ref mut file) => file.flush(),
+            None => Ok(()),
+        }
+    }
+}
@@ -2,6 +2,7 @@ mod aes128;
 mod aes192;
 mod aes256;
 mod aes_commons;
+mod block_cipher;
 mod des;
 mod tdes;
 
@@ -11,33 +12,92 @@ use self::{
     tdes::TripleDesEncryptionStrategy,
 };
 
-use super::{DataChunk, EncryptionSchemes};
+use ::des::{has_odd_parity, set_odd_parity, DesCipher};
+use triple_des::TripleDesCipher;
+
+use super::{error::Result, DataChunk, EncryptionSchemes};
+
+pub use self::aes_commons::{AES128_KEY_CHUNKS, AES256_KEY_CHUNKS, AES_BLOCK_CHUNKS};
 
 pub trait EncryptionStrategy {
-    fn encrypt(&self, plaintext: &[DataChunk]) -> Vec<DataChunk>;
-    fn decrypt(&self, ciphertext: &[DataChunk]) -> Vec<DataChunk>;
+    fn encrypt(&self, plaintext: &[DataChunk]) -> Result<Vec<DataChunk>>;
+    fn decrypt(&self, ciphertext: &[DataChunk]) -> Result<Vec<DataChunk>>;
+    fn block_size(&self) -> usize;
 }
 
 impl EncryptionSchemes {
-    pub fn strategy(&self, key_bytes: &[DataChunk]) -> Box<dyn EncryptionStrategy> {
+    pub(crate) fn strategy(&self, key_bytes: &[DataChunk]) -> Result<Box<dyn EncryptionStrategy>> {
+        Ok(match self {
+            Self::Des => Box::new(DesEncryptionStrategy::new(key_bytes)?),
+            Self::Tdes => Box::new(TripleDesEncryptionStrategy::new(key_bytes)?),
+            Self::DesLegacy => Box::new(DesEncryptionStrategy::legacy(key_bytes)?),
+            Self::TdesLegacyEee => Box::new(TripleDesEncryptionStrategy::legacy_eee(key_bytes)?),
+            Self::Aes128 => Box::new(Aes128EncryptionStrategy::new(key_bytes)?),
+            Self::Aes192 => Box::new(Aes192EncryptionStrategy::new(key_bytes)?),
+            Self::Aes256 => Box::new(Aes256EncryptionStrategy::new(key_bytes)?),
+        })
+    }
+
+    // Weak, semi-weak and degenerate keys are the concern of the DES family only
+    pub(crate) fn check_key(&self, key_bytes: &[DataChunk]) -> Result<()> {
         match self {
-            Self::Des => Box::new(DesEncryptionStrategy::new(key_bytes)),
-            Self::Tdes => Box::new(TripleDesEncryptionStrategy::new(key_bytes)),
-            Self::Aes128 => Box::new(Aes128EncryptionStrategy::new(key_bytes)),
-            Self::Aes192 => Box::new(Aes192EncryptionStrategy::new(key_bytes)),
-            Self::Aes256 => Box::new(Aes256EncryptionStrategy::new(key_bytes)),
+            Self::Des | Self::DesLegacy => {
+                DesCipher::new_checked(key_bytes.as_flattened())?;
+            }
+            Self::Tdes | Self::TdesLegacyEee => {
+                TripleDesCipher::new_checked(key_bytes.as_flattened())?;
+            }
+            _ => (),
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn set_key_parity(&self, key_bytes: &mut [DataChunk]) {
+        if self.has_parity_bits() {
+            key_bytes.iter_mut().for_each(set_odd_parity);
         }
     }
 
-    pub const fn key_size(&self) -> usize {
+    pub(crate) fn has_key_parity(&self, key_bytes: &[DataChunk]) -> bool {
+        !self.has_parity_bits() || key_bytes.iter().all(has_odd_parity)
+    }
+
+    const fn has_parity_bits(&self) -> bool {
+        matches!(
+            self,
+            Self::Des | Self::Tdes | Self::DesLegacy | Self::TdesLegacyEee
+        )
+    }
+
+    // Keying option 1, the other 3DES key sizes are accepted but never generated by default
+    pub(crate) const fn key_size(&self) -> usize {
         match self {
-            Self::Des => 1,
-            Self::Tdes => 3,
+            Self::Des | Self::DesLegacy => 1,
+            Self::Tdes | Self::TdesLegacyEee => 3,
             Self::Aes128 => 2,
             Self::Aes192 => 3,
             Self::Aes256 => 4,
         }
     }
+
+    pub(crate) const fn supports_key_size(&self, key_chunks: usize) -> bool {
+        match self {
+            Self::Tdes => key_chunks >= 1 && key_chunks <= 3,
+            _ => key_chunks == self.key_size(),
+        }
+    }
+
+    pub(crate) const fn is_legacy(&self) -> bool {
+        matches!(self, Self::DesLegacy | Self::TdesLegacyEee)
+    }
+
+    pub(crate) const fn block_size(&self) -> usize {
+        match self {
+            Self::Des | Self::Tdes | Self::DesLegacy | Self::TdesLegacyEee => 1,
+            _ => AES_BLOCK_CHUNKS,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -68,6 +128,39 @@ mod tests {
         [0x3A, 0x4B, 0x5C, 0x6D, 0x7E, 0x8F, 0x90, 0xA1],
     ];
 
+    #[test]
+    fn des_parity_set() {
+        let mut key = KEY_128_BITS;
+        EncryptionSchemes::Des.set_key_parity(&mut key);
+
+        let left = EncryptionSchemes::Des.has_key_parity(&key);
+        let right = true;
+
+        assert_eq!(left, right);
+        assert!(!EncryptionSchemes::Des.has_key_parity(&KEY_128_BITS));
+    }
+
+    #[test]
+    fn aes_keys_untouched() {
+        let mut key = KEY_128_BITS;
+        EncryptionSchemes::Aes128.set_key_parity(&mut key);
+
+        let left = key;
+        let right = KEY_128_BITS;
+
+        assert_eq!(left, right);
+        assert!(EncryptionSchemes::Aes128.check_key(&[[0u8; 8]; 2]).is_ok());
+    }
+
+    #[test]
+    fn weak_des_key_reported() {
+        assert!(EncryptionSchemes::Des.check_key(&[[0x01; 8]]).is_err());
+        assert!(EncryptionSchemes::Tdes
+            .check_key(&[KEY_196_BITS[0], KEY_196_BITS[0], KEY_196_BITS[2]])
+            .is_err());
+        assert!(EncryptionSchemes::Tdes.check_key(&KEY_196_BITS).is_ok());
+    }
+
     pub const PLAINTEXT_64_BITS: [DataChunk; 1] =
         [[0x4A, 0x7F, 0x22, 0xC3, 0x8D, 0x5E, 0xA1, 0x3B]; 1];
 
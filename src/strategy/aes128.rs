@@ -1,53 +1,32 @@
-use aes::{key::AesKeySize, AesCipher};
+use aes::Aes128Cipher;
 
-use crate::DataChunk;
+use super::block_cipher::BlockCipherStrategy;
 
-use super::{
-    aes_commons::{aes_decrypt, aes_encrypt, init_aes_scheme},
-    EncryptionStrategy,
-};
-
-pub(super) struct Aes128EncryptionStrategy {
-    scheme: AesCipher,
-}
-
-impl Aes128EncryptionStrategy {
-    pub fn new(key_bytes: &[DataChunk]) -> Self {
-        let scheme = init_aes_scheme(key_bytes, AesKeySize::Aes128);
-
-        Self { scheme }
-    }
-}
-
-impl EncryptionStrategy for Aes128EncryptionStrategy {
-    fn encrypt(&self, plaintext: &[DataChunk]) -> Vec<DataChunk> {
-        aes_encrypt(&self.scheme, plaintext)
-    }
-
-    fn decrypt(&self, ciphertext: &[DataChunk]) -> Vec<DataChunk> {
-        aes_decrypt(&self.scheme, ciphertext)
-    }
-}
+pub(super) type Aes128EncryptionStrategy = BlockCipherStrategy<Aes128Cipher>;
 
 #[cfg(test)]
 mod tests {
-    use crate::strategy::{
-        aes_commons::AES_BLOCK_CHUNKS,
-        tests::{KEY_128_BITS, PLAINTEXT_128_BITS},
+    use crate::{
+        error::SymciphError,
+        strategy::{
+            tests::{KEY_128_BITS, PLAINTEXT_128_BITS},
+            EncryptionStrategy, AES_BLOCK_CHUNKS,
+        },
+        DataChunk,
     };
 
     use super::*;
 
     const AES128_CIPHERTEXT: [DataChunk; AES_BLOCK_CHUNKS] = [
-        [0x1C, 0xE9, 0xF9, 0xE1, 0xAC, 0x8A, 0xCD, 0x69],
-        [0x7F, 0x52, 0xE4, 0xF6, 0x0C, 0x2C, 0xFC, 0x73],
+        [0x48, 0xFC, 0xAD, 0xFF, 0xF4, 0x84, 0x29, 0xF3],
+        [0x59, 0x1A, 0x25, 0x2C, 0x63, 0x60, 0xC0, 0x5C],
     ];
 
     #[test]
     fn encrypt_single_block() {
-        let scheme = Aes128EncryptionStrategy::new(&KEY_128_BITS);
+        let scheme = Aes128EncryptionStrategy::new(&KEY_128_BITS).unwrap();
 
-        let ciphertext = scheme.encrypt(&PLAINTEXT_128_BITS);
+        let ciphertext = scheme.encrypt(&PLAINTEXT_128_BITS).unwrap();
 
         let left = ciphertext.as_slice();
         let right = &AES128_CIPHERTEXT;
@@ -57,9 +36,9 @@ mod tests {
 
     #[test]
     fn decrypt_single_block() {
-        let scheme = Aes128EncryptionStrategy::new(&KEY_128_BITS);
+        let scheme = Aes128EncryptionStrategy::new(&KEY_128_BITS).unwrap();
 
-        let plaintext = scheme.decrypt(&AES128_CIPHERTEXT);
+        let plaintext = scheme.decrypt(&AES128_CIPHERTEXT).unwrap();
 
         let left = plaintext.as_slice();
         let right = &PLAINTEXT_128_BITS;
@@ -67,32 +46,36 @@ mod tests {
         assert_eq!(left, right)
     }
 
-    #[should_panic]
     #[test]
     fn encrypt_illegal_buffer() {
-        let scheme = Aes128EncryptionStrategy::new(&KEY_128_BITS);
+        let scheme = Aes128EncryptionStrategy::new(&KEY_128_BITS).unwrap();
 
-        scheme.decrypt(&[PLAINTEXT_128_BITS[0]]);
+        let left = scheme.encrypt(&[PLAINTEXT_128_BITS[0]]);
+
+        assert!(matches!(left, Err(SymciphError::BlockAlignment(_))));
     }
 
-    #[should_panic]
     #[test]
     fn decrypt_illegal_buffer() {
-        let scheme = Aes128EncryptionStrategy::new(&KEY_128_BITS);
+        let scheme = Aes128EncryptionStrategy::new(&KEY_128_BITS).unwrap();
+
+        let left = scheme.decrypt(&[AES128_CIPHERTEXT[0]]);
 
-        scheme.decrypt(&[AES128_CIPHERTEXT[0]]);
+        assert!(matches!(left, Err(SymciphError::BlockAlignment(_))));
     }
 
     #[test]
     fn encrypt_two_blocks() {
-        let scheme = Aes128EncryptionStrategy::new(&KEY_128_BITS);
+        let scheme = Aes128EncryptionStrategy::new(&KEY_128_BITS).unwrap();
 
-        let ciphertext = scheme.encrypt(&[
-            PLAINTEXT_128_BITS[0],
-            PLAINTEXT_128_BITS[1],
-            PLAINTEXT_128_BITS[0],
-            PLAINTEXT_128_BITS[1],
-        ]);
+        let ciphertext = scheme
+            .encrypt(&[
+                PLAINTEXT_128_BITS[0],
+                PLAINTEXT_128_BITS[1],
+                PLAINTEXT_128_BITS[0],
+                PLAINTEXT_128_BITS[1],
+            ])
+            .unwrap();
 
         let left = ciphertext.as_slice();
         let right = &[
@@ -107,14 +90,16 @@ mod tests {
 
     #[test]
     fn decrypt_two_blocks() {
-        let scheme = Aes128EncryptionStrategy::new(&KEY_128_BITS);
-
-        let plaintext = scheme.decrypt(&[
-            AES128_CIPHERTEXT[0],
-            AES128_CIPHERTEXT[1],
-            AES128_CIPHERTEXT[0],
-            AES128_CIPHERTEXT[1],
-        ]);
+        let scheme = Aes128EncryptionStrategy::new(&KEY_128_BITS).unwrap();
+
+        let plaintext = scheme
+            .decrypt(&[
+                AES128_CIPHERTEXT[0],
+                AES128_CIPHERTEXT[1],
+                AES128_CIPHERTEXT[0],
+                AES128_CIPHERTEXT[1],
+            ])
+            .unwrap();
 
         let left = plaintext.as_slice();
         let right = &[
@@ -1,53 +1,32 @@
-use aes::{key::AesKeySize, AesCipher};
+use aes::Aes256Cipher;
 
-use crate::DataChunk;
+use super::block_cipher::BlockCipherStrategy;
 
-use super::{
-    aes_commons::{aes_decrypt, aes_encrypt, init_aes_scheme},
-    EncryptionStrategy,
-};
-
-pub(super) struct Aes256EncryptionStrategy {
-    scheme: AesCipher,
-}
-
-impl Aes256EncryptionStrategy {
-    pub fn new(key_bytes: &[DataChunk]) -> Self {
-        let scheme = init_aes_scheme(key_bytes, AesKeySize::Aes256);
-
-        Self { scheme }
-    }
-}
-
-impl EncryptionStrategy for Aes256EncryptionStrategy {
-    fn encrypt(&self, plaintext: &[DataChunk]) -> Vec<DataChunk> {
-        aes_encrypt(&self.scheme, plaintext)
-    }
-
-    fn decrypt(&self, ciphertext: &[DataChunk]) -> Vec<DataChunk> {
-        aes_decrypt(&self.scheme, ciphertext)
-    }
-}
+pub(super) type Aes256EncryptionStrategy = BlockCipherStrategy<Aes256Cipher>;
 
 #[cfg(test)]
 mod tests {
-    use crate::strategy::{
-        aes_commons::AES_BLOCK_CHUNKS,
-        tests::{KEY_256_BITS, PLAINTEXT_128_BITS},
+    use crate::{
+        error::SymciphError,
+        strategy::{
+            tests::{KEY_256_BITS, PLAINTEXT_128_BITS},
+            EncryptionStrategy, AES_BLOCK_CHUNKS,
+        },
+        DataChunk,
     };
 
     use super::*;
 
     const AES192_CIPHERTEXT: [DataChunk; AES_BLOCK_CHUNKS] = [
-        [0x68, 0x52, 0x27, 0x55, 0x5B, 0xAB, 0xA0, 0xC1],
-        [0xAB, 0xF7, 0x20, 0x42, 0xC6, 0x8D, 0x8D, 0xA6],
+        [0x90, 0x34, 0x8B, 0x23, 0x3E, 0x0C, 0x22, 0x16],
+        [0x18, 0xD4, 0xFF, 0x08, 0xF4, 0xE9, 0xEC, 0xC8],
     ];
 
     #[test]
     fn encrypt_single_block() {
-        let scheme = Aes256EncryptionStrategy::new(&KEY_256_BITS);
+        let scheme = Aes256EncryptionStrategy::new(&KEY_256_BITS).unwrap();
 
-        let ciphertext = scheme.encrypt(&PLAINTEXT_128_BITS);
+        let ciphertext = scheme.encrypt(&PLAINTEXT_128_BITS).unwrap();
 
         let left = ciphertext.as_slice();
         let right = &AES192_CIPHERTEXT;
@@ -57,9 +36,9 @@ mod tests {
 
     #[test]
     fn decrypt_single_block() {
-        let scheme = Aes256EncryptionStrategy::new(&KEY_256_BITS);
+        let scheme = Aes256EncryptionStrategy::new(&KEY_256_BITS).unwrap();
 
-        let plaintext = scheme.decrypt(&AES192_CIPHERTEXT);
+        let plaintext = scheme.decrypt(&AES192_CIPHERTEXT).unwrap();
 
         let left = plaintext.as_slice();
         let right = &PLAINTEXT_128_BITS;
@@ -67,32 +46,36 @@ mod tests {
         assert_eq!(left, right)
     }
 
-    #[should_panic]
     #[test]
     fn encrypt_illegal_buffer() {
-        let scheme = Aes256EncryptionStrategy::new(&KEY_256_BITS);
+        let scheme = Aes256EncryptionStrategy::new(&KEY_256_BITS).unwrap();
 
-        scheme.decrypt(&[PLAINTEXT_128_BITS[0]]);
+        let left = scheme.encrypt(&[PLAINTEXT_128_BITS[0]]);
+
+        assert!(matches!(left, Err(SymciphError::BlockAlignment(_))));
     }
 
-    #[should_panic]
     #[test]
     fn decrypt_illegal_buffer() {
-        let scheme = Aes256EncryptionStrategy::new(&KEY_256_BITS);
+        let scheme = Aes256EncryptionStrategy::new(&KEY_256_BITS).unwrap();
+
+        let left = scheme.decrypt(&[AES192_CIPHERTEXT[0]]);
 
-        scheme.decrypt(&[AES192_CIPHERTEXT[0]]);
+        assert!(matches!(left, Err(SymciphError::BlockAlignment(_))));
     }
 
     #[test]
     fn encrypt_two_blocks() {
-        let scheme = Aes256EncryptionStrategy::new(&KEY_256_BITS);
+        let scheme = Aes256EncryptionStrategy::new(&KEY_256_BITS).unwrap();
 
-        let ciphertext = scheme.encrypt(&[
-            PLAINTEXT_128_BITS[0],
-            PLAINTEXT_128_BITS[1],
-            PLAINTEXT_128_BITS[0],
-            PLAINTEXT_128_BITS[1],
-        ]);
+        let ciphertext = scheme
+            .encrypt(&[
+                PLAINTEXT_128_BITS[0],
+                PLAINTEXT_128_BITS[1],
+                PLAINTEXT_128_BITS[0],
+                PLAINTEXT_128_BITS[1],
+            ])
+            .unwrap();
 
         let left = ciphertext.as_slice();
         let right = &[
@@ -107,14 +90,16 @@ mod tests {
 
     #[test]
     fn decrypt_two_blocks() {
-        let scheme = Aes256EncryptionStrategy::new(&KEY_256_BITS);
-
-        let plaintext = scheme.decrypt(&[
-            AES192_CIPHERTEXT[0],
-            AES192_CIPHERTEXT[1],
-            AES192_CIPHERTEXT[0],
-            AES192_CIPHERTEXT[1],
-        ]);
+        let scheme = Aes256EncryptionStrategy::new(&KEY_256_BITS).unwrap();
+
+        let plaintext = scheme
+            .decrypt(&[
+                AES192_CIPHERTEXT[0],
+                AES192_CIPHERTEXT[1],
+                AES192_CIPHERTEXT[0],
+                AES192_CIPHERTEXT[1],
+            ])
+            .unwrap();
 
         let left = plaintext.as_slice();
         let right = &[
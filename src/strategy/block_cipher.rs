@@ -0,0 +1,47 @@
+use crypto_primitives::block_cipher::BlockCipher;
+
+use crate::{
+    error::{Result, SymciphError},
+    DataChunk, BLOCK_CHUNK_SIZE,
+};
+
+use super::EncryptionStrategy;
+
+pub(super) struct BlockCipherStrategy<C> {
+    cipher: C,
+}
+
+impl<C: BlockCipher> BlockCipherStrategy<C>
+where
+    SymciphError: From<C::KeyError>,
+{
+    pub fn new(key_bytes: &[DataChunk]) -> Result<Self> {
+        Ok(Self::from_cipher(C::new(key_bytes.as_flattened())?))
+    }
+}
+
+impl<C> BlockCipherStrategy<C> {
+    pub fn from_cipher(cipher: C) -> Self {
+        Self { cipher }
+    }
+}
+
+impl<C: BlockCipher> EncryptionStrategy for BlockCipherStrategy<C> {
+    fn encrypt(&self, plaintext: &[DataChunk]) -> Result<Vec<DataChunk>> {
+        let mut ciphertext = plaintext.to_vec();
+        self.cipher.encrypt_blocks(ciphertext.as_flattened_mut())?;
+
+        Ok(ciphertext)
+    }
+
+    fn decrypt(&self, ciphertext: &[DataChunk]) -> Result<Vec<DataChunk>> {
+        let mut plaintext = ciphertext.to_vec();
+        self.cipher.decrypt_blocks(plaintext.as_flattened_mut())?;
+
+        Ok(plaintext)
+    }
+
+    fn block_size(&self) -> usize {
+        C::BLOCK_SIZE / BLOCK_CHUNK_SIZE
+    }
+}
@@ -1,74 +1,66 @@
 use des::DesCipher;
 
-use crate::DataChunk;
+use crate::{error::Result, DataChunk};
 
-use super::EncryptionStrategy;
+use super::block_cipher::BlockCipherStrategy;
 
-const DES_KEY_CHUNKS: usize = 1;
-
-pub(super) struct DesEncryptionStrategy {
-    scheme: DesCipher,
-}
+pub(super) type DesEncryptionStrategy = BlockCipherStrategy<DesCipher>;
 
 impl DesEncryptionStrategy {
-    pub fn new(key_bytes: &[DataChunk]) -> Self {
-        assert!(
-            key_bytes.len() == DES_KEY_CHUNKS,
-            "DES: Key not 64 bits long"
-        );
-
-        let key = key_bytes[0];
-        let scheme = DesCipher::new(&key);
-
-        Self { scheme }
+    pub fn legacy(key_bytes: &[DataChunk]) -> Result<Self> {
+        Ok(Self::from_cipher(DesCipher::new_legacy(
+            key_bytes.as_flattened(),
+        )?))
     }
 }
 
-impl EncryptionStrategy for DesEncryptionStrategy {
-    fn encrypt(&self, plaintext: &[DataChunk]) -> Vec<DataChunk> {
-        let mut ciphertext_blocks: Vec<DataChunk> = Vec::new();
+#[cfg(test)]
+mod tests {
+    use crate::strategy::{tests::*, EncryptionStrategy};
 
-        for plaintext_block in plaintext {
-            ciphertext_blocks.push(self.scheme.encrypt(plaintext_block));
-        }
+    use super::*;
 
-        ciphertext_blocks
-    }
+    const DES_CIPHERTEXT: [DataChunk; 1] = [[0x39, 0x00, 0x32, 0xAC, 0xA4, 0xC2, 0x4E, 0x52]; 1];
+    const LEGACY_DES_CIPHERTEXT: [DataChunk; 1] =
+        [[0x4E, 0x7F, 0x27, 0xC3, 0x9C, 0x0F, 0xF0, 0x2B]; 1];
 
-    fn decrypt(&self, ciphertext: &[DataChunk]) -> Vec<DataChunk> {
-        let mut plaintext_blocks: Vec<DataChunk> = Vec::new();
+    #[test]
+    fn encrypt() {
+        let strategy = DesEncryptionStrategy::new(&KEY_64_BITS).unwrap();
+        let ciphertext = strategy.encrypt(&PLAINTEXT_64_BITS).unwrap();
 
-        for ciphertext_block in ciphertext {
-            plaintext_blocks.push(self.scheme.decrypt(ciphertext_block));
-        }
+        let left = ciphertext[0];
+        let right = DES_CIPHERTEXT[0];
 
-        plaintext_blocks
+        assert_eq!(left, right);
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use crate::strategy::tests::*;
+    #[test]
+    fn decrypt() {
+        let strategy = DesEncryptionStrategy::new(&KEY_64_BITS).unwrap();
+        let plaintext = strategy.decrypt(&DES_CIPHERTEXT).unwrap();
 
-    use super::*;
+        let left = plaintext[0];
+        let right = PLAINTEXT_64_BITS[0];
 
-    const DES_CIPHERTEXT: [DataChunk; 1] = [[0x4E, 0x7F, 0x27, 0xC3, 0x9C, 0x0F, 0xF0, 0x2B]; 1];
+        assert_eq!(left, right);
+    }
 
     #[test]
-    fn encrypt() {
-        let strategy = DesEncryptionStrategy::new(&KEY_64_BITS);
-        let ciphertext = strategy.encrypt(&PLAINTEXT_64_BITS);
+    fn legacy_encrypt() {
+        let strategy = DesEncryptionStrategy::legacy(&KEY_64_BITS).unwrap();
+        let ciphertext = strategy.encrypt(&PLAINTEXT_64_BITS).unwrap();
 
         let left = ciphertext[0];
-        let right = DES_CIPHERTEXT[0];
+        let right = LEGACY_DES_CIPHERTEXT[0];
 
         assert_eq!(left, right);
     }
 
     #[test]
-    fn decrypt() {
-        let strategy = DesEncryptionStrategy::new(&KEY_64_BITS);
-        let plaintext = strategy.decrypt(&DES_CIPHERTEXT);
+    fn legacy_decrypt() {
+        let strategy = DesEncryptionStrategy::legacy(&KEY_64_BITS).unwrap();
+        let plaintext = strategy.decrypt(&LEGACY_DES_CIPHERTEXT).unwrap();
 
         let left = plaintext[0];
         let right = PLAINTEXT_64_BITS[0];
@@ -1,75 +1,95 @@
 use triple_des::TripleDesCipher;
 
-use crate::DataChunk;
+use crate::{error::Result, DataChunk};
 
-use super::EncryptionStrategy;
+use super::block_cipher::BlockCipherStrategy;
 
-const TRIPLE_DES_KEY_CHUNKS: usize = 3;
-
-pub(super) struct TripleDesEncryptionStrategy {
-    scheme: TripleDesCipher,
-}
+pub(super) type TripleDesEncryptionStrategy = BlockCipherStrategy<TripleDesCipher>;
 
 impl TripleDesEncryptionStrategy {
-    pub fn new(key_bytes: &[DataChunk]) -> Self {
-        assert!(
-            key_bytes.len() == TRIPLE_DES_KEY_CHUNKS,
-            "3DES: Key not 196 bits long"
-        );
-
-        let key = [key_bytes[0], key_bytes[1], key_bytes[2]];
-        let scheme = TripleDesCipher::new(&key);
-
-        Self { scheme }
-    }
-}
-
-impl EncryptionStrategy for TripleDesEncryptionStrategy {
-    fn encrypt(&self, plaintext: &[DataChunk]) -> Vec<DataChunk> {
-        let mut ciphertext_blocks: Vec<DataChunk> = Vec::new();
-
-        for plaintext_block in plaintext {
-            ciphertext_blocks.push(self.scheme.encrypt(plaintext_block));
-        }
-
-        ciphertext_blocks
-    }
-
-    fn decrypt(&self, ciphertext: &[DataChunk]) -> Vec<DataChunk> {
-        let mut plaintext_blocks: Vec<DataChunk> = Vec::new();
-
-        for ciphertext_block in ciphertext {
-            plaintext_blocks.push(self.scheme.decrypt(ciphertext_block));
-        }
-
-        plaintext_blocks
+    pub fn legacy_eee(key_bytes: &[DataChunk]) -> Result<Self> {
+        Ok(Self::from_cipher(TripleDesCipher::new_legacy_eee(
+            key_bytes.as_flattened(),
+        )?))
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::strategy::tests::*;
+    use crate::{
+        error::SymciphError,
+        strategy::{tests::*, EncryptionStrategy},
+        EncryptionSchemes,
+    };
 
     use super::*;
 
     const TRIPLE_DES_CIPHERTEXT: [DataChunk; 1] =
+        [[0xBC, 0xD9, 0x4F, 0x84, 0x57, 0x6A, 0xE5, 0x2D]; 1];
+    const TWO_KEY_TRIPLE_DES_CIPHERTEXT: [DataChunk; 1] =
+        [[0x6C, 0xFA, 0x34, 0x24, 0xF4, 0xFE, 0xF1, 0xDD]; 1];
+    const LEGACY_EEE_CIPHERTEXT: [DataChunk; 1] =
         [[0x0B, 0x7A, 0x37, 0xC2, 0x88, 0x5A, 0xE0, 0x2E]; 1];
 
     #[test]
     fn encrypt() {
-        let strategy = TripleDesEncryptionStrategy::new(&KEY_196_BITS);
+        let strategy = TripleDesEncryptionStrategy::new(&KEY_196_BITS).unwrap();
 
-        let ciphertext = strategy.encrypt(&PLAINTEXT_64_BITS);
+        let ciphertext = strategy.encrypt(&PLAINTEXT_64_BITS).unwrap();
 
         assert_eq!(ciphertext, TRIPLE_DES_CIPHERTEXT);
     }
 
     #[test]
     fn decrypt() {
-        let strategy = TripleDesEncryptionStrategy::new(&KEY_196_BITS);
+        let strategy = TripleDesEncryptionStrategy::new(&KEY_196_BITS).unwrap();
 
-        let plaintext = strategy.decrypt(&TRIPLE_DES_CIPHERTEXT);
+        let plaintext = strategy.decrypt(&TRIPLE_DES_CIPHERTEXT).unwrap();
 
         assert_eq!(plaintext, PLAINTEXT_64_BITS);
     }
+
+    #[test]
+    fn two_key_encrypt() {
+        let strategy = TripleDesEncryptionStrategy::new(&KEY_128_BITS).unwrap();
+
+        let ciphertext = strategy.encrypt(&PLAINTEXT_64_BITS).unwrap();
+
+        assert_eq!(ciphertext, TWO_KEY_TRIPLE_DES_CIPHERTEXT);
+    }
+
+    #[test]
+    fn single_key_matches_des() {
+        let strategy = TripleDesEncryptionStrategy::new(&KEY_64_BITS).unwrap();
+
+        let left = strategy.encrypt(&PLAINTEXT_64_BITS).unwrap();
+        let right = EncryptionSchemes::Des
+            .strategy(&KEY_64_BITS)
+            .unwrap()
+            .encrypt(&PLAINTEXT_64_BITS)
+            .unwrap();
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn legacy_eee_round_trip() {
+        let strategy = TripleDesEncryptionStrategy::legacy_eee(&KEY_196_BITS).unwrap();
+
+        assert_eq!(
+            strategy.encrypt(&PLAINTEXT_64_BITS).unwrap(),
+            LEGACY_EEE_CIPHERTEXT
+        );
+        assert_eq!(
+            strategy.decrypt(&LEGACY_EEE_CIPHERTEXT).unwrap(),
+            PLAINTEXT_64_BITS
+        );
+    }
+
+    #[test]
+    fn four_keys_rejected() {
+        let left = TripleDesEncryptionStrategy::new(&KEY_256_BITS);
+
+        assert!(matches!(left, Err(SymciphError::KeyLength(_))));
+    }
 }
@@ -0,0 +1,157 @@
+use std::{
+    fs::read,
+    io::{Cursor, Read, Write},
+};
+
+use symciph::{
+    DecryptingReader, Decryptor, EncryptingWriter, EncryptionModes, EncryptionSchemes, Encryptor,
+    SymciphError,
+};
+
+macro_rules! integration_test_data_prefix {
+    () => {
+        "tests/data/"
+    };
+}
+
+// Writes in uneven pieces, so units keep straddling the writes
+fn helper_write_in_pieces(writer: &mut impl Write, data: &[u8]) {
+    for piece in data.chunks(7) {
+        writer.write_all(piece).unwrap();
+    }
+}
+
+// Reads in uneven pieces through a buffer smaller than any unit
+fn helper_read_in_pieces(reader: &mut impl Read) -> Result<Vec<u8>, SymciphError> {
+    let mut data = Vec::new();
+    let mut buffer = [0u8; 5];
+
+    loop {
+        match reader.read(&mut buffer)? {
+            0 => return Ok(data),
+            bytes_read => data.extend_from_slice(&buffer[..bytes_read]),
+        }
+    }
+}
+
+#[test]
+fn streams_round_trip_in_every_mode() {
+    let plaintext: Vec<u8> = (0..1000).map(|byte| byte as u8).collect();
+    let key = read(concat!(integration_test_data_prefix!(), "aes256.key")).unwrap();
+    let double_key = [&key[..], &key.iter().map(|byte| !byte).collect::<Vec<_>>()].concat();
+
+    for (mode, key) in [
+        (EncryptionModes::Ecb, &key),
+        (EncryptionModes::Cbc, &key),
+        (EncryptionModes::CbcCs1, &key),
+        (EncryptionModes::Pcbc, &key),
+        (EncryptionModes::Cfb, &key),
+        (EncryptionModes::Ofb, &key),
+        (EncryptionModes::Ctr, &key),
+        (EncryptionModes::Gcm, &key),
+        (EncryptionModes::GcmStream, &key),
+        (EncryptionModes::Eax, &key),
+        (EncryptionModes::Xts, &double_key),
+    ] {
+        let mut writer = Encryptor::new(EncryptionSchemes::Aes256)
+            .mode(mode)
+            .key(key)
+            .segment_size(64)
+            .sector_size(96)
+            .writer(Vec::new())
+            .unwrap();
+        helper_write_in_pieces(&mut writer, &plaintext);
+        let ciphertext = writer.finish().unwrap();
+
+        let mut reader = DecryptingReader::new(Cursor::new(ciphertext), key).unwrap();
+
+        let left = helper_read_in_pieces(&mut reader).unwrap();
+        let right = plaintext.clone();
+
+        assert_eq!(left, right);
+    }
+}
+
+#[test]
+fn writer_matches_encryptor() {
+    let plaintext = read(concat!(integration_test_data_prefix!(), "in.60_bytes.txt")).unwrap();
+    let key = read(concat!(integration_test_data_prefix!(), "aes128.key")).unwrap();
+
+    // SIV without a nonce is deterministic, so both paths must give the same bytes
+    let encryptor = Encryptor::new(EncryptionSchemes::Aes128)
+        .mode(EncryptionModes::Siv)
+        .key(&[&key[..], &key[..]].concat())
+        .nonce_len(0);
+
+    let mut writer = encryptor.writer(Vec::new()).unwrap();
+    helper_write_in_pieces(&mut writer, &plaintext);
+
+    let mut ciphertext = Vec::new();
+    encryptor
+        .encrypt(&mut Cursor::new(&plaintext), &mut ciphertext)
+        .unwrap();
+
+    let left = writer.finish().unwrap();
+    let right = ciphertext;
+
+    assert_eq!(left, right);
+}
+
+#[test]
+fn writers_nest() {
+    let plaintext = b"wrapped twice, unwrapped twice";
+    let des_key = read(concat!(integration_test_data_prefix!(), "des.key")).unwrap();
+    let aes_key = read(concat!(integration_test_data_prefix!(), "aes128.key")).unwrap();
+
+    let outer = EncryptingWriter::new(
+        Vec::new(),
+        EncryptionSchemes::Aes128,
+        EncryptionModes::Ctr,
+        &aes_key,
+    )
+    .unwrap();
+    let mut inner = EncryptingWriter::new(
+        outer,
+        EncryptionSchemes::Des,
+        EncryptionModes::Cbc,
+        &des_key,
+    )
+    .unwrap();
+    inner.write_all(plaintext).unwrap();
+    let ciphertext = inner.finish().unwrap().finish().unwrap();
+
+    let outer = DecryptingReader::new(Cursor::new(ciphertext), &aes_key).unwrap();
+    let mut inner = DecryptingReader::new(outer, &des_key).unwrap();
+
+    let left = helper_read_in_pieces(&mut inner).unwrap();
+    let right = plaintext.to_vec();
+
+    assert_eq!(left, right);
+}
+
+#[test]
+fn tampered_segment_fails_the_read() {
+    let plaintext = [0x42u8; 200];
+    let key = read(concat!(integration_test_data_prefix!(), "aes128.key")).unwrap();
+
+    let mut writer = Encryptor::new(EncryptionSchemes::Aes128)
+        .mode(EncryptionModes::GcmStream)
+        .key(&key)
+        .segment_size(64)
+        .writer(Vec::new())
+        .unwrap();
+    writer.write_all(&plaintext).unwrap();
+    let mut ciphertext = writer.finish().unwrap();
+
+    let last = ciphertext.len() - 1;
+    ciphertext[last] ^= 0x01;
+
+    let mut reader = Decryptor::new()
+        .key(&key)
+        .reader(Cursor::new(ciphertext))
+        .unwrap();
+
+    let left = helper_read_in_pieces(&mut reader);
+
+    assert!(matches!(left, Err(SymciphError::Authentication(_))));
+}
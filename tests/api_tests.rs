@@ -0,0 +1,391 @@
+use std::{
+    fs::{read, remove_file},
+    io::Cursor,
+    path::Path,
+    process::{Command, Output},
+};
+
+use symciph::{
+    decrypt_file, encrypt_file, Decryptor, EncryptionModes, EncryptionSchemes, Encryptor,
+    KdfSchemes, PaddingSchemes, SymciphError,
+};
+
+macro_rules! integration_test_data_prefix {
+    () => {
+        "tests/data/"
+    };
+}
+
+fn helper_symciph(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_symciph"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+fn helper_encrypt(encryptor: &Encryptor, plaintext: &[u8]) -> Vec<u8> {
+    let mut ciphertext = Vec::new();
+    encryptor
+        .encrypt(&mut Cursor::new(plaintext), &mut ciphertext)
+        .unwrap();
+
+    ciphertext
+}
+
+fn helper_decrypt(decryptor: &Decryptor, ciphertext: &[u8]) -> Result<Vec<u8>, SymciphError> {
+    let mut plaintext = Vec::new();
+    decryptor.decrypt(&mut Cursor::new(ciphertext), &mut plaintext)?;
+
+    Ok(plaintext)
+}
+
+#[test]
+fn in_memory_round_trip_in_every_mode() {
+    let plaintext = read(concat!(integration_test_data_prefix!(), "in.60_bytes.txt")).unwrap();
+    let key = read(concat!(integration_test_data_prefix!(), "aes256.key")).unwrap();
+    let double_key = [&key[..], &key.iter().map(|byte| !byte).collect::<Vec<_>>()].concat();
+
+    for (mode, key) in [
+        (EncryptionModes::Ecb, &key),
+        (EncryptionModes::Cbc, &key),
+        (EncryptionModes::CbcCs3, &key),
+        (EncryptionModes::Pcbc, &key),
+        (EncryptionModes::Cfb8, &key),
+        (EncryptionModes::Ofb, &key),
+        (EncryptionModes::Ctr, &key),
+        (EncryptionModes::Gcm, &key),
+        (EncryptionModes::GcmStream, &key),
+        (EncryptionModes::Ocb, &key),
+        (EncryptionModes::GcmSiv, &key),
+        (EncryptionModes::Siv, &double_key),
+        (EncryptionModes::Xts, &double_key),
+    ] {
+        let ciphertext = helper_encrypt(
+            &Encryptor::new(EncryptionSchemes::Aes256)
+                .mode(mode)
+                .key(key)
+                .segment_size(16),
+            &plaintext,
+        );
+
+        let left = helper_decrypt(&Decryptor::new().key(key), &ciphertext).unwrap();
+        let right = plaintext.clone();
+
+        assert_eq!(left, right);
+    }
+}
+
+#[test]
+fn raw_round_trip_needs_matching_options() {
+    let plaintext = read(concat!(integration_test_data_prefix!(), "in.60_bytes.txt")).unwrap();
+    let key = read(concat!(integration_test_data_prefix!(), "des.key")).unwrap();
+
+    let ciphertext = helper_encrypt(
+        &Encryptor::new(EncryptionSchemes::Des)
+            .mode(EncryptionModes::Cbc)
+            .padding(PaddingSchemes::Iso7816)
+            .key(&key)
+            .raw(true),
+        &plaintext,
+    );
+
+    let decryptor = Decryptor::new()
+        .cipher(EncryptionSchemes::Des)
+        .mode(EncryptionModes::Cbc)
+        .padding(PaddingSchemes::Iso7816)
+        .key(&key)
+        .raw(true);
+
+    let left = helper_decrypt(&decryptor, &ciphertext).unwrap();
+    let right = plaintext;
+
+    assert_eq!(left, right);
+
+    let left = helper_decrypt(&Decryptor::new().key(&key).raw(true), &ciphertext);
+
+    assert!(matches!(left, Err(SymciphError::InvalidInput(_))));
+}
+
+#[test]
+fn password_round_trip() {
+    let plaintext = b"correct horse battery staple";
+
+    let ciphertext = helper_encrypt(
+        &Encryptor::new(EncryptionSchemes::Aes128)
+            .mode(EncryptionModes::Gcm)
+            .password("hunter2")
+            .kdf(KdfSchemes::Pbkdf2)
+            .iterations(1000),
+        plaintext,
+    );
+
+    let left = helper_decrypt(&Decryptor::new().password("hunter2"), &ciphertext).unwrap();
+    let right = plaintext.to_vec();
+
+    assert_eq!(left, right);
+
+    let left = helper_decrypt(&Decryptor::new().password("hunter3"), &ciphertext);
+
+    assert!(matches!(left, Err(SymciphError::Authentication(_))));
+}
+
+// The KDF parameters start after the fixed fields and the KDF id and length
+const KDF_PARAMS_OFFSET: usize = 25;
+
+#[test]
+fn oversized_kdf_params_are_rejected() {
+    let encryptor = |kdf| {
+        Encryptor::new(EncryptionSchemes::Aes128)
+            .mode(EncryptionModes::Gcm)
+            .password("hunter2")
+            .kdf(kdf)
+            .memory_cost(256)
+            .time_cost(1)
+    };
+
+    let ciphertext = helper_encrypt(&encryptor(KdfSchemes::Argon2id), b"");
+
+    // Argon2id memory of 128 GiB and 255 lanes
+    for (position, bytes) in [(0, [0x08, 0x00, 0x00, 0x00]), (8, [0x00, 0x00, 0x01, 0x00])] {
+        let mut crafted = ciphertext.clone();
+        crafted[KDF_PARAMS_OFFSET + position..KDF_PARAMS_OFFSET + position + 4]
+            .copy_from_slice(&bytes);
+
+        let left = helper_decrypt(&Decryptor::new().password("hunter2"), &crafted);
+
+        assert!(matches!(left, Err(SymciphError::Format(_))));
+    }
+
+    let ciphertext = helper_encrypt(&encryptor(KdfSchemes::Scrypt), b"");
+
+    // scrypt N of 2^62 and 2^16 lanes
+    for (position, bytes) in [(0, vec![62]), (5, vec![0x00, 0x01, 0x00, 0x00])] {
+        let mut crafted = ciphertext.clone();
+        crafted[KDF_PARAMS_OFFSET + position..KDF_PARAMS_OFFSET + position + bytes.len()]
+            .copy_from_slice(&bytes);
+
+        let left = helper_decrypt(&Decryptor::new().password("hunter2"), &crafted);
+
+        assert!(matches!(left, Err(SymciphError::Format(_))));
+    }
+}
+
+// The counter bits and the data unit size follow the magic, version, cipher, mode and padding
+const COUNTER_BITS_OFFSET: usize = 8;
+const UNIT_SIZE_OFFSET: usize = 10;
+
+#[test]
+fn corrupted_header_fields_are_rejected() {
+    let key = read(concat!(integration_test_data_prefix!(), "aes128.key")).unwrap();
+    let xts_key = [key.clone(), key.iter().rev().cloned().collect()].concat();
+
+    let ctr = helper_encrypt(
+        &Encryptor::new(EncryptionSchemes::Aes128)
+            .mode(EncryptionModes::Ctr)
+            .key(&key),
+        b"",
+    );
+
+    for counter_bits in [0, 200] {
+        let mut crafted = ctr.clone();
+        crafted[COUNTER_BITS_OFFSET] = counter_bits;
+
+        let left = helper_decrypt(&Decryptor::new().key(&key), &crafted);
+
+        assert!(matches!(left, Err(SymciphError::Format(_))));
+    }
+
+    // An 8-byte IV in place of the 16-byte one, the rest of it is read as ciphertext
+    let mut crafted = helper_encrypt(
+        &Encryptor::new(EncryptionSchemes::Aes128)
+            .mode(EncryptionModes::Cbc)
+            .key(&key),
+        b"",
+    );
+    crafted[KDF_PARAMS_OFFSET] = 8;
+
+    let left = helper_decrypt(&Decryptor::new().key(&key), &crafted);
+
+    assert!(matches!(left, Err(SymciphError::Format(_))));
+
+    let mut crafted = helper_encrypt(
+        &Encryptor::new(EncryptionSchemes::Aes128)
+            .mode(EncryptionModes::Xts)
+            .key(&xts_key),
+        &[0x5A; 32],
+    );
+    crafted[UNIT_SIZE_OFFSET..UNIT_SIZE_OFFSET + 4].copy_from_slice(&[0x00; 4]);
+
+    let left = helper_decrypt(&Decryptor::new().key(&xts_key), &crafted);
+
+    assert!(matches!(left, Err(SymciphError::Format(_))));
+
+    let left = Decryptor::new().key(&xts_key).decrypt_range(
+        &mut Cursor::new(crafted),
+        &mut Vec::new(),
+        16,
+        None,
+    );
+
+    assert!(matches!(left, Err(SymciphError::Format(_))));
+}
+
+// A 13-byte nonce leaves CCM a 2-byte length field, so messages stop short of 64 KiB
+#[test]
+fn ccm_message_longer_than_length_field_is_rejected() {
+    let key = read(concat!(integration_test_data_prefix!(), "aes128.key")).unwrap();
+    let plaintext = vec![0x5A; 0x10001];
+
+    let left = Encryptor::new(EncryptionSchemes::Aes128)
+        .mode(EncryptionModes::Ccm)
+        .nonce_len(13)
+        .key(&key)
+        .encrypt(&mut Cursor::new(&plaintext), &mut Vec::new());
+
+    assert!(matches!(left, Err(SymciphError::InvalidInput(_))));
+
+    // Read with a nonce one byte longer, the ciphertext is still one byte too long
+    let ciphertext = helper_encrypt(
+        &Encryptor::new(EncryptionSchemes::Aes128)
+            .mode(EncryptionModes::Ccm)
+            .nonce_len(12)
+            .raw(true)
+            .key(&key),
+        &plaintext,
+    );
+
+    let left = helper_decrypt(
+        &Decryptor::new()
+            .cipher(EncryptionSchemes::Aes128)
+            .mode(EncryptionModes::Ccm)
+            .nonce_len(13)
+            .raw(true)
+            .key(&key),
+        &ciphertext,
+    );
+
+    assert!(matches!(left, Err(SymciphError::InvalidInput(_))));
+}
+
+#[test]
+fn errors_are_typed() {
+    let key = read(concat!(integration_test_data_prefix!(), "aes128.key")).unwrap();
+    let mut ciphertext = Vec::new();
+
+    let left = Encryptor::new(EncryptionSchemes::Aes256)
+        .key(&key)
+        .encrypt(&mut Cursor::new(b""), &mut ciphertext);
+
+    assert!(matches!(left, Err(SymciphError::KeyLength(_))));
+
+    let left = Encryptor::new(EncryptionSchemes::Aes128)
+        .mode(EncryptionModes::Ctr)
+        .key(&key)
+        .counter_bits(12)
+        .encrypt(&mut Cursor::new(b""), &mut ciphertext);
+
+    assert!(matches!(left, Err(SymciphError::InvalidInput(_))));
+
+    let left =
+        Encryptor::new(EncryptionSchemes::Aes128).encrypt(&mut Cursor::new(b""), &mut ciphertext);
+
+    assert!(matches!(left, Err(SymciphError::InvalidInput(_))));
+
+    let left = helper_decrypt(&Decryptor::new().key(&key), b"not a container");
+
+    assert!(matches!(left, Err(SymciphError::Format(_))));
+}
+
+#[test]
+fn byte_range_from_memory() {
+    let plaintext: Vec<u8> = (0..=255).collect();
+    let key = read(concat!(integration_test_data_prefix!(), "aes128.key")).unwrap();
+
+    let ciphertext = helper_encrypt(
+        &Encryptor::new(EncryptionSchemes::Aes128)
+            .mode(EncryptionModes::Ctr)
+            .key(&key),
+        &plaintext,
+    );
+
+    let mut range = Vec::new();
+    Decryptor::new()
+        .key(&key)
+        .decrypt_range(&mut Cursor::new(ciphertext), &mut range, 100, Some(50))
+        .unwrap();
+
+    let left = range;
+    let right = plaintext[100..150].to_vec();
+
+    assert_eq!(left, right);
+}
+
+#[test]
+fn files_interoperate_with_cli() {
+    let plaintext_path = concat!(integration_test_data_prefix!(), "in.60_bytes.txt");
+    let ciphertext_path = concat!(integration_test_data_prefix!(), "api.sym");
+    let cli_ciphertext_path = concat!(integration_test_data_prefix!(), "api.cli.sym");
+    let decrypted_path = concat!(integration_test_data_prefix!(), "api.out.txt");
+    let key_path = concat!(integration_test_data_prefix!(), "aes128.key");
+    let key = read(key_path).unwrap();
+
+    encrypt_file(
+        plaintext_path,
+        ciphertext_path,
+        EncryptionSchemes::Aes128,
+        EncryptionModes::Cbc,
+        &key,
+    )
+    .unwrap();
+    helper_symciph(&["decrypt", ciphertext_path, decrypted_path, key_path]);
+
+    let left = read(decrypted_path).unwrap();
+    let right = read(plaintext_path).unwrap();
+
+    assert_eq!(left, right);
+
+    helper_symciph(&[
+        "encrypt",
+        "aes128",
+        plaintext_path,
+        cli_ciphertext_path,
+        key_path,
+        "--mode",
+        "eax",
+    ]);
+    decrypt_file(cli_ciphertext_path, decrypted_path, &key).unwrap();
+
+    let left = read(decrypted_path).unwrap();
+    let right = read(plaintext_path).unwrap();
+
+    assert_eq!(left, right);
+
+    for path in [ciphertext_path, cli_ciphertext_path, decrypted_path] {
+        remove_file(path).unwrap();
+    }
+}
+
+#[test]
+fn failed_tag_leaves_no_output_file() {
+    let plaintext_path = concat!(integration_test_data_prefix!(), "in.60_bytes.txt");
+    let ciphertext_path = concat!(integration_test_data_prefix!(), "api.gcm.sym");
+    let decrypted_path = concat!(integration_test_data_prefix!(), "api.gcm.out.txt");
+    let key = read(concat!(integration_test_data_prefix!(), "aes128.key")).unwrap();
+
+    Encryptor::new(EncryptionSchemes::Aes128)
+        .mode(EncryptionModes::Gcm)
+        .key(&key)
+        .aad(b"v1")
+        .encrypt_file(plaintext_path, ciphertext_path)
+        .unwrap();
+
+    let left = Decryptor::new()
+        .key(&key)
+        .aad(b"v2")
+        .decrypt_file(ciphertext_path, decrypted_path);
+
+    assert!(matches!(left, Err(SymciphError::Authentication(_))));
+    assert!(!Path::new(decrypted_path).exists());
+
+    remove_file(ciphertext_path).unwrap();
+}
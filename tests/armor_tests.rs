@@ -0,0 +1,195 @@
+use std::{
+    fs::{read, read_to_string, remove_file, write},
+    process::{Command, Output},
+};
+
+macro_rules! integration_test_data_prefix {
+    () => {
+        "tests/data/"
+    };
+}
+
+fn helper_symciph(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_symciph"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn armored_ciphertext_round_trip() {
+    let plaintext_path = concat!(integration_test_data_prefix!(), "in.60_bytes.txt");
+    let ciphertext_path = concat!(integration_test_data_prefix!(), "armor.sym");
+    let decrypted_path = concat!(integration_test_data_prefix!(), "armor.out.txt");
+    let key_path = concat!(integration_test_data_prefix!(), "aes128.key");
+
+    for armor in ["hex", "base64", "pem"] {
+        helper_symciph(&[
+            "encrypt",
+            "aes128",
+            plaintext_path,
+            ciphertext_path,
+            key_path,
+            "--mode",
+            "gcm",
+            "--armor",
+            armor,
+        ]);
+
+        assert!(read_to_string(ciphertext_path).unwrap().is_ascii());
+
+        let output = helper_symciph(&["decrypt", ciphertext_path, decrypted_path, key_path]);
+
+        assert!(output.status.success());
+
+        let left = read(decrypted_path).unwrap();
+        let right = read(plaintext_path).unwrap();
+
+        assert_eq!(left, right);
+    }
+
+    for path in [ciphertext_path, decrypted_path] {
+        remove_file(path).unwrap();
+    }
+}
+
+#[test]
+fn pem_block_carries_header_fields() {
+    let plaintext_path = concat!(integration_test_data_prefix!(), "in.60_bytes.txt");
+    let key_path = concat!(integration_test_data_prefix!(), "aes128.key");
+
+    let output = helper_symciph(&[
+        "encrypt",
+        "aes128",
+        plaintext_path,
+        "-",
+        key_path,
+        "--mode",
+        "ctr",
+        "--armor",
+        "pem",
+    ]);
+    let armored = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = armored.lines().collect();
+
+    let left = &lines[..3];
+    let right = [
+        "-----BEGIN SYMCIPH MESSAGE-----",
+        "Cipher: aes128",
+        "Mode: ctr",
+    ];
+
+    assert_eq!(left, right);
+
+    let left = lines[lines.len() - 1];
+    let right = "-----END SYMCIPH MESSAGE-----";
+
+    assert_eq!(left, right);
+    assert!(lines[lines.len() - 2].starts_with('='));
+}
+
+#[test]
+fn damaged_pem_block_is_rejected() {
+    let plaintext_path = concat!(integration_test_data_prefix!(), "in.60_bytes.txt");
+    let ciphertext_path = concat!(integration_test_data_prefix!(), "armor.damaged.sym");
+    let key_path = concat!(integration_test_data_prefix!(), "aes128.key");
+
+    helper_symciph(&[
+        "encrypt",
+        "aes128",
+        plaintext_path,
+        ciphertext_path,
+        key_path,
+        "--mode",
+        "gcm",
+        "--armor",
+        "pem",
+    ]);
+
+    // Damage lands in the last body line, right before the checksum line
+    let armored = read_to_string(ciphertext_path).unwrap();
+    let mut lines: Vec<String> = armored.lines().map(str::to_string).collect();
+    let last_body_index = lines.len() - 3;
+    let last_body_line = &mut lines[last_body_index];
+    let damaged_symbol = match last_body_line.starts_with('A') {
+        true => "B",
+        false => "A",
+    };
+    last_body_line.replace_range(..1, damaged_symbol);
+    let damaged = lines.join("\n") + "\n";
+    write(ciphertext_path, damaged).unwrap();
+
+    let output = helper_symciph(&["decrypt", ciphertext_path, "-", key_path]);
+
+    assert_eq!(output.status.code(), Some(8));
+    assert!(output.stdout.is_empty());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("checksum"));
+
+    remove_file(ciphertext_path).unwrap();
+}
+
+#[test]
+fn armored_keys_are_read() {
+    let plaintext_path = concat!(integration_test_data_prefix!(), "in.60_bytes.txt");
+    let ciphertext_path = concat!(integration_test_data_prefix!(), "armor.key.sym");
+    let decrypted_path = concat!(integration_test_data_prefix!(), "armor.key.out.txt");
+    let key_path = concat!(integration_test_data_prefix!(), "armor.key");
+
+    for armor in ["hex", "base64", "pem"] {
+        helper_symciph(&["generate-key", "tdes", key_path, "--armor", armor]);
+
+        assert!(read_to_string(key_path).unwrap().is_ascii());
+
+        let output = helper_symciph(&["check-key", "tdes", key_path]);
+
+        assert!(output.status.success());
+
+        helper_symciph(&[
+            "encrypt",
+            "tdes",
+            plaintext_path,
+            ciphertext_path,
+            key_path,
+            "--mode",
+            "cbc",
+        ]);
+        let output = helper_symciph(&["decrypt", ciphertext_path, decrypted_path, key_path]);
+
+        assert!(output.status.success());
+
+        let left = read(decrypted_path).unwrap();
+        let right = read(plaintext_path).unwrap();
+
+        assert_eq!(left, right);
+    }
+
+    for path in [ciphertext_path, decrypted_path, key_path] {
+        remove_file(path).unwrap();
+    }
+}
+
+#[test]
+fn byte_ranges_need_binary_input() {
+    let plaintext_path = concat!(integration_test_data_prefix!(), "in.60_bytes.txt");
+    let ciphertext_path = concat!(integration_test_data_prefix!(), "armor.range.sym");
+    let key_path = concat!(integration_test_data_prefix!(), "aes128.key");
+
+    helper_symciph(&[
+        "encrypt",
+        "aes128",
+        plaintext_path,
+        ciphertext_path,
+        key_path,
+        "--mode",
+        "ctr",
+        "--armor",
+        "base64",
+    ]);
+
+    let output = helper_symciph(&["decrypt", ciphertext_path, "-", key_path, "--offset", "10"]);
+
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("armored"));
+
+    remove_file(ciphertext_path).unwrap();
+}
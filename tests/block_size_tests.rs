@@ -0,0 +1,91 @@
+use std::{
+    fs::{read, remove_file, write},
+    process::{Command, Output},
+};
+
+macro_rules! integration_test_data_prefix {
+    () => {
+        "tests/data/"
+    };
+}
+
+fn helper_symciph(
+    command: &str,
+    cipher: &str,
+    input_path: &str,
+    output_path: &str,
+    key_path: &str,
+    mode: &str,
+) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_symciph"))
+        .args([command, cipher, input_path, output_path, key_path])
+        .args(["--mode", mode])
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn aes_handles_every_input_length() {
+    let plaintext_path = concat!(integration_test_data_prefix!(), "block_size.in.bin");
+    let ciphertext_path = concat!(integration_test_data_prefix!(), "block_size.enc");
+    let decrypted_path = concat!(integration_test_data_prefix!(), "block_size.out.bin");
+    let aes192_key_path = concat!(integration_test_data_prefix!(), "block_size.aes192.key");
+
+    write(aes192_key_path, [0x5Au8; 24]).unwrap();
+
+    let ciphers = [
+        (
+            "aes128",
+            concat!(integration_test_data_prefix!(), "aes128.key"),
+        ),
+        ("aes192", aes192_key_path),
+        (
+            "aes256",
+            concat!(integration_test_data_prefix!(), "aes256.key"),
+        ),
+    ];
+
+    // 24 bytes used to leave a lone 8-byte chunk for the last AES batch
+    for plaintext_len in [0, 1, 8, 15, 16, 17, 24, 40, 48, 100] {
+        let plaintext: Vec<u8> = (0..plaintext_len).map(|byte| byte as u8).collect();
+        write(plaintext_path, &plaintext).unwrap();
+
+        for (cipher, key_path) in ciphers {
+            for mode in ["ecb", "cbc", "pcbc", "cfb", "cfb8", "ofb", "ctr"] {
+                let output = helper_symciph(
+                    "encrypt",
+                    cipher,
+                    plaintext_path,
+                    ciphertext_path,
+                    key_path,
+                    mode,
+                );
+
+                assert!(output.status.success());
+
+                helper_symciph(
+                    "decrypt",
+                    cipher,
+                    ciphertext_path,
+                    decrypted_path,
+                    key_path,
+                    mode,
+                );
+
+                let left = read(decrypted_path).unwrap();
+                let right = plaintext.clone();
+
+                assert_eq!(left, right);
+            }
+        }
+    }
+
+    for path in [
+        plaintext_path,
+        ciphertext_path,
+        decrypted_path,
+        aes192_key_path,
+    ] {
+        remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,46 @@
+use std::{
+    fs::{metadata, read, remove_file},
+    process::Command,
+};
+
+macro_rules! integration_test_data_prefix {
+    () => {
+        "tests/data/"
+    };
+}
+
+#[test]
+fn ciphertext_is_as_long_as_plaintext() {
+    let plaintext_path = concat!(integration_test_data_prefix!(), "in.60_bytes.txt");
+    let ciphertext_path = concat!(integration_test_data_prefix!(), "cbc_cs.enc");
+    let decrypted_path = concat!(integration_test_data_prefix!(), "cbc_cs.out.txt");
+
+    for (cipher, key, iv_len) in [("des", "des.key", 8), ("aes128", "aes128.key", 16)] {
+        for mode in ["cbc-cs1", "cbc-cs2", "cbc-cs3"] {
+            let symciph = |command: &str, input_path: &str, output_path: &str| {
+                Command::new(env!("CARGO_BIN_EXE_symciph"))
+                    .args([command, cipher, input_path, output_path])
+                    .arg(format!("{}{}", integration_test_data_prefix!(), key))
+                    .args(["--mode", mode, "--raw"])
+                    .output()
+                    .unwrap()
+            };
+
+            symciph("encrypt", plaintext_path, ciphertext_path);
+            symciph("decrypt", ciphertext_path, decrypted_path);
+
+            let left = metadata(ciphertext_path).unwrap().len();
+            let right = 60 + iv_len;
+
+            assert_eq!(left, right);
+
+            let left = read(decrypted_path).unwrap();
+            let right = read(plaintext_path).unwrap();
+
+            assert_eq!(left, right);
+        }
+    }
+
+    remove_file(ciphertext_path).unwrap();
+    remove_file(decrypted_path).unwrap();
+}
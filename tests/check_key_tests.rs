@@ -0,0 +1,102 @@
+use std::{
+    fs::{remove_file, write},
+    process::{Command, Output},
+};
+
+macro_rules! integration_test_data_prefix {
+    () => {
+        "tests/data/"
+    };
+}
+
+fn helper_symciph(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_symciph"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn generated_des_keys_are_sound() {
+    let des_key_path = concat!(integration_test_data_prefix!(), "check.des.key");
+    let tdes_key_path = concat!(integration_test_data_prefix!(), "check.tdes.key");
+
+    helper_symciph(&["generate-key", "des", des_key_path]);
+    helper_symciph(&["generate-key", "tdes", tdes_key_path]);
+
+    for (cipher, key_path) in [("des", des_key_path), ("tdes", tdes_key_path)] {
+        let output = helper_symciph(&["check-key", cipher, key_path]);
+
+        let left = String::from_utf8_lossy(&output.stdout);
+        let right = "Key is sound.\n";
+
+        assert_eq!(left, right);
+    }
+
+    remove_file(des_key_path).unwrap();
+    remove_file(tdes_key_path).unwrap();
+}
+
+#[test]
+fn weak_and_degenerate_keys_reported() {
+    let weak_key_path = concat!(integration_test_data_prefix!(), "check.weak.key");
+    let degenerate_key_path = concat!(integration_test_data_prefix!(), "check.degenerate.key");
+
+    write(
+        weak_key_path,
+        [0x1F, 0xE0, 0x1F, 0xE0, 0x0E, 0xF1, 0x0E, 0xF1],
+    )
+    .unwrap();
+    write(
+        degenerate_key_path,
+        [[0x13; 8], [0x13; 8], [0x26; 8]].concat(),
+    )
+    .unwrap();
+
+    let output = helper_symciph(&["check-key", "des", weak_key_path]);
+
+    assert!(String::from_utf8_lossy(&output.stderr).contains("semi-weak"));
+
+    let output = helper_symciph(&["check-key", "tdes", degenerate_key_path]);
+
+    assert!(String::from_utf8_lossy(&output.stderr).contains("degenerate"));
+
+    let output = helper_symciph(&["check-key", "tdes", weak_key_path]);
+
+    assert!(String::from_utf8_lossy(&output.stderr).contains("semi-weak"));
+
+    let output = helper_symciph(&["check-key", "des", degenerate_key_path]);
+
+    assert!(String::from_utf8_lossy(&output.stderr).contains("length"));
+
+    remove_file(weak_key_path).unwrap();
+    remove_file(degenerate_key_path).unwrap();
+}
+
+#[test]
+fn missing_parity_noted() {
+    let key_path = concat!(integration_test_data_prefix!(), "des.key");
+
+    let output = helper_symciph(&["check-key", "des", key_path]);
+
+    let left = String::from_utf8_lossy(&output.stdout);
+    let right = "Key is sound, but its parity bits are not set.\n";
+
+    assert_eq!(left, right);
+}
+
+#[test]
+fn encryption_refuses_weak_keys() {
+    let plaintext_path = concat!(integration_test_data_prefix!(), "in.60_bytes.txt");
+    let ciphertext_path = concat!(integration_test_data_prefix!(), "check.weak.sym");
+    let key_path = concat!(integration_test_data_prefix!(), "check.refused.key");
+
+    write(key_path, [0xFE; 8]).unwrap();
+
+    let output = helper_symciph(&["encrypt", "des", plaintext_path, ciphertext_path, key_path]);
+
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Unsound key, weak DES key"));
+
+    remove_file(key_path).unwrap();
+    let _ = remove_file(ciphertext_path);
+}
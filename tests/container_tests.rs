@@ -0,0 +1,98 @@
+use std::{
+    fs::{read, remove_file},
+    process::{Command, Output},
+};
+
+macro_rules! integration_test_data_prefix {
+    () => {
+        "tests/data/"
+    };
+}
+
+fn helper_symciph(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_symciph"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn decrypt_without_naming_the_cipher() {
+    let plaintext_path = concat!(integration_test_data_prefix!(), "in.60_bytes.txt");
+    let ciphertext_path = concat!(integration_test_data_prefix!(), "container.sym");
+    let decrypted_path = concat!(integration_test_data_prefix!(), "container.out.txt");
+    let key_path = concat!(integration_test_data_prefix!(), "aes256.key");
+
+    helper_symciph(&[
+        "encrypt",
+        "aes256",
+        plaintext_path,
+        ciphertext_path,
+        key_path,
+        "--mode",
+        "ctr",
+        "--counter-bits",
+        "64",
+    ]);
+    helper_symciph(&["decrypt", ciphertext_path, decrypted_path, key_path]);
+
+    let left = read(decrypted_path).unwrap();
+    let right = read(plaintext_path).unwrap();
+
+    assert_eq!(left, right);
+
+    let output = helper_symciph(&[
+        "decrypt",
+        "aes128",
+        ciphertext_path,
+        decrypted_path,
+        key_path,
+    ]);
+
+    assert!(String::from_utf8_lossy(&output.stderr).contains("does not match"));
+
+    remove_file(ciphertext_path).unwrap();
+    remove_file(decrypted_path).unwrap();
+}
+
+#[test]
+fn headerless_files_need_raw() {
+    let plaintext_path = concat!(integration_test_data_prefix!(), "in.60_bytes.txt");
+    let ciphertext_path = concat!(integration_test_data_prefix!(), "container.raw.bin");
+    let decrypted_path = concat!(integration_test_data_prefix!(), "container.raw.out.txt");
+    let key_path = concat!(integration_test_data_prefix!(), "des.key");
+
+    helper_symciph(&[
+        "encrypt",
+        "des",
+        plaintext_path,
+        ciphertext_path,
+        key_path,
+        "--mode",
+        "cbc",
+        "--raw",
+    ]);
+
+    let output = helper_symciph(&["decrypt", "des", ciphertext_path, decrypted_path, key_path]);
+
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--raw"));
+
+    helper_symciph(&[
+        "decrypt",
+        "des",
+        ciphertext_path,
+        decrypted_path,
+        key_path,
+        "--mode",
+        "cbc",
+        "--raw",
+    ]);
+
+    let left = read(decrypted_path).unwrap();
+    let right = read(plaintext_path).unwrap();
+
+    assert_eq!(left, right);
+
+    remove_file(ciphertext_path).unwrap();
+    remove_file(decrypted_path).unwrap();
+}
@@ -0,0 +1,73 @@
+use std::{
+    fs::{remove_file, write},
+    path::Path,
+    process::{Command, Output},
+};
+
+macro_rules! integration_test_data_prefix {
+    () => {
+        "tests/data/"
+    };
+}
+
+fn helper_symciph(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_symciph"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn counter_longer_than_des_block_is_rejected() {
+    let ciphertext_path = concat!(integration_test_data_prefix!(), "ctr.des96.sym");
+
+    let output = helper_symciph(&[
+        "encrypt",
+        "des",
+        concat!(integration_test_data_prefix!(), "in.60_bytes.txt"),
+        ciphertext_path,
+        concat!(integration_test_data_prefix!(), "des.key"),
+        "--mode",
+        "ctr",
+        "--counter-bits",
+        "96",
+    ]);
+
+    // The output file is created before the options are checked against each other
+    if Path::new(ciphertext_path).exists() {
+        remove_file(ciphertext_path).unwrap();
+    }
+
+    let left = output.status.code();
+    let right = Some(2);
+
+    assert_eq!(left, right);
+}
+
+// 256 counter values cover 4096 bytes of AES keystream, one more byte would reuse it
+#[test]
+fn input_longer_than_counter_space_is_rejected() {
+    let plaintext_path = concat!(integration_test_data_prefix!(), "ctr.4097_bytes.bin");
+    let ciphertext_path = concat!(integration_test_data_prefix!(), "ctr.4097_bytes.sym");
+
+    write(plaintext_path, [0u8; 4097]).unwrap();
+
+    let output = helper_symciph(&[
+        "encrypt",
+        "aes128",
+        plaintext_path,
+        ciphertext_path,
+        concat!(integration_test_data_prefix!(), "aes128.key"),
+        "--mode",
+        "ctr",
+        "--counter-bits",
+        "8",
+    ]);
+
+    remove_file(plaintext_path).unwrap();
+
+    let left = (output.status.code(), Path::new(ciphertext_path).exists());
+    let right = (Some(2), false);
+
+    assert_eq!(left, right);
+}
@@ -0,0 +1,170 @@
+use std::{
+    fs::{read, remove_file, write},
+    path::Path,
+    process::{Command, Output},
+};
+
+macro_rules! integration_test_data_prefix {
+    () => {
+        "tests/data/"
+    };
+}
+
+fn helper_symciph(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_symciph"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+// Failing runs may still have opened their output file before bailing out
+fn helper_remove_if_exists(path: &str) {
+    if Path::new(path).exists() {
+        remove_file(path).unwrap();
+    }
+}
+
+#[test]
+fn wrong_key_length_exits_with_3() {
+    let output_path = concat!(integration_test_data_prefix!(), "exit.key_length.bin");
+    let output = helper_symciph(&[
+        "encrypt",
+        "aes128",
+        concat!(integration_test_data_prefix!(), "in.60_bytes.txt"),
+        output_path,
+        concat!(integration_test_data_prefix!(), "des.key"),
+    ]);
+
+    helper_remove_if_exists(output_path);
+
+    let left = output.status.code();
+    let right = Some(3);
+
+    assert_eq!(left, right);
+}
+
+#[test]
+fn ccm_input_longer_than_length_field_exits_with_2() {
+    let input_path = concat!(integration_test_data_prefix!(), "exit.ccm_64_kib.bin");
+    let output_path = concat!(integration_test_data_prefix!(), "exit.ccm_64_kib.sym");
+
+    write(input_path, [0u8; 0x10000]).unwrap();
+
+    let output = helper_symciph(&[
+        "encrypt",
+        "aes128",
+        input_path,
+        output_path,
+        concat!(integration_test_data_prefix!(), "aes128.key"),
+        "--mode",
+        "ccm",
+        "--nonce-len",
+        "13",
+    ]);
+
+    remove_file(input_path).unwrap();
+
+    let left = (output.status.code(), Path::new(output_path).exists());
+    let right = (Some(2), false);
+
+    assert_eq!(left, right);
+}
+
+#[test]
+fn weak_key_exits_with_4() {
+    let weak_key_path = concat!(integration_test_data_prefix!(), "exit.weak.key");
+
+    write(
+        weak_key_path,
+        [0x1F, 0xE0, 0x1F, 0xE0, 0x0E, 0xF1, 0x0E, 0xF1],
+    )
+    .unwrap();
+
+    let output = helper_symciph(&["check-key", "des", weak_key_path]);
+
+    remove_file(weak_key_path).unwrap();
+
+    let left = output.status.code();
+    let right = Some(4);
+
+    assert_eq!(left, right);
+}
+
+#[test]
+fn unpadded_partial_block_exits_with_5() {
+    let output_path = concat!(integration_test_data_prefix!(), "exit.alignment.bin");
+    let output = helper_symciph(&[
+        "encrypt",
+        "aes128",
+        concat!(integration_test_data_prefix!(), "in.60_bytes.txt"),
+        output_path,
+        concat!(integration_test_data_prefix!(), "aes128.key"),
+        "--padding",
+        "none",
+    ]);
+
+    helper_remove_if_exists(output_path);
+
+    let left = output.status.code();
+    let right = Some(5);
+
+    assert_eq!(left, right);
+}
+
+#[test]
+fn tampered_ciphertext_exits_with_7() {
+    let ciphertext_path = concat!(integration_test_data_prefix!(), "exit.tampered.bin");
+    let plaintext_path = concat!(integration_test_data_prefix!(), "exit.tampered.out.txt");
+
+    helper_symciph(&[
+        "encrypt",
+        "aes128",
+        concat!(integration_test_data_prefix!(), "in.60_bytes.txt"),
+        ciphertext_path,
+        concat!(integration_test_data_prefix!(), "aes128.key"),
+        "--mode",
+        "gcm",
+    ]);
+
+    let mut ciphertext = read(ciphertext_path).unwrap();
+    let last = ciphertext.len() - 1;
+    ciphertext[last] ^= 0x01;
+    write(ciphertext_path, &ciphertext).unwrap();
+
+    let output = helper_symciph(&[
+        "decrypt",
+        "aes128",
+        ciphertext_path,
+        plaintext_path,
+        concat!(integration_test_data_prefix!(), "aes128.key"),
+        "--mode",
+        "gcm",
+    ]);
+
+    remove_file(ciphertext_path).unwrap();
+    helper_remove_if_exists(plaintext_path);
+
+    let left = output.status.code();
+    let right = Some(7);
+
+    assert_eq!(left, right);
+}
+
+#[test]
+fn non_container_exits_with_8() {
+    let output_path = concat!(integration_test_data_prefix!(), "exit.format.out.txt");
+    let output = helper_symciph(&[
+        "decrypt",
+        "aes128",
+        concat!(integration_test_data_prefix!(), "in.8_bytes.txt"),
+        output_path,
+        concat!(integration_test_data_prefix!(), "aes128.key"),
+    ]);
+
+    helper_remove_if_exists(output_path);
+
+    let left = output.status.code();
+    let right = Some(8);
+
+    assert_eq!(left, right);
+}
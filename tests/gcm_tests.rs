@@ -0,0 +1,42 @@
+use std::{
+    fs::{read, remove_file, write},
+    path::Path,
+    process::Command,
+};
+
+macro_rules! integration_test_data_prefix {
+    () => {
+        "tests/data/"
+    };
+}
+
+#[test]
+fn cli_refuses_to_write_tampered_plaintext() {
+    let ciphertext_path = concat!(integration_test_data_prefix!(), "gcm.tampered.bin");
+    let plaintext_path = concat!(integration_test_data_prefix!(), "gcm.tampered.out.txt");
+    let symciph = |command: &str, input_path: &str, output_path: &str| {
+        Command::new(env!("CARGO_BIN_EXE_symciph"))
+            .args([command, "aes128", input_path, output_path])
+            .arg(concat!(integration_test_data_prefix!(), "aes128.key"))
+            .args(["--mode", "gcm", "--aad", "v1"])
+            .output()
+            .unwrap()
+    };
+
+    symciph(
+        "encrypt",
+        concat!(integration_test_data_prefix!(), "in.60_bytes.txt"),
+        ciphertext_path,
+    );
+
+    let mut ciphertext = read(ciphertext_path).unwrap();
+    ciphertext[20] ^= 0x01;
+    write(ciphertext_path, &ciphertext).unwrap();
+
+    let output = symciph("decrypt", ciphertext_path, plaintext_path);
+
+    remove_file(ciphertext_path).unwrap();
+
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Authentication failed"));
+    assert!(!Path::new(plaintext_path).exists());
+}
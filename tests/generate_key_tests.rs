@@ -0,0 +1,112 @@
+use std::{
+    fs::{read, remove_file},
+    process::{Command, Output},
+};
+
+macro_rules! integration_test_data_prefix {
+    () => {
+        "tests/data/"
+    };
+}
+
+fn helper_symciph(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_symciph"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn generated_keys_are_fresh() {
+    let first_key_path = concat!(integration_test_data_prefix!(), "generated.first.key");
+    let second_key_path = concat!(integration_test_data_prefix!(), "generated.second.key");
+
+    helper_symciph(&["generate-key", "aes256", first_key_path]);
+    helper_symciph(&[
+        "generate-key",
+        "aes256",
+        second_key_path,
+        "--prediction-resistance",
+    ]);
+
+    let first_key = read(first_key_path).unwrap();
+    let second_key = read(second_key_path).unwrap();
+
+    let left = (first_key.len(), second_key.len());
+    let right = (32, 32);
+
+    assert_eq!(left, right);
+    assert_ne!(first_key, second_key);
+
+    remove_file(first_key_path).unwrap();
+    remove_file(second_key_path).unwrap();
+}
+
+#[test]
+fn generated_key_round_trips() {
+    let plaintext_path = concat!(integration_test_data_prefix!(), "in.60_bytes.txt");
+    let key_path = concat!(integration_test_data_prefix!(), "generated.xts.key");
+    let ciphertext_path = concat!(integration_test_data_prefix!(), "generated.xts.sym");
+    let decrypted_path = concat!(integration_test_data_prefix!(), "generated.xts.out.txt");
+
+    helper_symciph(&["generate-key", "aes128", key_path, "--mode", "xts"]);
+    helper_symciph(&[
+        "encrypt",
+        "aes128",
+        plaintext_path,
+        ciphertext_path,
+        key_path,
+        "--mode",
+        "xts",
+    ]);
+    helper_symciph(&["decrypt", ciphertext_path, decrypted_path, key_path]);
+
+    let left = read(decrypted_path).unwrap();
+    let right = read(plaintext_path).unwrap();
+
+    assert_eq!(left, right);
+
+    remove_file(key_path).unwrap();
+    remove_file(ciphertext_path).unwrap();
+    remove_file(decrypted_path).unwrap();
+}
+
+#[test]
+fn unsupported_mode_writes_no_key() {
+    let key_path = concat!(integration_test_data_prefix!(), "generated.tdes_xts.key");
+
+    for mode in ["xts", "siv"] {
+        let output = helper_symciph(&["generate-key", "tdes", key_path, "--mode", mode]);
+
+        let left = (output.status.code(), read(key_path).is_err());
+        let right = (Some(2), true);
+
+        assert_eq!(left, right);
+    }
+}
+
+#[test]
+fn tdes_eax_key_takes_no_tag_length() {
+    let key_path = concat!(integration_test_data_prefix!(), "generated.tdes_eax.key");
+
+    let output = helper_symciph(&["generate-key", "tdes", key_path, "--mode", "eax"]);
+
+    let left = (output.status.code(), read(key_path).unwrap().len());
+    let right = (Some(0), 24);
+
+    assert_eq!(left, right);
+
+    remove_file(key_path).unwrap();
+}
+
+#[test]
+fn keying_option_needs_tdes() {
+    let key_path = concat!(integration_test_data_prefix!(), "generated.aes_keying.key");
+
+    let output = helper_symciph(&["generate-key", "aes128", key_path, "--keying-option", "2"]);
+
+    let left = (output.status.code(), read(key_path).is_err());
+    let right = (Some(2), true);
+
+    assert_eq!(left, right);
+}
@@ -1,6 +1,6 @@
 use std::fs::File;
 
-use symciph::io::{read_chunks, read_key, write_chunks, write_key};
+use symciph::io::{read_iv, read_key, write_key};
 
 macro_rules! integration_test_data_prefix {
     () => {
@@ -11,85 +11,29 @@ macro_rules! integration_test_data_prefix {
 const NUM_BLOCK_BYTES: usize = 8;
 
 #[test]
-fn read_chunks_buffer_size_8_chunks_input_60_bytes() {
-    let mut file = File::open(concat!(integration_test_data_prefix!(), "in.60_bytes.txt")).unwrap();
-    let buffer = &mut [[0; NUM_BLOCK_BYTES]; 8];
-
-    let (chunks_num, last_chunk_len) = read_chunks(&mut file, buffer).unwrap();
-
-    assert_eq!(chunks_num, 8);
-    assert_eq!(last_chunk_len, 4);
-}
-
-#[test]
-fn read_chunks_buffer_size_4_chunks_input_60_bytes() {
-    let mut file = File::open(concat!(integration_test_data_prefix!(), "in.60_bytes.txt")).unwrap();
-    let buffer = &mut [[0; NUM_BLOCK_BYTES]; 4];
-
-    let (chunks_num, last_chunk_len) = read_chunks(&mut file, buffer).unwrap();
-
-    assert_eq!(chunks_num, 4);
-    assert_eq!(last_chunk_len, 8);
-
-    let buffer = &mut [[0; NUM_BLOCK_BYTES]; 4];
-
-    let (chunks_num, last_chunk_len) = read_chunks(&mut file, buffer).unwrap();
-
-    assert_eq!(chunks_num, 4);
-    assert_eq!(last_chunk_len, 4);
-}
-
-#[test]
-fn read_chunks_buffer_size_2_chunks_input_8_bytes() {
-    let mut file = File::open(concat!(integration_test_data_prefix!(), "in.8_bytes.txt")).unwrap();
-    let buffer = &mut [[0; NUM_BLOCK_BYTES]; 2];
-
-    let (chunks_num, last_chunk_len) = read_chunks(&mut file, buffer).unwrap();
-
-    assert_eq!(chunks_num, 1);
-    assert_eq!(last_chunk_len, 8);
-}
-
-#[test]
-fn read_chunks_buffer_size_2_chunks_input_16_bytes() {
+fn read_iv_128_bits_input_16_bytes() {
     let mut file = File::open(concat!(integration_test_data_prefix!(), "in.16_bytes.txt")).unwrap();
-    let buffer = &mut [[0; NUM_BLOCK_BYTES]; 2];
 
-    let (chunks_num, last_chunk_len) = read_chunks(&mut file, buffer).unwrap();
+    let iv = read_iv(&mut file, 2).unwrap();
 
-    assert_eq!(chunks_num, 2);
-    assert_eq!(last_chunk_len, 8);
+    assert_eq!(iv.len(), 2);
 }
 
 #[test]
-fn read_chunks_buffer_size_4_chunks_input_16_bytes() {
-    let mut file = File::open(concat!(integration_test_data_prefix!(), "in.16_bytes.txt")).unwrap();
-    let buffer = &mut [[0; NUM_BLOCK_BYTES]; 4];
-
-    let (chunks_num, last_chunk_len) = read_chunks(&mut file, buffer).unwrap();
-
-    assert_eq!(chunks_num, 2);
-    assert_eq!(last_chunk_len, 8);
-}
-
-#[test]
-fn read_chunks_buffer_size_4_chunks_input_empty() {
+fn read_iv_empty() {
     let mut file = File::open(concat!(integration_test_data_prefix!(), "in.empty.txt")).unwrap();
-    let buffer = &mut [[0; NUM_BLOCK_BYTES]; 4];
 
-    let (chunks_num, last_chunk_len) = read_chunks(&mut file, buffer).unwrap();
+    let iv = read_iv(&mut file, 0).unwrap();
 
-    assert_eq!(chunks_num, 0);
-    assert_eq!(last_chunk_len, 0);
+    assert!(iv.is_empty());
 }
 
+#[should_panic]
 #[test]
-fn write_two_chunks() {
-    // NOTE: Check sha1sum -> expected: 5eb4a0004ea3dfc4bf2c4e779dc26904ea08da6c
-    let mut file = File::create(concat!(integration_test_data_prefix!(), "out.txt")).unwrap();
-    let buffer = &[[62; NUM_BLOCK_BYTES], [65; NUM_BLOCK_BYTES]];
+fn read_iv_128_bits_input_8_bytes() {
+    let mut file = File::open(concat!(integration_test_data_prefix!(), "in.8_bytes.txt")).unwrap();
 
-    write_chunks(&mut file, buffer).unwrap();
+    read_iv(&mut file, 2).unwrap();
 }
 
 #[should_panic]
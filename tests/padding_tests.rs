@@ -0,0 +1,73 @@
+use std::{
+    fs::{read, remove_file, write},
+    process::{Command, Output},
+};
+
+macro_rules! integration_test_data_prefix {
+    () => {
+        "tests/data/"
+    };
+}
+
+fn helper_symciph(
+    command: &str,
+    input_path: &str,
+    output_path: &str,
+    padding: &str,
+    raw: bool,
+) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_symciph"))
+        .args([command, "aes128", input_path, output_path])
+        .arg(concat!(integration_test_data_prefix!(), "aes128.key"))
+        .args(["--mode", "cbc", "--padding", padding])
+        .args(raw.then_some("--raw"))
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn trailing_zeros_survive_round_trip() {
+    let plaintext_path = concat!(integration_test_data_prefix!(), "padding.zeros.bin");
+    let ciphertext_path = concat!(integration_test_data_prefix!(), "padding.zeros.enc");
+    let decrypted_path = concat!(integration_test_data_prefix!(), "padding.zeros.out.bin");
+
+    // UTF-16 text ends in a zero byte, followed here by a full block of zeros
+    let plaintext = [b"h\0i\0".as_slice(), &[0u8; 16]].concat();
+    write(plaintext_path, &plaintext).unwrap();
+
+    for padding in ["pkcs7", "ansi-x923", "iso7816", "iso10126"] {
+        helper_symciph("encrypt", plaintext_path, ciphertext_path, padding, false);
+        helper_symciph("decrypt", ciphertext_path, decrypted_path, padding, false);
+
+        let left = read(decrypted_path).unwrap();
+        let right = plaintext.clone();
+
+        assert_eq!(left, right);
+    }
+
+    for path in [plaintext_path, ciphertext_path, decrypted_path] {
+        remove_file(path).unwrap();
+    }
+}
+
+#[test]
+fn bad_padding_is_reported() {
+    let ciphertext_path = concat!(integration_test_data_prefix!(), "padding.bad.enc");
+    let decrypted_path = concat!(integration_test_data_prefix!(), "padding.bad.out.txt");
+
+    helper_symciph(
+        "encrypt",
+        concat!(integration_test_data_prefix!(), "in.60_bytes.txt"),
+        ciphertext_path,
+        "ansi-x923",
+        true,
+    );
+
+    // Only headerless files take the padding from the command line
+    let output = helper_symciph("decrypt", ciphertext_path, decrypted_path, "iso7816", true);
+
+    remove_file(ciphertext_path).unwrap();
+    let _ = remove_file(decrypted_path);
+
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Invalid padding"));
+}
@@ -0,0 +1,253 @@
+use std::{
+    fs::{read, remove_file},
+    io::Write,
+    process::{Command, Output, Stdio},
+};
+
+macro_rules! integration_test_data_prefix {
+    () => {
+        "tests/data/"
+    };
+}
+
+// The password is piped to standard input, which --password-fd 0 reads from
+fn helper_symciph(args: &[&str], password: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_symciph"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(password.as_bytes())
+        .unwrap();
+
+    child.wait_with_output().unwrap()
+}
+
+// Costs are kept low so that debug builds derive keys quickly
+fn helper_encrypt(
+    cipher: &str,
+    mode: &str,
+    kdf: &str,
+    plaintext_path: &str,
+    ciphertext_path: &str,
+) {
+    let output = helper_symciph(
+        &[
+            "encrypt",
+            cipher,
+            plaintext_path,
+            ciphertext_path,
+            "--mode",
+            mode,
+            "--password-fd",
+            "0",
+            "--kdf",
+            kdf,
+            "--iterations",
+            "1000",
+            "--memory-cost",
+            "256",
+            "--time-cost",
+            "1",
+            "--parallelism",
+            "2",
+        ],
+        "correct horse battery staple\n",
+    );
+
+    assert!(output.stderr.is_empty());
+}
+
+#[test]
+fn password_round_trip() {
+    let plaintext_path = concat!(integration_test_data_prefix!(), "in.60_bytes.txt");
+    let decrypted_path = concat!(integration_test_data_prefix!(), "password.out.txt");
+
+    for (cipher, mode, kdf, ciphertext_path) in [
+        (
+            "aes256",
+            "gcm",
+            "argon2id",
+            concat!(integration_test_data_prefix!(), "password.gcm.sym"),
+        ),
+        (
+            "aes128",
+            "xts",
+            "scrypt",
+            concat!(integration_test_data_prefix!(), "password.xts.sym"),
+        ),
+        (
+            "tdes",
+            "cbc",
+            "pbkdf2",
+            concat!(integration_test_data_prefix!(), "password.cbc.sym"),
+        ),
+    ] {
+        helper_encrypt(cipher, mode, kdf, plaintext_path, ciphertext_path);
+
+        let output = helper_symciph(
+            &[
+                "decrypt",
+                ciphertext_path,
+                decrypted_path,
+                "--password-fd",
+                "0",
+            ],
+            "correct horse battery staple\n",
+        );
+
+        assert!(output.stderr.is_empty());
+
+        let left = read(decrypted_path).unwrap();
+        let right = read(plaintext_path).unwrap();
+
+        assert_eq!(left, right);
+
+        remove_file(ciphertext_path).unwrap();
+        remove_file(decrypted_path).unwrap();
+    }
+}
+
+#[test]
+fn same_password_different_salt() {
+    let plaintext_path = concat!(integration_test_data_prefix!(), "in.16_bytes.txt");
+    let ciphertext_path = concat!(integration_test_data_prefix!(), "password.salt.sym");
+    let other_ciphertext_path = concat!(integration_test_data_prefix!(), "password.salt.other.sym");
+
+    helper_encrypt("aes128", "ecb", "argon2id", plaintext_path, ciphertext_path);
+    helper_encrypt(
+        "aes128",
+        "ecb",
+        "argon2id",
+        plaintext_path,
+        other_ciphertext_path,
+    );
+
+    let left = read(ciphertext_path).unwrap();
+    let right = read(other_ciphertext_path).unwrap();
+
+    assert_ne!(left, right);
+
+    remove_file(ciphertext_path).unwrap();
+    remove_file(other_ciphertext_path).unwrap();
+}
+
+#[test]
+fn wrong_password_is_rejected() {
+    let plaintext_path = concat!(integration_test_data_prefix!(), "in.60_bytes.txt");
+    let ciphertext_path = concat!(integration_test_data_prefix!(), "password.wrong.sym");
+    let decrypted_path = concat!(integration_test_data_prefix!(), "password.wrong.out.txt");
+
+    helper_encrypt("aes128", "gcm", "scrypt", plaintext_path, ciphertext_path);
+
+    let output = helper_symciph(
+        &[
+            "decrypt",
+            ciphertext_path,
+            decrypted_path,
+            "--password-fd",
+            "0",
+        ],
+        "correct horse battery stapler\n",
+    );
+
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Authentication failed"));
+
+    remove_file(ciphertext_path).unwrap();
+}
+
+#[test]
+fn password_key_needs_password() {
+    let plaintext_path = concat!(integration_test_data_prefix!(), "in.60_bytes.txt");
+    let ciphertext_path = concat!(integration_test_data_prefix!(), "password.keyfile.sym");
+    let decrypted_path = concat!(integration_test_data_prefix!(), "password.keyfile.out.txt");
+    let key_path = concat!(integration_test_data_prefix!(), "aes128.key");
+
+    helper_encrypt("aes128", "ctr", "pbkdf2", plaintext_path, ciphertext_path);
+
+    let output = helper_symciph(&["decrypt", ciphertext_path, decrypted_path, key_path], "");
+
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--password"));
+
+    remove_file(ciphertext_path).unwrap();
+}
+
+#[test]
+fn password_needs_container() {
+    let plaintext_path = concat!(integration_test_data_prefix!(), "in.60_bytes.txt");
+    let ciphertext_path = concat!(integration_test_data_prefix!(), "password.raw.bin");
+
+    let output = helper_symciph(
+        &[
+            "encrypt",
+            "aes128",
+            plaintext_path,
+            ciphertext_path,
+            "--password-fd",
+            "0",
+            "--raw",
+        ],
+        "correct horse battery staple\n",
+    );
+
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--raw"));
+
+    remove_file(ciphertext_path).unwrap();
+}
+
+#[test]
+fn kdf_bench_prints_encrypt_flags() {
+    for (kdf, flag) in [
+        ("argon2id", "--time-cost"),
+        ("scrypt", "--memory-cost"),
+        ("pbkdf2", "--iterations"),
+    ] {
+        let output = helper_symciph(
+            &[
+                "kdf-bench",
+                "--kdf",
+                kdf,
+                "--target-ms",
+                "20",
+                "--memory-cost",
+                "64",
+            ],
+            "",
+        );
+
+        assert!(String::from_utf8_lossy(&output.stdout).contains(flag));
+    }
+}
+
+#[test]
+fn too_little_memory_per_lane() {
+    let plaintext_path = concat!(integration_test_data_prefix!(), "in.60_bytes.txt");
+    let ciphertext_path = concat!(integration_test_data_prefix!(), "password.lanes.sym");
+
+    let output = helper_symciph(
+        &[
+            "encrypt",
+            "aes128",
+            plaintext_path,
+            ciphertext_path,
+            "--password-fd",
+            "0",
+            "--memory-cost",
+            "64",
+            "--parallelism",
+            "16",
+        ],
+        "correct horse battery staple\n",
+    );
+
+    assert!(String::from_utf8_lossy(&output.stderr).contains("per lane"));
+
+    remove_file(ciphertext_path).unwrap();
+}
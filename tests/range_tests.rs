@@ -0,0 +1,222 @@
+use std::{
+    fs::{read, remove_file, write},
+    process::{Command, Output},
+};
+
+macro_rules! integration_test_data_prefix {
+    () => {
+        "tests/data/"
+    };
+}
+
+fn helper_symciph(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_symciph"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+fn helper_get_plaintext(plaintext_path: &str) -> Vec<u8> {
+    let plaintext: Vec<u8> = (0..5000).map(|byte| (byte * 7 % 251) as u8).collect();
+    write(plaintext_path, &plaintext).unwrap();
+
+    plaintext
+}
+
+fn helper_decrypt_range(
+    ciphertext_path: &str,
+    decrypted_path: &str,
+    key_path: &str,
+    offset: &str,
+    length: &str,
+) -> Vec<u8> {
+    let output = helper_symciph(&[
+        "decrypt",
+        ciphertext_path,
+        decrypted_path,
+        key_path,
+        "--offset",
+        offset,
+        "--length",
+        length,
+    ]);
+
+    assert!(output.stderr.is_empty());
+
+    read(decrypted_path).unwrap()
+}
+
+#[test]
+fn ctr_range() {
+    let plaintext_path = concat!(integration_test_data_prefix!(), "range.ctr.in.bin");
+    let ciphertext_path = concat!(integration_test_data_prefix!(), "range.ctr.sym");
+    let decrypted_path = concat!(integration_test_data_prefix!(), "range.ctr.out.bin");
+    let key_path = concat!(integration_test_data_prefix!(), "aes128.key");
+
+    let plaintext = helper_get_plaintext(plaintext_path);
+
+    helper_symciph(&[
+        "encrypt",
+        "aes128",
+        plaintext_path,
+        ciphertext_path,
+        key_path,
+        "--mode",
+        "ctr",
+    ]);
+
+    let left = helper_decrypt_range(ciphertext_path, decrypted_path, key_path, "1234", "99");
+    let right = &plaintext[1234..1333];
+
+    assert_eq!(left, right);
+
+    // A length past the end stops at the end of the plaintext
+    let left = helper_decrypt_range(ciphertext_path, decrypted_path, key_path, "4990", "100");
+    let right = &plaintext[4990..];
+
+    assert_eq!(left, right);
+
+    remove_file(plaintext_path).unwrap();
+    remove_file(ciphertext_path).unwrap();
+    remove_file(decrypted_path).unwrap();
+}
+
+#[test]
+fn xts_range() {
+    let plaintext_path = concat!(integration_test_data_prefix!(), "range.xts.in.bin");
+    let ciphertext_path = concat!(integration_test_data_prefix!(), "range.xts.sym");
+    let decrypted_path = concat!(integration_test_data_prefix!(), "range.xts.out.bin");
+    let key_path = concat!(integration_test_data_prefix!(), "aes256.key");
+
+    let plaintext = helper_get_plaintext(plaintext_path);
+
+    helper_symciph(&[
+        "encrypt",
+        "aes128",
+        plaintext_path,
+        ciphertext_path,
+        key_path,
+        "--mode",
+        "xts",
+        "--start-sector",
+        "7",
+    ]);
+
+    // Spans three sectors, starting and ending mid-sector
+    let left = helper_decrypt_range(ciphertext_path, decrypted_path, key_path, "1000", "700");
+    let right = &plaintext[1000..1700];
+
+    assert_eq!(left, right);
+
+    // The final sector is partial and stolen from
+    let left = helper_decrypt_range(ciphertext_path, decrypted_path, key_path, "4700", "300");
+    let right = &plaintext[4700..];
+
+    assert_eq!(left, right);
+
+    remove_file(plaintext_path).unwrap();
+    remove_file(ciphertext_path).unwrap();
+    remove_file(decrypted_path).unwrap();
+}
+
+#[test]
+fn stream_range() {
+    let plaintext_path = concat!(integration_test_data_prefix!(), "range.stream.in.bin");
+    let ciphertext_path = concat!(integration_test_data_prefix!(), "range.stream.sym");
+    let decrypted_path = concat!(integration_test_data_prefix!(), "range.stream.out.bin");
+    let key_path = concat!(integration_test_data_prefix!(), "aes128.key");
+
+    let plaintext = helper_get_plaintext(plaintext_path);
+
+    helper_symciph(&[
+        "encrypt",
+        "aes128",
+        plaintext_path,
+        ciphertext_path,
+        key_path,
+        "--mode",
+        "gcm-stream",
+        "--segment-size",
+        "1024",
+    ]);
+
+    let left = helper_decrypt_range(ciphertext_path, decrypted_path, key_path, "1000", "1100");
+    let right = &plaintext[1000..2100];
+
+    assert_eq!(left, right);
+
+    let left = helper_decrypt_range(ciphertext_path, decrypted_path, key_path, "4500", "1000");
+    let right = &plaintext[4500..];
+
+    assert_eq!(left, right);
+
+    // Tampering outside the range goes unnoticed, inside it is caught
+    let mut ciphertext = read(ciphertext_path).unwrap();
+    let len = ciphertext.len();
+    ciphertext[len - 1] ^= 0x01;
+    write(ciphertext_path, &ciphertext).unwrap();
+
+    let left = helper_decrypt_range(ciphertext_path, decrypted_path, key_path, "0", "100");
+    let right = &plaintext[..100];
+
+    assert_eq!(left, right);
+
+    let output = helper_symciph(&[
+        "decrypt",
+        ciphertext_path,
+        decrypted_path,
+        key_path,
+        "--offset",
+        "4500",
+    ]);
+
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Segment authentication failed"));
+
+    remove_file(plaintext_path).unwrap();
+    remove_file(ciphertext_path).unwrap();
+    remove_file(decrypted_path).unwrap();
+}
+
+#[test]
+fn range_needs_seekable_mode() {
+    let plaintext_path = concat!(integration_test_data_prefix!(), "in.60_bytes.txt");
+    let ciphertext_path = concat!(integration_test_data_prefix!(), "range.cbc.sym");
+    let decrypted_path = concat!(integration_test_data_prefix!(), "range.cbc.out.txt");
+    let key_path = concat!(integration_test_data_prefix!(), "aes128.key");
+
+    helper_symciph(&[
+        "encrypt",
+        "aes128",
+        plaintext_path,
+        ciphertext_path,
+        key_path,
+        "--mode",
+        "cbc",
+    ]);
+
+    let output = helper_symciph(&[
+        "decrypt",
+        ciphertext_path,
+        decrypted_path,
+        key_path,
+        "--offset",
+        "16",
+    ]);
+
+    assert!(String::from_utf8_lossy(&output.stderr).contains("CTR, XTS and GCM-STREAM"));
+
+    let output = helper_symciph(&[
+        "decrypt",
+        ciphertext_path,
+        decrypted_path,
+        key_path,
+        "--mode",
+        "ctr",
+        "--offset",
+        "16",
+    ]);
+
+    assert!(!output.stderr.is_empty());
+
+    remove_file(ciphertext_path).unwrap();
+}
@@ -0,0 +1,204 @@
+use std::{
+    fs::{read, remove_file, File},
+    io::Write,
+    process::{Command, Output, Stdio},
+    thread,
+};
+
+macro_rules! integration_test_data_prefix {
+    () => {
+        "tests/data/"
+    };
+}
+
+// Stdin is fed from another thread, so a full stdout pipe cannot stall the feed
+fn helper_symciph(args: &[&str], stdin: &[u8]) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_symciph"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    let mut child_stdin = child.stdin.take().unwrap();
+    let stdin = stdin.to_vec();
+    let feeder = thread::spawn(move || child_stdin.write_all(&stdin));
+
+    let output = child.wait_with_output().unwrap();
+    // The child may quit before reading everything, which is what some tests check
+    let _ = feeder.join().unwrap();
+
+    output
+}
+
+fn helper_symciph_with_stdin_file(args: &[&str], stdin_path: &str) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_symciph"))
+        .args(args)
+        .stdin(File::open(stdin_path).unwrap())
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn pipe_round_trip() {
+    let plaintext: Vec<u8> = (0..100_000).map(|byte| (byte % 251) as u8).collect();
+    let key_path = concat!(integration_test_data_prefix!(), "aes128.key");
+
+    for mode in ["cbc", "ctr", "gcm", "gcm-stream", "xts"] {
+        let key_path = match mode {
+            "xts" => concat!(integration_test_data_prefix!(), "aes256.key"),
+            _ => key_path,
+        };
+
+        let encrypted = helper_symciph(
+            &["encrypt", "aes128", "-", "-", key_path, "--mode", mode],
+            &plaintext,
+        );
+
+        assert!(encrypted.status.success());
+        assert!(encrypted.stderr.is_empty());
+
+        let decrypted = helper_symciph(&["decrypt", "-", "-", key_path], &encrypted.stdout);
+
+        assert!(decrypted.status.success());
+        assert!(decrypted.stderr.is_empty());
+
+        let left = decrypted.stdout;
+        let right = plaintext.clone();
+
+        assert_eq!(left, right);
+    }
+}
+
+#[test]
+fn stdout_carries_only_ciphertext() {
+    let plaintext_path = concat!(integration_test_data_prefix!(), "in.60_bytes.txt");
+    let ciphertext_path = concat!(integration_test_data_prefix!(), "stdio.siv.sym");
+    let key_path = concat!(integration_test_data_prefix!(), "aes256.key");
+
+    // SIV without a nonce is deterministic, so stdout must match the file byte for byte
+    let args = ["--mode", "siv", "--nonce-len", "0"];
+    helper_symciph(
+        &[
+            &[
+                "encrypt",
+                "aes128",
+                plaintext_path,
+                ciphertext_path,
+                key_path,
+            ],
+            &args[..],
+        ]
+        .concat(),
+        b"",
+    );
+    let piped = helper_symciph(
+        &[
+            &["encrypt", "aes128", plaintext_path, "-", key_path],
+            &args[..],
+        ]
+        .concat(),
+        b"",
+    );
+
+    let left = piped.stdout;
+    let right = read(ciphertext_path).unwrap();
+
+    assert_eq!(left, right);
+
+    remove_file(ciphertext_path).unwrap();
+}
+
+#[test]
+fn key_from_descriptor_or_stdin() {
+    let plaintext_path = concat!(integration_test_data_prefix!(), "in.60_bytes.txt");
+    let ciphertext_path = concat!(integration_test_data_prefix!(), "stdio.key_fd.sym");
+    let decrypted_path = concat!(integration_test_data_prefix!(), "stdio.key_fd.out.txt");
+    let key_path = concat!(integration_test_data_prefix!(), "des.key");
+
+    let encrypted = helper_symciph_with_stdin_file(
+        &[
+            "encrypt",
+            "des",
+            plaintext_path,
+            ciphertext_path,
+            "--key-fd",
+            "0",
+            "--mode",
+            "cbc",
+        ],
+        key_path,
+    );
+
+    assert!(encrypted.status.success());
+
+    let decrypted = helper_symciph_with_stdin_file(
+        &["decrypt", ciphertext_path, decrypted_path, "-"],
+        key_path,
+    );
+
+    assert!(decrypted.status.success());
+
+    let left = read(decrypted_path).unwrap();
+    let right = read(plaintext_path).unwrap();
+
+    assert_eq!(left, right);
+
+    for path in [ciphertext_path, decrypted_path] {
+        remove_file(path).unwrap();
+    }
+}
+
+#[test]
+fn stdin_carries_one_stream() {
+    let key = read(concat!(integration_test_data_prefix!(), "des.key")).unwrap();
+
+    let output = helper_symciph(&["encrypt", "des", "-", "-", "-"], &key);
+
+    assert_eq!(output.status.code(), Some(2));
+    assert!(output.stdout.is_empty());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("stdin"));
+
+    let output = helper_symciph(&["decrypt", "-", "-", "--key-fd", "0"], &key);
+
+    assert_eq!(output.status.code(), Some(2));
+    assert!(output.stdout.is_empty());
+}
+
+#[test]
+fn failed_tag_writes_nothing_to_stdout() {
+    let plaintext = b"attack at dawn";
+    let key_path = concat!(integration_test_data_prefix!(), "aes128.key");
+
+    let encrypted = helper_symciph(
+        &["encrypt", "aes128", "-", "-", key_path, "--mode", "gcm"],
+        plaintext,
+    );
+    let mut ciphertext = encrypted.stdout;
+    let last = ciphertext.len() - 1;
+    ciphertext[last] ^= 0x01;
+
+    let decrypted = helper_symciph(&["decrypt", "-", "-", key_path], &ciphertext);
+
+    assert_eq!(decrypted.status.code(), Some(7));
+    assert!(decrypted.stdout.is_empty());
+    assert!(String::from_utf8_lossy(&decrypted.stderr).contains("Authentication failed"));
+}
+
+#[test]
+fn byte_ranges_need_a_file() {
+    let key_path = concat!(integration_test_data_prefix!(), "aes128.key");
+
+    let encrypted = helper_symciph(
+        &["encrypt", "aes128", "-", "-", key_path, "--mode", "ctr"],
+        b"0123456789",
+    );
+    let decrypted = helper_symciph(
+        &["decrypt", "-", "-", key_path, "--offset", "2"],
+        &encrypted.stdout,
+    );
+
+    assert_eq!(decrypted.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&decrypted.stderr).contains("seekable"));
+}
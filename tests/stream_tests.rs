@@ -0,0 +1,256 @@
+use std::{
+    fs::{read, remove_file, write, File},
+    path::Path,
+    process::{Command, Output},
+};
+
+macro_rules! integration_test_data_prefix {
+    () => {
+        "tests/data/"
+    };
+}
+
+const NONCE_LEN: usize = 12;
+const SEGMENT_SIZE: usize = 16;
+const SEALED_SEGMENT_LEN: usize = SEGMENT_SIZE + 16;
+
+fn helper_symciph(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_symciph"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+fn helper_encrypt(plaintext_path: &str, ciphertext_path: &str, key_path: &str) {
+    let output = helper_symciph(&[
+        "encrypt",
+        "aes128",
+        plaintext_path,
+        ciphertext_path,
+        key_path,
+        "--mode",
+        "gcm-stream",
+        "--segment-size",
+        "16",
+        "--raw",
+    ]);
+
+    assert!(output.stderr.is_empty());
+}
+
+fn helper_decrypt(ciphertext_path: &str, decrypted_path: &str, key_path: &str) -> Output {
+    helper_symciph(&[
+        "decrypt",
+        "aes128",
+        ciphertext_path,
+        decrypted_path,
+        key_path,
+        "--mode",
+        "gcm-stream",
+        "--segment-size",
+        "16",
+        "--raw",
+    ])
+}
+
+#[test]
+fn round_trip_with_container() {
+    let plaintext_path = concat!(integration_test_data_prefix!(), "in.60_bytes.txt");
+    let ciphertext_path = concat!(integration_test_data_prefix!(), "stream.sym");
+    let decrypted_path = concat!(integration_test_data_prefix!(), "stream.out.txt");
+    let key_path = concat!(integration_test_data_prefix!(), "aes256.key");
+
+    helper_symciph(&[
+        "encrypt",
+        "aes256",
+        plaintext_path,
+        ciphertext_path,
+        key_path,
+        "--mode",
+        "gcm-stream",
+        "--segment-size",
+        "20",
+    ]);
+    let output = helper_symciph(&["decrypt", ciphertext_path, decrypted_path, key_path]);
+
+    assert!(output.stderr.is_empty());
+
+    let left = read(decrypted_path).unwrap();
+    let right = read(plaintext_path).unwrap();
+
+    assert_eq!(left, right);
+
+    remove_file(ciphertext_path).unwrap();
+    remove_file(decrypted_path).unwrap();
+}
+
+#[test]
+fn every_segment_carries_a_tag() {
+    let plaintext_path = concat!(integration_test_data_prefix!(), "in.60_bytes.txt");
+    let ciphertext_path = concat!(integration_test_data_prefix!(), "stream.tags.bin");
+    let decrypted_path = concat!(integration_test_data_prefix!(), "stream.tags.out.txt");
+    let key_path = concat!(integration_test_data_prefix!(), "aes128.key");
+
+    helper_encrypt(plaintext_path, ciphertext_path, key_path);
+
+    let left = read(ciphertext_path).unwrap().len();
+    let right = NONCE_LEN + 60 + 4 * 16;
+
+    assert_eq!(left, right);
+
+    assert!(helper_decrypt(ciphertext_path, decrypted_path, key_path)
+        .stderr
+        .is_empty());
+
+    let left = read(decrypted_path).unwrap();
+    let right = read(plaintext_path).unwrap();
+
+    assert_eq!(left, right);
+
+    remove_file(ciphertext_path).unwrap();
+    remove_file(decrypted_path).unwrap();
+}
+
+#[test]
+fn empty_input_is_one_final_segment() {
+    let plaintext_path = concat!(integration_test_data_prefix!(), "in.empty.txt");
+    let ciphertext_path = concat!(integration_test_data_prefix!(), "stream.empty.bin");
+    let decrypted_path = concat!(integration_test_data_prefix!(), "stream.empty.out.txt");
+    let key_path = concat!(integration_test_data_prefix!(), "aes128.key");
+
+    helper_encrypt(plaintext_path, ciphertext_path, key_path);
+
+    let left = read(ciphertext_path).unwrap().len();
+    let right = NONCE_LEN + 16;
+
+    assert_eq!(left, right);
+
+    assert!(helper_decrypt(ciphertext_path, decrypted_path, key_path)
+        .stderr
+        .is_empty());
+    assert!(read(decrypted_path).unwrap().is_empty());
+
+    // Without its final segment the stream is rejected
+    write(
+        ciphertext_path,
+        &read(ciphertext_path).unwrap()[..NONCE_LEN],
+    )
+    .unwrap();
+
+    assert!(String::from_utf8_lossy(
+        &helper_decrypt(ciphertext_path, decrypted_path, key_path).stderr
+    )
+    .contains("Segment authentication failed"));
+
+    remove_file(ciphertext_path).unwrap();
+    remove_file(decrypted_path).unwrap();
+}
+
+#[test]
+fn truncated_stream_is_rejected() {
+    let plaintext_path = concat!(integration_test_data_prefix!(), "in.60_bytes.txt");
+    let ciphertext_path = concat!(integration_test_data_prefix!(), "stream.truncated.bin");
+    let decrypted_path = concat!(integration_test_data_prefix!(), "stream.truncated.out.txt");
+    let key_path = concat!(integration_test_data_prefix!(), "aes128.key");
+
+    helper_encrypt(plaintext_path, ciphertext_path, key_path);
+
+    // Cutting at a segment boundary leaves only valid segments, none of them marked last
+    let ciphertext = read(ciphertext_path).unwrap();
+    write(
+        ciphertext_path,
+        &ciphertext[..NONCE_LEN + 2 * SEALED_SEGMENT_LEN],
+    )
+    .unwrap();
+
+    let output = helper_decrypt(ciphertext_path, decrypted_path, key_path);
+
+    assert!(String::from_utf8_lossy(&output.stderr).contains("truncated"));
+
+    // Segments verified before the failure have already been released
+    let left = read(decrypted_path).unwrap();
+    let right = &read(plaintext_path).unwrap()[..SEGMENT_SIZE];
+
+    assert_eq!(left, right);
+
+    remove_file(ciphertext_path).unwrap();
+    remove_file(decrypted_path).unwrap();
+}
+
+#[test]
+fn reordered_segments_are_rejected() {
+    let plaintext_path = concat!(integration_test_data_prefix!(), "in.60_bytes.txt");
+    let ciphertext_path = concat!(integration_test_data_prefix!(), "stream.reordered.bin");
+    let decrypted_path = concat!(integration_test_data_prefix!(), "stream.reordered.out.txt");
+    let key_path = concat!(integration_test_data_prefix!(), "aes128.key");
+
+    helper_encrypt(plaintext_path, ciphertext_path, key_path);
+
+    let ciphertext = read(ciphertext_path).unwrap();
+    let (prefix, segments) = ciphertext.split_at(NONCE_LEN);
+    let (first, rest) = segments.split_at(SEALED_SEGMENT_LEN);
+    let (second, rest) = rest.split_at(SEALED_SEGMENT_LEN);
+    write(ciphertext_path, [prefix, second, first, rest].concat()).unwrap();
+
+    let output = helper_decrypt(ciphertext_path, decrypted_path, key_path);
+
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Segment authentication failed"));
+    assert!(read(decrypted_path).unwrap().is_empty());
+
+    remove_file(ciphertext_path).unwrap();
+    remove_file(decrypted_path).unwrap();
+}
+
+#[test]
+fn duplicated_segment_is_rejected() {
+    let plaintext_path = concat!(integration_test_data_prefix!(), "in.60_bytes.txt");
+    let ciphertext_path = concat!(integration_test_data_prefix!(), "stream.duplicated.bin");
+    let decrypted_path = concat!(integration_test_data_prefix!(), "stream.duplicated.out.txt");
+    let key_path = concat!(integration_test_data_prefix!(), "aes128.key");
+
+    helper_encrypt(plaintext_path, ciphertext_path, key_path);
+
+    let ciphertext = read(ciphertext_path).unwrap();
+    let (prefix, segments) = ciphertext.split_at(NONCE_LEN);
+    let (first, rest) = segments.split_at(SEALED_SEGMENT_LEN);
+    write(ciphertext_path, [prefix, first, first, rest].concat()).unwrap();
+
+    assert!(String::from_utf8_lossy(
+        &helper_decrypt(ciphertext_path, decrypted_path, key_path).stderr
+    )
+    .contains("Segment authentication failed"));
+
+    remove_file(ciphertext_path).unwrap();
+    remove_file(decrypted_path).unwrap();
+}
+
+// One-byte segments exhaust the 32-bit segment counter past 4 GiB, a sparse file gets there cheaply
+#[test]
+fn input_longer_than_segment_counter_is_rejected() {
+    let plaintext_path = concat!(integration_test_data_prefix!(), "stream.sparse.bin");
+    let ciphertext_path = concat!(integration_test_data_prefix!(), "stream.sparse.sym");
+
+    File::create(plaintext_path)
+        .unwrap()
+        .set_len((u64::from(u32::MAX) + 1) + 1)
+        .unwrap();
+
+    let output = helper_symciph(&[
+        "encrypt",
+        "aes128",
+        plaintext_path,
+        ciphertext_path,
+        concat!(integration_test_data_prefix!(), "aes128.key"),
+        "--mode",
+        "gcm-stream",
+        "--segment-size",
+        "1",
+    ]);
+
+    remove_file(plaintext_path).unwrap();
+
+    let left = (output.status.code(), Path::new(ciphertext_path).exists());
+    let right = (Some(2), false);
+
+    assert_eq!(left, right);
+}
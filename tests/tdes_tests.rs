@@ -0,0 +1,215 @@
+use std::{
+    fs::{read, remove_file, write},
+    process::{Command, Output},
+};
+
+macro_rules! integration_test_data_prefix {
+    () => {
+        "tests/data/"
+    };
+}
+
+fn helper_symciph(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_symciph"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+fn helper_encrypt_ecb(key_path: &str, ciphertext_path: &str) -> Vec<u8> {
+    let plaintext_path = concat!(integration_test_data_prefix!(), "in.16_bytes.txt");
+
+    helper_symciph(&[
+        "encrypt",
+        "tdes",
+        plaintext_path,
+        ciphertext_path,
+        key_path,
+        "--padding",
+        "none",
+        "--raw",
+    ]);
+
+    let ciphertext = read(ciphertext_path).unwrap();
+    remove_file(ciphertext_path).unwrap();
+
+    ciphertext
+}
+
+// Reference ciphertexts from an independent SP 800-67 TDEA implementation
+#[test]
+fn ede_matches_reference() {
+    let key_path = concat!(integration_test_data_prefix!(), "3des.key");
+    let two_key_path = concat!(integration_test_data_prefix!(), "tdes.two.key");
+    let ciphertext_path = concat!(integration_test_data_prefix!(), "tdes.ecb.bin");
+
+    let left = helper_encrypt_ecb(key_path, ciphertext_path);
+    let right = [
+        0xDB, 0x1E, 0x3F, 0x5B, 0xF9, 0x3F, 0xDD, 0x40, 0xB5, 0x34, 0x0B, 0x1F, 0x5A, 0x35, 0x10,
+        0x25,
+    ];
+
+    assert_eq!(left, right);
+
+    write(two_key_path, &read(key_path).unwrap()[..16]).unwrap();
+
+    let left = helper_encrypt_ecb(two_key_path, ciphertext_path);
+    let right = [
+        0x99, 0xC3, 0xE8, 0xDE, 0x7E, 0x67, 0x31, 0xD1, 0xE2, 0x4F, 0x06, 0x51, 0xCD, 0xC5, 0x79,
+        0x41,
+    ];
+
+    assert_eq!(left, right);
+
+    remove_file(two_key_path).unwrap();
+}
+
+#[test]
+fn single_key_option_matches_des() {
+    let key_path = concat!(integration_test_data_prefix!(), "des.key");
+    let tdes_ciphertext_path = concat!(integration_test_data_prefix!(), "tdes.single.bin");
+    let des_ciphertext_path = concat!(integration_test_data_prefix!(), "tdes.single.des.bin");
+    let plaintext_path = concat!(integration_test_data_prefix!(), "in.16_bytes.txt");
+
+    helper_symciph(&[
+        "encrypt",
+        "des",
+        plaintext_path,
+        des_ciphertext_path,
+        key_path,
+        "--padding",
+        "none",
+        "--raw",
+    ]);
+
+    let left = helper_encrypt_ecb(key_path, tdes_ciphertext_path);
+    let right = read(des_ciphertext_path).unwrap();
+
+    assert_eq!(left, right);
+
+    remove_file(des_ciphertext_path).unwrap();
+}
+
+#[test]
+fn keying_option_sets_key_length() {
+    let key_path = concat!(integration_test_data_prefix!(), "tdes.option2.key");
+    let plaintext_path = concat!(integration_test_data_prefix!(), "in.60_bytes.txt");
+    let ciphertext_path = concat!(integration_test_data_prefix!(), "tdes.option2.sym");
+    let decrypted_path = concat!(integration_test_data_prefix!(), "tdes.option2.out.txt");
+
+    helper_symciph(&["generate-key", "tdes", key_path, "--keying-option", "2"]);
+
+    let left = read(key_path).unwrap().len();
+    let right = 16;
+
+    assert_eq!(left, right);
+
+    helper_symciph(&[
+        "encrypt",
+        "tdes",
+        plaintext_path,
+        ciphertext_path,
+        key_path,
+        "--mode",
+        "cbc",
+    ]);
+    helper_symciph(&["decrypt", ciphertext_path, decrypted_path, key_path]);
+
+    let left = read(decrypted_path).unwrap();
+    let right = read(plaintext_path).unwrap();
+
+    assert_eq!(left, right);
+
+    remove_file(key_path).unwrap();
+    remove_file(ciphertext_path).unwrap();
+    remove_file(decrypted_path).unwrap();
+}
+
+// Both fixtures were written before DES followed FIPS 46-3 and 3DES used EDE
+#[test]
+fn legacy_ciphertexts_still_decrypt() {
+    let plaintext_path = concat!(integration_test_data_prefix!(), "in.60_bytes.txt");
+    let container_path = concat!(integration_test_data_prefix!(), "legacy.tdes.cbc.sym");
+    let raw_path = concat!(integration_test_data_prefix!(), "legacy.des.ctr.raw.bin");
+    let decrypted_path = concat!(integration_test_data_prefix!(), "legacy.out.txt");
+
+    helper_symciph(&[
+        "decrypt",
+        container_path,
+        decrypted_path,
+        concat!(integration_test_data_prefix!(), "3des.key"),
+    ]);
+
+    let left = read(decrypted_path).unwrap();
+    let right = read(plaintext_path).unwrap();
+
+    assert_eq!(left, right);
+
+    helper_symciph(&[
+        "decrypt",
+        "des-legacy",
+        raw_path,
+        decrypted_path,
+        concat!(integration_test_data_prefix!(), "des.key"),
+        "--mode",
+        "ctr",
+        "--raw",
+    ]);
+
+    let left = read(decrypted_path).unwrap();
+    let right = read(plaintext_path).unwrap();
+
+    assert_eq!(left, right);
+
+    remove_file(decrypted_path).unwrap();
+}
+
+#[test]
+fn legacy_ciphers_do_not_encrypt() {
+    let plaintext_path = concat!(integration_test_data_prefix!(), "in.60_bytes.txt");
+    let ciphertext_path = concat!(integration_test_data_prefix!(), "legacy.refused.sym");
+
+    let output = helper_symciph(&[
+        "encrypt",
+        "tdes-legacy-eee",
+        plaintext_path,
+        ciphertext_path,
+        concat!(integration_test_data_prefix!(), "3des.key"),
+    ]);
+
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Legacy ciphers"));
+    assert!(read(ciphertext_path).is_err());
+}
+
+// The default tag is cut to the 8-byte 3DES block, so no --tag-len is needed
+#[test]
+fn eax_with_default_options() {
+    let plaintext_path = concat!(integration_test_data_prefix!(), "in.60_bytes.txt");
+    let key_path = concat!(integration_test_data_prefix!(), "3des.key");
+    let ciphertext_path = concat!(integration_test_data_prefix!(), "tdes.eax.sym");
+    let decrypted_path = concat!(integration_test_data_prefix!(), "tdes.eax.out.txt");
+
+    let output = helper_symciph(&[
+        "encrypt",
+        "tdes",
+        plaintext_path,
+        ciphertext_path,
+        key_path,
+        "--mode",
+        "eax",
+    ]);
+
+    assert!(output.status.success());
+
+    let output = helper_symciph(&["decrypt", ciphertext_path, decrypted_path, key_path]);
+
+    assert!(output.status.success());
+
+    let left = read(decrypted_path).unwrap();
+    let right = read(plaintext_path).unwrap();
+
+    assert_eq!(left, right);
+
+    remove_file(ciphertext_path).unwrap();
+    remove_file(decrypted_path).unwrap();
+}
@@ -1,40 +1,109 @@
-use super::{DesCipher, TripleDesCipher, NUM_STEPS};
+use crypto_primitives::block_cipher::BlockCipher;
+use des::{check_key, same_key};
 
-impl TripleDesCipher {
-    pub fn new(key_bytes: &[[u8; 8]; NUM_STEPS]) -> Self {
-        let mut step_vec: Vec<DesCipher> = Vec::with_capacity(NUM_STEPS);
+use super::{DesCipher, KeyError, TripleDesCipher, NUM_STEPS};
 
-        for key in key_bytes {
-            step_vec.push(DesCipher::new(key));
-        }
+const DES_KEY_LENGTH_BUG: &str =
+    "🙀🧨 3DES step key not 8 bytes long. This was not supposed to happen.";
+
+impl BlockCipher for TripleDesCipher {
+    const BLOCK_SIZE: usize = DesCipher::BLOCK_SIZE;
+    const KEY_SIZE: usize = NUM_STEPS * DesCipher::KEY_SIZE;
 
-        let steps = match step_vec.try_into() {
-            Ok(steps) => steps,
-            Err(_) => panic!("3DES: Key expansion failed. This was not supposed to happen."),
-        };
+    type KeyError = KeyError;
 
-        Self { steps }
+    fn new(key: &[u8]) -> Result<Self, KeyError> {
+        TripleDesCipher::new(key)
     }
 
-    pub fn encrypt(&self, plaintext_block: &[u8; 8]) -> [u8; 8] {
-        let mut ciphertext_block = *plaintext_block;
+    fn encrypt_block(&self, block: &mut [u8]) {
+        let [first, second, third] = &self.steps;
 
-        for step in &self.steps {
-            ciphertext_block = step.encrypt(&ciphertext_block);
+        first.encrypt_block(block);
+
+        match self.legacy_eee {
+            true => second.encrypt_block(block),
+            false => second.decrypt_block(block),
         }
 
-        ciphertext_block
+        third.encrypt_block(block);
+    }
+
+    fn decrypt_block(&self, block: &mut [u8]) {
+        let [first, second, third] = &self.steps;
+
+        third.decrypt_block(block);
+
+        match self.legacy_eee {
+            true => second.decrypt_block(block),
+            false => second.encrypt_block(block),
+        }
+
+        first.decrypt_block(block);
+    }
+}
+
+impl TripleDesCipher {
+    // Encrypt-decrypt-encrypt as in NIST SP 800-67, keying option 1 takes three keys,
+    // option 2 two with K3 = K1 and option 3 one, which makes it single DES
+    pub fn new(key_bytes: &[u8]) -> Result<Self, KeyError> {
+        let keys = expand_keying_option(key_bytes)?;
+
+        Ok(Self {
+            steps: keys.map(|key| DesCipher::new(&key).expect(DES_KEY_LENGTH_BUG)),
+            legacy_eee: false,
+        })
     }
 
-    pub fn decrypt(&self, ciphertext_block: &[u8; 8]) -> [u8; 8] {
-        let mut plaintext_block = *ciphertext_block;
+    pub fn new_checked(key_bytes: &[u8]) -> Result<Self, KeyError> {
+        let keys = step_keys(key_bytes)?;
+
+        for (step, key) in keys.iter().enumerate() {
+            check_key(key).map_err(|des_error| KeyError::Des(step, des_error))?;
+        }
 
-        for step in self.steps.iter().rev() {
-            plaintext_block = step.decrypt(&plaintext_block);
+        if keys
+            .windows(2)
+            .any(|adjacent_keys| same_key(&adjacent_keys[0], &adjacent_keys[1]))
+        {
+            return Err(KeyError::Degenerate);
         }
 
-        plaintext_block
+        Self::new(key_bytes)
+    }
+
+    // Three legacy DES encryptions in a row, only to read ciphertexts written before EDE
+    pub fn new_legacy_eee(key_bytes: &[u8]) -> Result<Self, KeyError> {
+        let keys: [[u8; 8]; NUM_STEPS] = step_keys(key_bytes)?
+            .try_into()
+            .map_err(|_| KeyError::Length(key_bytes.len()))?;
+
+        Ok(Self {
+            steps: keys.map(|key| DesCipher::new_legacy(&key).expect(DES_KEY_LENGTH_BUG)),
+            legacy_eee: true,
+        })
+    }
+}
+
+fn expand_keying_option(key_bytes: &[u8]) -> Result<[[u8; 8]; NUM_STEPS], KeyError> {
+    match step_keys(key_bytes)?.as_slice() {
+        [key] => Ok([*key; NUM_STEPS]),
+        [first_key, second_key] => Ok([*first_key, *second_key, *first_key]),
+        [first_key, second_key, third_key] => Ok([*first_key, *second_key, *third_key]),
+        _ => Err(KeyError::Length(key_bytes.len())),
+    }
+}
+
+fn step_keys(key_bytes: &[u8]) -> Result<Vec<[u8; 8]>, KeyError> {
+    if key_bytes.is_empty() || !key_bytes.len().is_multiple_of(8) || key_bytes.len() > NUM_STEPS * 8
+    {
+        return Err(KeyError::Length(key_bytes.len()));
     }
+
+    Ok(key_bytes
+        .chunks_exact(8)
+        .map(|key| key.try_into().expect(DES_KEY_LENGTH_BUG))
+        .collect())
 }
 
 #[cfg(test)]
@@ -48,13 +117,29 @@ mod tests {
     ];
 
     const PLAINTEXT: [u8; 8] = [0x03, 0x4C, 0x65, 0x52, 0x8D, 0x32, 0x4D, 0x4F];
-    const CIPHERTEXT: [u8; 8] = [0x07, 0x19, 0x64, 0x46, 0x99, 0x33, 0x19, 0x1B];
+    const CIPHERTEXT: [u8; 8] = [0x7B, 0xE6, 0x7A, 0x30, 0xA9, 0x9F, 0xF2, 0x7E];
+    const TWO_KEY_CIPHERTEXT: [u8; 8] = [0xCC, 0x6C, 0xF7, 0x5C, 0xAD, 0x4B, 0x8F, 0x40];
+    const LEGACY_EEE_CIPHERTEXT: [u8; 8] = [0x07, 0x19, 0x64, 0x46, 0x99, 0x33, 0x19, 0x1B];
+
+    fn helper_encrypt(cipher: &impl BlockCipher, block: &[u8; 8]) -> [u8; 8] {
+        let mut block = *block;
+        cipher.encrypt_block(&mut block);
+
+        block
+    }
+
+    fn helper_decrypt(cipher: &impl BlockCipher, block: &[u8; 8]) -> [u8; 8] {
+        let mut block = *block;
+        cipher.decrypt_block(&mut block);
+
+        block
+    }
 
     #[test]
     fn encrypt() {
-        let tdes = TripleDesCipher::new(&KEYS);
+        let tdes = TripleDesCipher::new(KEYS.as_flattened()).unwrap();
 
-        let left = tdes.encrypt(&PLAINTEXT);
+        let left = helper_encrypt(&tdes, &PLAINTEXT);
         let right = CIPHERTEXT;
 
         assert_eq!(left, right);
@@ -62,19 +147,119 @@ mod tests {
 
     #[test]
     fn decrypt() {
-        let tdes = TripleDesCipher::new(&KEYS);
+        let tdes = TripleDesCipher::new(KEYS.as_flattened()).unwrap();
+
+        let left = helper_decrypt(&tdes, &CIPHERTEXT);
+        let right = PLAINTEXT;
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn two_key_option() {
+        let tdes = TripleDesCipher::new_checked(KEYS[..2].as_flattened()).unwrap();
+
+        let left = helper_encrypt(&tdes, &PLAINTEXT);
+        let right = TWO_KEY_CIPHERTEXT;
+
+        assert_eq!(left, right);
+    }
 
-        let left = tdes.decrypt(&CIPHERTEXT);
+    #[test]
+    fn single_key_option_is_des() {
+        let tdes = TripleDesCipher::new_checked(KEYS[..1].as_flattened()).unwrap();
+
+        let left = helper_encrypt(&tdes, &PLAINTEXT);
+        let right = helper_encrypt(&DesCipher::new(&KEYS[0]).unwrap(), &PLAINTEXT);
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn legacy_eee_encrypt() {
+        let tdes = TripleDesCipher::new_legacy_eee(KEYS.as_flattened()).unwrap();
+
+        let left = helper_encrypt(&tdes, &PLAINTEXT);
+        let right = LEGACY_EEE_CIPHERTEXT;
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn legacy_eee_decrypt() {
+        let tdes = TripleDesCipher::new_legacy_eee(KEYS.as_flattened()).unwrap();
+
+        let left = helper_decrypt(&tdes, &LEGACY_EEE_CIPHERTEXT);
         let right = PLAINTEXT;
 
         assert_eq!(left, right);
     }
 
+    #[test]
+    fn checked_keys_accepted() {
+        assert!(TripleDesCipher::new_checked(KEYS.as_flattened()).is_ok());
+    }
+
+    #[test]
+    fn degenerate_keys_rejected() {
+        let keys = [
+            KEYS[0],
+            [0x73, 0x49, 0xF3, 0x37, 0xD7, 0x0D, 0x41, 0x38],
+            KEYS[2],
+        ];
+
+        let left = TripleDesCipher::new_checked(keys.as_flattened()).err();
+        let right = Some(KeyError::Degenerate);
+
+        assert_eq!(left, right);
+
+        let keys = [KEYS[0], KEYS[1], KEYS[1]];
+
+        let left = TripleDesCipher::new_checked(keys.as_flattened()).err();
+        let right = Some(KeyError::Degenerate);
+
+        assert_eq!(left, right);
+
+        let left = TripleDesCipher::new_checked([KEYS[1], KEYS[1]].as_flattened()).err();
+        let right = Some(KeyError::Degenerate);
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn weak_step_key_rejected() {
+        let keys = [
+            KEYS[0],
+            KEYS[1],
+            [0xE0, 0xE0, 0xE0, 0xE0, 0xF1, 0xF1, 0xF1, 0xF1],
+        ];
+
+        let left = TripleDesCipher::new_checked(keys.as_flattened()).err();
+        let right = Some(KeyError::Des(2, des::KeyError::Weak));
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn wrong_key_length_rejected() {
+        for key_len in [0, 7, 20, 32] {
+            let left = TripleDesCipher::new(&vec![0x13; key_len]).err();
+            let right = Some(KeyError::Length(key_len));
+
+            assert_eq!(left, right);
+        }
+
+        let left = TripleDesCipher::new_legacy_eee(KEYS[..2].as_flattened()).err();
+        let right = Some(KeyError::Length(16));
+
+        assert_eq!(left, right);
+    }
+
     #[test]
     fn encrypt_and_decrypt() {
-        let tdes = TripleDesCipher::new(&KEYS);
+        let tdes = TripleDesCipher::new(KEYS.as_flattened()).unwrap();
 
-        let left = tdes.decrypt(&tdes.encrypt(&PLAINTEXT));
+        let left = helper_decrypt(&tdes, &helper_encrypt(&tdes, &PLAINTEXT));
         let right = PLAINTEXT;
 
         assert_eq!(left, right);
@@ -1,9 +1,34 @@
 mod cipher;
 
+use std::fmt::{self, Display, Formatter};
+
 use des::DesCipher;
 
 const NUM_STEPS: usize = 3;
 
 pub struct TripleDesCipher {
     steps: [DesCipher; NUM_STEPS],
+    legacy_eee: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyError {
+    Length(usize),
+    Des(usize, des::KeyError),
+    Degenerate,
 }
+
+impl Display for KeyError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Length(len) => write!(f, "3DES key is {} bytes long instead of 8, 16 or 24", len),
+            Self::Des(step, des_error) => write!(f, "3DES key {}: {}", step + 1, des_error),
+            Self::Degenerate => write!(
+                f,
+                "degenerate 3DES key, adjacent keys are equal so it collapses to single DES"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for KeyError {}